@@ -6,9 +6,24 @@ use tauri::{Emitter, Manager};
 
 use crate::platform::*;
 use crate::rainscape::*;
+use crate::target_window::{self, TargetCandidate, TargetWindowStatus};
+use crate::recording::{self, RecordingSummary, ReplayStatus};
 use crate::types::*;
+use crate::void_mask::{self, VoidMaskData};
+use crate::diagnostics;
+use crate::docks;
+use crate::interaction::{self, InteractionModifier, InteractionStatus};
+use crate::layout::{self, Remembered};
+use crate::monitor_identity;
+use crate::monitor_state::MonitorState;
+use crate::obstacles::{self, Obstacle, ObstacleDraft, StaticGeometry};
+use crate::pointer;
+use crate::poll_scheduler::{self, PollStats};
+use crate::privacy::{self, TitlePrivacy};
+use crate::rain_zones::{self, RainZone, RainZoneDraft};
+use crate::surfaces::{self, HandoffDrop, WindowMode};
 use crate::window_mgmt::*;
-use crate::{RAIN_PAUSED, PAUSE_MENU_ITEM, RAINSCAPER_VISIBLE, OVERLAY_HEALTH, BACKGROUND_HEALTH, LAST_TRAY_POSITION};
+use crate::{RAIN_PAUSED, PAUSE_MENU_ITEM, RAINSCAPER_VISIBLE, WINDOW_HEALTH, LAST_TRAY_POSITION, MONITOR_STATES};
 
 #[tauri::command]
 pub fn log_message(message: String) {
@@ -166,6 +181,7 @@ pub fn update_rainscape_param(path: String, value: serde_json::Value, app: tauri
     if path == "system.paused" {
         if let Some(paused) = value.as_bool() {
             RAIN_PAUSED.store(paused, Ordering::Relaxed);
+            poll_scheduler::wake();
             if let Ok(guard) = PAUSE_MENU_ITEM.lock() {
                 if let Some(ref item) = *guard {
                     let _ = item.set_text(if paused { "Resume" } else { "Pause" });
@@ -175,6 +191,12 @@ pub fn update_rainscape_param(path: String, value: serde_json::Value, app: tauri
         }
     }
 
+    if path == "system.umbrellaCursor" {
+        if let Some(enabled) = value.as_bool() {
+            pointer::set_enabled(&app, enabled);
+        }
+    }
+
     if let Err(e) = app.emit("update-rainscape-param", serde_json::json!({ "path": path, "value": value })) {
         log::error!("[ParamSync] Failed to emit {}: {}", path, e);
     }
@@ -188,14 +210,8 @@ pub fn trigger_audio_start(app: tauri::AppHandle) {
 #[tauri::command]
 pub fn heartbeat(window: tauri::Window) {
     let label = window.label();
-    let health_mutex = match label {
-        "overlay" => &OVERLAY_HEALTH,
-        "background" => &BACKGROUND_HEALTH,
-        _ => return,
-    };
-
-    let mut guard = health_mutex.lock().unwrap();
-    if let Some(health) = guard.as_mut() {
+    let mut guard = WINDOW_HEALTH.lock().unwrap();
+    if let Some(health) = guard.get_mut(label) {
         let now = std::time::Instant::now();
         if !health.init_complete {
             health.init_complete = true;
@@ -210,21 +226,13 @@ pub fn heartbeat(window: tauri::Window) {
 pub fn show_rainscaper(app: tauri::AppHandle, tray_x: i32, tray_y: i32) -> Result<(), String> {
     log::info!("[Rainscaper] Show requested at tray position ({}, {})", tray_x, tray_y);
 
-    let (panel_w, panel_h) = app.get_webview_window("rainscaper")
-        .and_then(|w| {
-            let size = w.outer_size().ok()?;
-            let s = w.current_monitor().ok()??.scale_factor();
-            Some(((size.width as f64 / s) as i32, (size.height as f64 / s) as i32))
-        })
-        .unwrap_or((400, 500));
+    let (panel_w, panel_h) = scaled_panel_size(&app);
 
-    let config = load_panel_config(&app);
-    let detached = config.as_ref().and_then(|c| c.detached).unwrap_or(false);
+    let detached = load_panel_config(&app).and_then(|c| c.detached).unwrap_or(false);
 
     let (x, y) = if detached {
         // Detached: restore saved position (fall back to tray position if none saved)
-        config.and_then(|c| c.x.zip(c.y))
-            .map(|(sx, sy)| clamp_panel_to_work_area(&app, sx, sy, panel_w, panel_h))
+        saved_panel_position(&app, panel_w, panel_h)
             .unwrap_or_else(|| calculate_rainscaper_position(&app, tray_x, tray_y, panel_w, panel_h))
     } else {
         calculate_rainscaper_position(&app, tray_x, tray_y, panel_w, panel_h)
@@ -260,16 +268,9 @@ pub fn show_rainscaper(app: tauri::AppHandle, tray_x: i32, tray_y: i32) -> Resul
 pub fn hide_rainscaper(app: tauri::AppHandle) -> Result<(), String> {
     log::info!("[Rainscaper] Hide requested");
     if let Some(window) = app.get_webview_window("rainscaper") {
-        if let Ok(pos) = window.outer_position() {
-            let scale = window.current_monitor()
-                .ok().flatten()
-                .map(|m| m.scale_factor())
-                .unwrap_or(1.0);
-            let mut config = load_panel_config(&app).unwrap_or_default();
-            config.x = Some((pos.x as f64 / scale) as i32);
-            config.y = Some((pos.y as f64 / scale) as i32);
-            save_panel_config(&app, &config);
-            log::info!("[Rainscaper] Saved logical position ({}, {})", config.x.unwrap(), config.y.unwrap());
+        if let Some((x, y, w, h)) = logical_frame(&window) {
+            remember_position(&app, Remembered::Panel, x, y, w, h);
+            log::info!("[Rainscaper] Saved logical position ({}, {})", x, y);
         }
         window.set_ignore_cursor_events(true).ok();
         log::info!("[Rainscaper] Calling window.hide()");
@@ -296,52 +297,22 @@ pub fn toggle_rainscaper(app: tauri::AppHandle, tray_x: i32, tray_y: i32) -> Res
 #[tauri::command]
 pub fn resize_rainscaper(app: tauri::AppHandle, width: f64, height: f64) -> Result<(), String> {
     if let Some(window) = app.get_webview_window("rainscaper") {
-        use tauri::{LogicalSize, PhysicalPosition};
-
-        window.set_resizable(true).ok();
-        window.set_size(LogicalSize::new(width, height))
-            .map_err(|e| format!("Failed to resize: {}", e))?;
-        window.set_resizable(false).ok();
-
-        if let (Ok(pos), Some(monitor)) = (window.outer_position(), window.current_monitor().ok().flatten()) {
-            let scale = monitor.scale_factor();
-            let mon_size = monitor.size();
-            let mon_pos = monitor.position();
-
-            let work_area = get_monitor_work_area(
-                mon_pos.x, mon_pos.y, mon_size.width, mon_size.height
-            );
-
-            let phys_width = (width * scale) as i32;
-            let phys_height = (height * scale) as i32;
-
-            let mut new_x = pos.x;
-            let mut new_y = pos.y;
-            let mut moved = false;
-
-            let work_bottom = work_area.y + work_area.height as i32;
-            if new_y + phys_height > work_bottom {
-                new_y = (work_bottom - phys_height).max(work_area.y);
-                moved = true;
-            }
-
-            let work_right = work_area.x + work_area.width as i32;
-            if new_x + phys_width > work_right {
-                new_x = (work_right - phys_width).max(work_area.x);
-                moved = true;
-            }
-
-            if moved {
-                window.set_position(PhysicalPosition::new(new_x, new_y)).ok();
-                log::info!("[Rainscaper] Repositioned to ({}, {}) to stay in work area", new_x, new_y);
-            }
-        }
-
+        resize_panel(&app, &window, width, height)?;
         log::info!("[Rainscaper] Resized to {}x{}", width, height);
     }
     Ok(())
 }
 
+#[tauri::command]
+pub fn get_ui_scale(app: tauri::AppHandle) -> Option<f64> {
+    saved_ui_scale(&app)
+}
+
+#[tauri::command]
+pub fn set_ui_scale(app: tauri::AppHandle, scale: f64) -> Result<f64, String> {
+    apply_ui_scale(&app, scale)
+}
+
 #[tauri::command]
 pub fn get_panel_detached(app: tauri::AppHandle) -> bool {
     load_panel_config(&app)
@@ -368,33 +339,13 @@ pub fn snap_panel_to_tray(app: tauri::AppHandle) -> Result<(), String> {
         log::warn!("[Rainscaper] snap_panel_to_tray: no tray position recorded yet");
     }
 
-    let (panel_w, panel_h) = app.get_webview_window("rainscaper")
-        .and_then(|w| {
-            let size = w.outer_size().ok()?;
-            let s = w.current_monitor().ok()??.scale_factor();
-            Some(((size.width as f64 / s) as i32, (size.height as f64 / s) as i32))
-        })
-        .unwrap_or((400, 500));
+    let (panel_w, panel_h) = scaled_panel_size(&app);
 
     let (x, y) = if tx == 0 && ty == 0 {
         // No tray click yet — fall back to saved position, then primary work area bottom-right
-        load_panel_config(&app)
-            .and_then(|c| c.x.zip(c.y))
-            .map(|(sx, sy)| clamp_panel_to_work_area(&app, sx, sy, panel_w, panel_h))
+        saved_panel_position(&app, panel_w, panel_h)
             .unwrap_or_else(|| {
-                let monitors: Vec<tauri::Monitor> = app.available_monitors().unwrap_or_default().into_iter().collect();
-                if monitors.is_empty() { return (100, 100); }
-                let idx = get_primary_monitor_index(&monitors);
-                let mon = &monitors[idx];
-                let pos = mon.position();
-                let size = mon.size();
-                let scale = mon.scale_factor();
-                let work = get_monitor_work_area(pos.x, pos.y, size.width, size.height);
-                let wx = (work.x as f64 / scale) as i32;
-                let wy = (work.y as f64 / scale) as i32;
-                let ww = (work.width as f64 / scale) as i32;
-                let wh = (work.height as f64 / scale) as i32;
-                (wx + ww - panel_w - 12, wy + wh - panel_h - 12)
+                layout::panel_home(&describe_monitors(&app), panel_w, panel_h).unwrap_or((100, 100))
             })
     } else {
         calculate_rainscaper_position(&app, tx, ty, panel_w, panel_h)
@@ -402,9 +353,8 @@ pub fn snap_panel_to_tray(app: tauri::AppHandle) -> Result<(), String> {
 
     let mut config = load_panel_config(&app).unwrap_or_default();
     config.detached = Some(false);
-    config.x = Some(x);
-    config.y = Some(y);
     save_panel_config(&app, &config);
+    remember_position(&app, Remembered::Panel, x, y, panel_w, panel_h);
 
     if let Some(window) = app.get_webview_window("rainscaper") {
         window.set_position(tauri::Position::Logical(tauri::LogicalPosition::new(x as f64, y as f64)))
@@ -425,6 +375,13 @@ pub fn show_help_window(app: tauri::AppHandle) -> Result<(), String> {
     log::info!("[Help] Show requested");
 
     if let Some(window) = app.get_webview_window("help") {
+        // The display layout may have changed while it was hidden
+        let restorable = !window.is_maximized().unwrap_or(false);
+        if let Some((_, _, w, h)) = logical_frame(&window).filter(|_| restorable) {
+            if let Some((x, y)) = recall_position(&app, Remembered::Help, w, h) {
+                window.set_position(tauri::LogicalPosition::new(x as f64, y as f64)).ok();
+            }
+        }
         window.unminimize().ok();
         window.show().map_err(|e| format!("Failed to show help: {}", e))?;
         window.set_focus().map_err(|e| format!("Failed to focus help: {}", e))?;
@@ -439,6 +396,12 @@ pub fn show_help_window(app: tauri::AppHandle) -> Result<(), String> {
 #[tauri::command]
 pub fn hide_help_window(app: tauri::AppHandle) -> Result<(), String> {
     if let Some(window) = app.get_webview_window("help") {
+        // A maximized window's spot isn't where the user left it
+        if !window.is_maximized().unwrap_or(false) {
+            if let Some((x, y, w, h)) = logical_frame(&window) {
+                remember_position(&app, Remembered::Help, x, y, w, h);
+            }
+        }
         window.hide().map_err(|e| format!("Failed to hide help: {}", e))?;
         log::info!("[Help] Hidden");
     }
@@ -464,52 +427,19 @@ pub fn center_help_window(app: tauri::AppHandle) -> Result<(), String> {
     let win_size = window.outer_size()
         .map_err(|e| format!("Failed to get help size: {}", e))?;
 
-    let monitors: Vec<tauri::Monitor> = app
-        .available_monitors()
-        .unwrap_or_default()
-        .into_iter()
-        .collect();
-
-    if monitors.is_empty() {
+    let Some((area, clamped)) = layout::center_help_window(&describe_monitors(&app), win_size.width, win_size.height) else {
         return Ok(());
-    }
+    };
 
-    let idx = get_primary_monitor_index(&monitors);
-    let mon = &monitors[idx];
-    let mon_pos = mon.position();
-    let mon_size = mon.size();
-    let work = get_monitor_work_area(
-        mon_pos.x, mon_pos.y, mon_size.width, mon_size.height,
-    );
-    let scale = mon.scale_factor();
-
-    let work_w = work.width as f64 / scale;
-    let work_h = work.height as f64 / scale;
-    let work_x = work.x as f64 / scale;
-    let work_y = work.y as f64 / scale;
-
-    let mut win_w = win_size.width as f64 / scale;
-    let mut win_h = win_size.height as f64 / scale;
-
-    const MARGIN: f64 = 16.0;
-    let max_w = (work_w - MARGIN * 2.0).max(200.0);
-    let max_h = (work_h - MARGIN * 2.0).max(150.0);
-    let needs_resize = win_w > max_w || win_h > max_h;
-    if needs_resize {
-        win_w = win_w.min(max_w);
-        win_h = win_h.min(max_h);
-        window.set_size(tauri::LogicalSize::new(win_w, win_h))
+    if clamped {
+        window.set_size(tauri::LogicalSize::new(area.width, area.height))
             .map_err(|e| format!("Failed to clamp help size: {}", e))?;
     }
-
-    let pos_x = work_x + (work_w - win_w) / 2.0;
-    let pos_y = work_y + (work_h - win_h) / 2.0;
-
-    window.set_position(tauri::LogicalPosition::new(pos_x, pos_y))
+    window.set_position(tauri::LogicalPosition::new(area.x, area.y))
         .map_err(|e| format!("Failed to position help: {}", e))?;
 
     log::info!("[Help] Centered on primary monitor: ({:.0}, {:.0}), size {:.0}x{:.0}{}",
-        pos_x, pos_y, win_w, win_h, if needs_resize { " (clamped)" } else { "" });
+        area.x, area.y, area.width, area.height, if clamped { " (clamped)" } else { "" });
     Ok(())
 }
 
@@ -650,12 +580,7 @@ pub fn save_user_themes(app: tauri::AppHandle, data: serde_json::Value) -> Resul
 
 #[tauri::command]
 pub fn get_display_info(window: tauri::Window) -> Result<DisplayInfo, String> {
-    let label = window.label();
-    let index: usize = label
-        .strip_prefix("overlay-")
-        .or_else(|| label.strip_prefix("background-"))
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(0);
+    let index = surfaces::monitor_index(window.label()).unwrap_or(0);
 
     if let Ok(Some(monitor)) = window.current_monitor() {
         let pos = monitor.position();
@@ -669,8 +594,13 @@ pub fn get_display_info(window: tauri::Window) -> Result<DisplayInfo, String> {
             index, size.width, size.height, pos.x, pos.y, work_area.height
         );
 
+        let identity = monitor_identity::identify(std::slice::from_ref(&monitor))
+            .pop()
+            .unwrap_or_default();
+
         Ok(DisplayInfo {
             index,
+            identity,
             bounds: Bounds {
                 x: pos.x,
                 y: pos.y,
@@ -692,8 +622,9 @@ pub fn get_all_displays(app: tauri::AppHandle) -> Result<Vec<DisplayInfo>, Strin
         .available_monitors()
         .map_err(|e| format!("Failed to get monitors: {}", e))?;
 
+    let identities = monitor_identity::identify(&monitors);
     let mut displays = Vec::new();
-    for (index, monitor) in monitors.iter().enumerate() {
+    for ((index, monitor), identity) in monitors.iter().enumerate().zip(identities) {
         let pos = monitor.position();
         let size = monitor.size();
         let scale = monitor.scale_factor();
@@ -702,6 +633,7 @@ pub fn get_all_displays(app: tauri::AppHandle) -> Result<Vec<DisplayInfo>, Strin
 
         displays.push(DisplayInfo {
             index,
+            identity,
             bounds: Bounds {
                 x: pos.x,
                 y: pos.y,
@@ -718,6 +650,237 @@ pub fn get_all_displays(app: tauri::AppHandle) -> Result<Vec<DisplayInfo>, Strin
     Ok(displays)
 }
 
+// Latest per-monitor occupancy (same payload as the monitor-state event)
+#[tauri::command]
+pub fn get_monitor_states() -> Vec<MonitorState> {
+    MONITOR_STATES.lock().map(|s| s.clone()).unwrap_or_default()
+}
+
+// Window detection scheduler diagnostics (mode, interval, effective rate)
+#[tauri::command]
+pub fn get_window_poll_stats() -> PollStats {
+    poll_scheduler::stats()
+}
+
+#[tauri::command]
+pub fn get_title_privacy() -> TitlePrivacy {
+    privacy::mode()
+}
+
+#[tauri::command]
+pub fn set_title_privacy(app: tauri::AppHandle, mode: TitlePrivacy) -> Result<(), String> {
+    privacy::apply(&app, mode);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_rain_on_docks() -> bool {
+    docks::rain_on_docks()
+}
+
+#[tauri::command]
+pub fn set_rain_on_docks(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    docks::apply(&app, enabled);
+    Ok(())
+}
+
+// Mega vs per-monitor overlay/background windows (see surfaces.rs)
+
+#[tauri::command]
+pub fn get_window_mode() -> WindowMode {
+    surfaces::mode()
+}
+
+#[tauri::command]
+pub fn set_window_mode(app: tauri::AppHandle, mode: WindowMode) -> Result<(), String> {
+    surfaces::apply(&app, mode);
+    Ok(())
+}
+
+/// The calling window's own monitor as a one-monitor desktop; None for mega windows
+#[tauri::command]
+pub fn get_surface_desktop(app: tauri::AppHandle, window: tauri::Window) -> Result<Option<VirtualDesktop>, String> {
+    let desktop = get_virtual_desktop(app)?;
+    Ok(surfaces::surface_desktop(window.label(), &desktop))
+}
+
+#[tauri::command]
+pub fn handoff_drops(app: tauri::AppHandle, window: tauri::Window, drops: Vec<HandoffDrop>) {
+    surfaces::hand_off(&app, window.label(), drops);
+}
+
+// Target-window mode (see target_window.rs)
+
+#[tauri::command]
+pub fn list_target_windows() -> Vec<TargetCandidate> {
+    target_window::candidates()
+}
+
+#[tauri::command]
+pub fn get_target_window() -> Option<TargetWindowStatus> {
+    target_window::status()
+}
+
+#[tauri::command]
+pub fn set_target_window(app: tauri::AppHandle, id: u64) -> Result<TargetWindowStatus, String> {
+    target_window::select(&app, id)
+}
+
+#[tauri::command]
+pub fn clear_target_window(app: tauri::AppHandle) {
+    target_window::clear(&app);
+}
+
+// Interaction mode (see interaction.rs); the backend owns overlay click-through
+
+#[tauri::command]
+pub fn get_interaction_status() -> Option<InteractionStatus> {
+    interaction::status()
+}
+
+#[tauri::command]
+pub fn start_interaction(app: tauri::AppHandle, seconds: Option<u64>) -> Result<InteractionStatus, String> {
+    interaction::start_timed(&app, seconds.unwrap_or(interaction::DEFAULT_TIMED_SECS))
+}
+
+#[tauri::command]
+pub fn stop_interaction(app: tauri::AppHandle) {
+    interaction::stop(&app, "stopped by user");
+}
+
+#[tauri::command]
+pub fn set_interaction_modifier(app: tauri::AppHandle, modifier: InteractionModifier) -> InteractionStatus {
+    interaction::set_modifier(&app, modifier)
+}
+
+/// Void mask + spawn rows for the renderer's grid, from get_virtual_desktop's regions.
+#[tauri::command]
+pub fn get_void_mask(app: tauri::AppHandle, window: tauri::Window, grid_scale: f64) -> Result<VoidMaskData, String> {
+    if !(grid_scale > 0.0 && grid_scale <= 1.0) {
+        return Err(format!("Invalid grid scale {}", grid_scale));
+    }
+    let desktop = get_virtual_desktop(app.clone())?;
+    let desktop = surfaces::surface_desktop(window.label(), &desktop).unwrap_or(desktop);
+    let zones = rain_zones::projected(&app, &desktop);
+    Ok(void_mask::rasterize(&desktop, &zones, grid_scale))
+}
+
+// Custom static obstacles for the current monitor layout (see obstacles.rs)
+
+#[tauri::command]
+pub fn list_obstacles(app: tauri::AppHandle) -> Result<Vec<Obstacle>, String> {
+    let desktop = get_virtual_desktop(app.clone())?;
+    Ok(obstacles::list(&app, &desktop))
+}
+
+#[tauri::command]
+pub fn add_obstacle(app: tauri::AppHandle, obstacle: ObstacleDraft) -> Result<Obstacle, String> {
+    let desktop = get_virtual_desktop(app.clone())?;
+    obstacles::add(&app, &desktop, obstacle)
+}
+
+#[tauri::command]
+pub fn update_obstacle(app: tauri::AppHandle, id: u64, obstacle: ObstacleDraft) -> Result<Obstacle, String> {
+    let desktop = get_virtual_desktop(app.clone())?;
+    obstacles::update(&app, &desktop, id, obstacle)
+}
+
+#[tauri::command]
+pub fn delete_obstacle(app: tauri::AppHandle, id: u64) -> Result<(), String> {
+    let desktop = get_virtual_desktop(app.clone())?;
+    obstacles::delete(&app, &desktop, id)
+}
+
+#[tauri::command]
+pub fn get_static_geometry(app: tauri::AppHandle) -> Result<StaticGeometry, String> {
+    let desktop = get_virtual_desktop(app.clone())?;
+    Ok(obstacles::static_geometry(&app, &desktop))
+}
+
+// Rain-free / rain-only zones, stored in settings relative to a monitor (see rain_zones.rs)
+
+#[tauri::command]
+pub fn list_rain_zones(app: tauri::AppHandle) -> Result<Vec<RainZone>, String> {
+    let desktop = get_virtual_desktop(app.clone())?;
+    Ok(rain_zones::list(&app, &desktop))
+}
+
+#[tauri::command]
+pub fn add_rain_zone(app: tauri::AppHandle, zone: RainZoneDraft) -> Result<RainZone, String> {
+    let desktop = get_virtual_desktop(app.clone())?;
+    rain_zones::add(&app, &desktop, zone)
+}
+
+#[tauri::command]
+pub fn update_rain_zone(app: tauri::AppHandle, id: u64, zone: RainZoneDraft) -> Result<RainZone, String> {
+    let desktop = get_virtual_desktop(app.clone())?;
+    rain_zones::update(&app, &desktop, id, zone)
+}
+
+#[tauri::command]
+pub fn delete_rain_zone(app: tauri::AppHandle, id: u64) -> Result<(), String> {
+    let desktop = get_virtual_desktop(app.clone())?;
+    rain_zones::delete(&app, &desktop, id)
+}
+
+// Layout diagnostics image (see diagnostics.rs). Async so window enumeration and PNG
+// encoding stay off the main thread.
+
+#[tauri::command]
+pub async fn save_diagnostics_image(app: tauri::AppHandle) -> Result<String, String> {
+    diagnostics::save(&app)
+        .map(|path| path.display().to_string())
+        .map_err(|e| format!("Failed to save diagnostics image: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_diagnostics_image(app: tauri::AppHandle) -> Result<tauri::ipc::Response, String> {
+    diagnostics::capture(&app)
+        .map(tauri::ipc::Response::new)
+        .map_err(|e| format!("Failed to render diagnostics image: {}", e))
+}
+
+// Window-detection recording/replay (see recording.rs)
+
+#[tauri::command]
+pub fn start_window_recording(app: tauri::AppHandle) -> Result<String, String> {
+    let desktop = get_virtual_desktop(app)?;
+    recording::start_recording(&desktop)
+        .map(|path| path.display().to_string())
+        .map_err(|e| format!("Failed to start recording: {}", e))
+}
+
+#[tauri::command]
+pub fn stop_window_recording() -> Option<RecordingSummary> {
+    recording::stop_recording()
+}
+
+#[tauri::command]
+pub fn start_window_replay(app: tauri::AppHandle, path: String) -> Result<ReplayStatus, String> {
+    recording::start_replay(&app, std::path::Path::new(&path))
+        .map_err(|e| format!("Failed to load recording: {}", e))
+}
+
+#[tauri::command]
+pub fn set_window_replay_playing(app: tauri::AppHandle, playing: bool) -> Result<ReplayStatus, String> {
+    recording::set_replay_playing(&app, playing)
+}
+
+#[tauri::command]
+pub fn seek_window_replay(app: tauri::AppHandle, position_ms: u64) -> Result<ReplayStatus, String> {
+    recording::seek_replay(&app, position_ms)
+}
+
+#[tauri::command]
+pub fn stop_window_replay(app: tauri::AppHandle) {
+    recording::stop_replay(&app);
+}
+
+#[tauri::command]
+pub fn get_window_replay_status() -> Option<ReplayStatus> {
+    recording::replay_status()
+}
+
 #[tauri::command]
 pub fn get_system_specs(state: tauri::State<'_, AppState>) -> SystemSpecs {
     state.system_specs.clone()
@@ -748,93 +911,24 @@ pub fn collect_system_specs() -> SystemSpecs {
 
 #[tauri::command]
 pub fn get_virtual_desktop(app: tauri::AppHandle) -> Result<VirtualDesktop, String> {
-    let monitors: Vec<tauri::Monitor> = app
-        .available_monitors()
-        .map_err(|e| format!("Failed to get monitors: {}", e))?
-        .into_iter()
-        .collect();
-
-    if monitors.is_empty() {
-        return Err("No monitors found".to_string());
-    }
-
-    let mut x_min = i32::MAX;
-    let mut y_min = i32::MAX;
-    let mut x_max = i32::MIN;
-    let mut y_max = i32::MIN;
-
-    for monitor in &monitors {
-        let pos = monitor.position();
-        let size = monitor.size();
-
-        x_min = x_min.min(pos.x);
-        y_min = y_min.min(pos.y);
-        x_max = x_max.max(pos.x + size.width as i32);
-        y_max = y_max.max(pos.y + size.height as i32);
-    }
-
-    let primary_index = get_primary_monitor_index(&monitors);
-    let primary_scale = monitors[primary_index].scale_factor();
-
-    let to_logical = |v: i32| -> i32 { (v as f64 / primary_scale).round() as i32 };
-    let to_logical_u = |v: u32| -> u32 { (v as f64 / primary_scale).round() as u32 };
-
-    let logical_x_min = to_logical(x_min);
-    let logical_y_min = to_logical(y_min);
-    let logical_x_max = to_logical(x_max);
-    let logical_y_max = to_logical(y_max);
-    let total_width = (logical_x_max - logical_x_min) as u32;
-    let total_height = (logical_y_max - logical_y_min) as u32;
+    let desktop = layout::virtual_desktop(&describe_monitors(&app))
+        .ok_or_else(|| "No monitors found".to_string())?;
 
     log::info!(
-        "[VirtualDesktop] Physical bbox: ({}, {})-->({}, {}), scale={}, logical bbox: ({}, {}) {}x{}",
-        x_min, y_min, x_max, y_max, primary_scale,
-        logical_x_min, logical_y_min, total_width, total_height
+        "[VirtualDesktop] Logical bbox: ({}, {}) {}x{}, primary scale={}",
+        desktop.origin_x, desktop.origin_y, desktop.width, desktop.height, desktop.primary_scale_factor
     );
-
-    let mut regions = Vec::new();
-    for (index, monitor) in monitors.iter().enumerate() {
-        let pos = monitor.position();
-        let size = monitor.size();
-        let scale = monitor.scale_factor();
-
-        let work_area = get_monitor_work_area(pos.x, pos.y, size.width, size.height);
-
-        let rel_x = (to_logical(pos.x) - logical_x_min) as u32;
-        let rel_y = (to_logical(pos.y) - logical_y_min) as u32;
-        let rel_work_x = (to_logical(work_area.x) - logical_x_min) as u32;
-        let rel_work_y = (to_logical(work_area.y) - logical_y_min) as u32;
-
-        regions.push(MonitorRegion {
-            index,
-            x: rel_x,
-            y: rel_y,
-            width: to_logical_u(size.width),
-            height: to_logical_u(size.height),
-            work_x: rel_work_x,
-            work_y: rel_work_y,
-            work_width: to_logical_u(work_area.width),
-            work_height: to_logical_u(work_area.height),
-            scale_factor: scale,
-            refresh_rate: get_monitor_refresh_rate(pos.x, pos.y, size.width, size.height),
-        });
-
+    for m in &desktop.monitors {
+        let p = &m.physical;
         log::info!(
-            "[VirtualDesktop] Monitor {}{}: rel({}, {}) {}x{} work_height={} (logical)",
-            index,
-            if index == primary_index { " (primary)" } else { "" },
-            rel_x, rel_y, to_logical_u(size.width), to_logical_u(size.height),
-            to_logical_u(work_area.height)
+            "[VirtualDesktop] Monitor {}{}: physical ({}, {}) {}x{} @ {:.0}% -> rel({}, {}) {}x{} work_height={} (logical), {} [{}], rotation {}",
+            m.index,
+            if m.index == desktop.primary_index { " (primary)" } else { "" },
+            p.x, p.y, p.width, p.height, m.scale_factor * 100.0,
+            m.x, m.y, m.width, m.height, m.work_height,
+            m.identity.name, m.identity.id, m.identity.rotation
         );
     }
 
-    Ok(VirtualDesktop {
-        origin_x: logical_x_min,
-        origin_y: logical_y_min,
-        width: total_width,
-        height: total_height,
-        monitors: regions,
-        primary_index,
-        primary_scale_factor: primary_scale,
-    })
+    Ok(desktop)
 }
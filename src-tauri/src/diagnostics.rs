@@ -0,0 +1,315 @@
+// Diagnostics image: a PNG of the virtual desktop as the backend sees it, for collision
+// bugs the log lines can't explain. Monitors (primary in gold) with their work areas, the
+// void mask hatched (gaps between monitors and rain zones), every detected window in green,
+// and every window the detector filtered out dashed in orange, labelled with its rule.
+// Saved under logs/diagnostics or handed back as PNG bytes.
+
+use std::io::Cursor;
+use std::path::PathBuf;
+use chrono::Local;
+use image::{ImageFormat, Rgba, RgbaImage};
+
+use crate::docks;
+use crate::geometry;
+use crate::logging::log_dir;
+use crate::rain_zones::{self, ProjectedZone};
+use crate::types::VirtualDesktop;
+use crate::void_mask;
+use crate::window_detector::{self, Bounds, SkippedWindow, WindowInfo};
+
+// Longest image side in pixels; bigger desktops are drawn scaled down
+const MAX_SIDE: f64 = 2400.0;
+// Pixels per font dot (glyphs are 3x5 dots)
+const TEXT_SCALE: i64 = 2;
+// Longest label, in characters
+const MAX_LABEL: usize = 48;
+
+const BACKGROUND: Rgba<u8> = Rgba([14, 14, 18, 255]);
+const MONITOR_FILL: Rgba<u8> = Rgba([34, 44, 62, 255]);
+const WORK_FILL: Rgba<u8> = Rgba([46, 62, 88, 255]);
+const WORK_EDGE: Rgba<u8> = Rgba([110, 150, 200, 255]);
+const MONITOR_EDGE: Rgba<u8> = Rgba([230, 230, 230, 255]);
+const PRIMARY_EDGE: Rgba<u8> = Rgba([255, 200, 40, 255]);
+const VOID_HATCH: Rgba<u8> = Rgba([110, 36, 44, 255]);
+const WINDOW_EDGE: Rgba<u8> = Rgba([80, 220, 120, 255]);
+const SKIPPED_EDGE: Rgba<u8> = Rgba([255, 150, 40, 255]);
+const LABEL_BACKING: Rgba<u8> = Rgba([0, 0, 0, 255]);
+
+// Pixel rect: left, top, right, bottom (exclusive)
+type PxRect = (i64, i64, i64, i64);
+
+struct Canvas {
+    image: RgbaImage,
+    scale: f64,
+    origin_x: i32,
+    origin_y: i32,
+}
+
+impl Canvas {
+    /// Absolute logical rect (virtual-desktop coords) to pixels
+    fn rect(&self, x: i32, y: i32, width: u32, height: u32) -> PxRect {
+        let px = |v: i32, origin: i32| ((v - origin) as f64 * self.scale).floor() as i64;
+        (
+            px(x, self.origin_x),
+            px(y, self.origin_y),
+            px(x + width as i32, self.origin_x).max(px(x, self.origin_x) + 1),
+            px(y + height as i32, self.origin_y).max(px(y, self.origin_y) + 1),
+        )
+    }
+
+    fn bounds(&self, b: &Bounds) -> PxRect {
+        self.rect(b.x, b.y, b.width, b.height)
+    }
+
+    fn put(&mut self, x: i64, y: i64, color: Rgba<u8>) {
+        if x >= 0 && y >= 0 && x < self.image.width() as i64 && y < self.image.height() as i64 {
+            self.image.put_pixel(x as u32, y as u32, color);
+        }
+    }
+
+    fn blend(&mut self, x: i64, y: i64, color: Rgba<u8>, alpha: f32) {
+        if x < 0 || y < 0 || x >= self.image.width() as i64 || y >= self.image.height() as i64 {
+            return;
+        }
+        let pixel = self.image.get_pixel_mut(x as u32, y as u32);
+        for c in 0..3 {
+            pixel.0[c] = (pixel.0[c] as f32 * (1.0 - alpha) + color.0[c] as f32 * alpha).round() as u8;
+        }
+    }
+
+    fn fill(&mut self, (left, top, right, bottom): PxRect, color: Rgba<u8>) {
+        for y in top..bottom {
+            for x in left..right {
+                self.put(x, y, color);
+            }
+        }
+    }
+
+    fn tint(&mut self, (left, top, right, bottom): PxRect, color: Rgba<u8>, alpha: f32) {
+        for y in top..bottom {
+            for x in left..right {
+                self.blend(x, y, color, alpha);
+            }
+        }
+    }
+
+    /// Outline drawn inside the rect; dashed outlines alternate 6px on, 4px off
+    fn outline(&mut self, (left, top, right, bottom): PxRect, color: Rgba<u8>, thickness: i64, dashed: bool) {
+        let on = |i: i64| !dashed || i.rem_euclid(10) < 6;
+        for t in 0..thickness {
+            for x in left..right {
+                if on(x) {
+                    self.put(x, top + t, color);
+                    self.put(x, bottom - 1 - t, color);
+                }
+            }
+            for y in top..bottom {
+                if on(y) {
+                    self.put(left + t, y, color);
+                    self.put(right - 1 - t, y, color);
+                }
+            }
+        }
+    }
+
+    /// Text on a dark backing box, top-left at pixel (x, y)
+    fn text(&mut self, x: i64, y: i64, text: &str, color: Rgba<u8>) {
+        let text: Vec<char> = text.chars().take(MAX_LABEL).collect();
+        let advance = 4 * TEXT_SCALE;
+        let width = text.len() as i64 * advance + TEXT_SCALE;
+        let height = 7 * TEXT_SCALE;
+        self.tint((x, y, x + width, y + height), LABEL_BACKING, 0.7);
+
+        for (i, &c) in text.iter().enumerate() {
+            let left = x + TEXT_SCALE + i as i64 * advance;
+            for (row, bits) in glyph(c).iter().enumerate() {
+                for col in 0..3 {
+                    if bits & (4 >> col) == 0 {
+                        continue;
+                    }
+                    let dot_x = left + col * TEXT_SCALE;
+                    let dot_y = y + TEXT_SCALE + row as i64 * TEXT_SCALE;
+                    self.fill((dot_x, dot_y, dot_x + TEXT_SCALE, dot_y + TEXT_SCALE), color);
+                }
+            }
+        }
+    }
+}
+
+// 3x5 bitmap font: one byte per row, bit 2 = left column. Letters are drawn uppercase;
+// anything without a glyph shows as '?'.
+fn glyph(c: char) -> [u8; 5] {
+    match c.to_ascii_uppercase() {
+        'A' => [2, 5, 7, 5, 5],
+        'B' => [6, 5, 6, 5, 6],
+        'C' => [3, 4, 4, 4, 3],
+        'D' => [6, 5, 5, 5, 6],
+        'E' => [7, 4, 6, 4, 7],
+        'F' => [7, 4, 6, 4, 4],
+        'G' => [3, 4, 5, 5, 3],
+        'H' => [5, 5, 7, 5, 5],
+        'I' => [7, 2, 2, 2, 7],
+        'J' => [1, 1, 1, 5, 2],
+        'K' => [5, 5, 6, 5, 5],
+        'L' => [4, 4, 4, 4, 7],
+        'M' => [5, 7, 7, 5, 5],
+        'N' => [6, 5, 5, 5, 5],
+        'O' => [2, 5, 5, 5, 2],
+        'P' => [6, 5, 6, 4, 4],
+        'Q' => [2, 5, 5, 6, 3],
+        'R' => [6, 5, 6, 5, 5],
+        'S' => [3, 4, 2, 1, 6],
+        'T' => [7, 2, 2, 2, 2],
+        'U' => [5, 5, 5, 5, 7],
+        'V' => [5, 5, 5, 5, 2],
+        'W' => [5, 5, 7, 7, 5],
+        'X' => [5, 5, 2, 5, 5],
+        'Y' => [5, 5, 2, 2, 2],
+        'Z' => [7, 1, 2, 4, 7],
+        '0' => [7, 5, 5, 5, 7],
+        '1' => [2, 6, 2, 2, 7],
+        '2' => [6, 1, 2, 4, 7],
+        '3' => [6, 1, 2, 1, 6],
+        '4' => [5, 5, 7, 1, 1],
+        '5' => [7, 4, 6, 1, 6],
+        '6' => [3, 4, 7, 5, 7],
+        '7' => [7, 1, 2, 2, 2],
+        '8' => [7, 5, 7, 5, 7],
+        '9' => [7, 5, 7, 1, 6],
+        ' ' => [0, 0, 0, 0, 0],
+        '-' => [0, 0, 7, 0, 0],
+        '_' => [0, 0, 0, 0, 7],
+        '.' => [0, 0, 0, 0, 2],
+        ',' => [0, 0, 0, 2, 4],
+        ':' => [0, 2, 0, 2, 0],
+        '/' => [1, 1, 2, 4, 4],
+        '\\' => [4, 4, 2, 1, 1],
+        '(' | '[' => [1, 2, 2, 2, 1],
+        ')' | ']' => [4, 2, 2, 2, 4],
+        '#' => [5, 7, 5, 7, 5],
+        '%' => [5, 1, 2, 4, 5],
+        '@' => [7, 5, 7, 4, 3],
+        '+' => [0, 2, 7, 2, 0],
+        '=' => [0, 7, 0, 7, 0],
+        '*' => [0, 5, 2, 5, 0],
+        '\'' => [2, 2, 0, 0, 0],
+        '"' => [5, 5, 0, 0, 0],
+        '!' => [2, 2, 2, 0, 2],
+        '|' => [2, 2, 2, 2, 2],
+        _ => [6, 1, 2, 0, 2],
+    }
+}
+
+/// Draw the layout. `windows` and `skipped` must already be projected (logical pieces).
+pub(crate) fn render(
+    desktop: &VirtualDesktop,
+    zones: &[ProjectedZone],
+    windows: &[WindowInfo],
+    skipped: &[SkippedWindow],
+) -> RgbaImage {
+    let scale = (MAX_SIDE / desktop.width.max(desktop.height).max(1) as f64).min(1.0);
+
+    // The mask at image resolution: one cell per pixel
+    let (mask_width, mask_height, cells) = void_mask::build_mask(desktop, zones, scale);
+    let mut canvas = Canvas {
+        image: RgbaImage::from_pixel(mask_width.max(1), mask_height.max(1), BACKGROUND),
+        scale,
+        origin_x: desktop.origin_x,
+        origin_y: desktop.origin_y,
+    };
+
+    let region_rect = |canvas: &Canvas, x: u32, y: u32, width: u32, height: u32| {
+        canvas.rect(desktop.origin_x + x as i32, desktop.origin_y + y as i32, width, height)
+    };
+
+    for m in &desktop.monitors {
+        let monitor = region_rect(&canvas, m.x, m.y, m.width, m.height);
+        canvas.fill(monitor, MONITOR_FILL);
+        canvas.fill(region_rect(&canvas, m.work_x, m.work_y, m.work_width, m.work_height), WORK_FILL);
+    }
+
+    // Void cells as diagonal stripes over whatever is underneath
+    for (i, &cell) in cells.iter().enumerate() {
+        let (x, y) = ((i % mask_width as usize) as i64, (i / mask_width as usize) as i64);
+        if cell == 1 && (x + y).rem_euclid(12) < 4 {
+            canvas.put(x, y, VOID_HATCH);
+        }
+    }
+
+    for m in &desktop.monitors {
+        let primary = m.index == desktop.primary_index;
+        let monitor = region_rect(&canvas, m.x, m.y, m.width, m.height);
+        canvas.outline(region_rect(&canvas, m.work_x, m.work_y, m.work_width, m.work_height), WORK_EDGE, 1, false);
+        canvas.outline(monitor, if primary { PRIMARY_EDGE } else { MONITOR_EDGE }, 3, false);
+
+        let label = format!(
+            "{}: {} {}x{} @{:.0}%{}",
+            m.index, m.identity.name, m.physical.width, m.physical.height,
+            m.scale_factor * 100.0, if primary { " PRIMARY" } else { "" },
+        );
+        canvas.text(monitor.0 + 6, monitor.1 + 6, &label, if primary { PRIMARY_EDGE } else { MONITOR_EDGE });
+    }
+
+    for skip in skipped {
+        for piece in &skip.window.pieces {
+            canvas.outline(canvas.bounds(&piece.bounds), SKIPPED_EDGE, 1, true);
+        }
+        if !skip.window.pieces.is_empty() {
+            let (left, top, _, _) = canvas.bounds(&skip.window.bounds);
+            canvas.text(left + 2, top + 2, &format!("[{}] {}", skip.rule.label(), skip.window.title), SKIPPED_EDGE);
+        }
+    }
+
+    // Bottom of the stack first so the topmost window's outline and label end up on top
+    for win in windows.iter().rev() {
+        for piece in &win.pieces {
+            let rect = canvas.bounds(&piece.bounds);
+            canvas.tint(rect, WINDOW_EDGE, 0.12);
+            canvas.outline(rect, WINDOW_EDGE, 2, false);
+        }
+        if !win.pieces.is_empty() {
+            let (left, top, _, _) = canvas.bounds(&win.bounds);
+            canvas.text(left + 4, top + 4, &format!("#{} {}", win.z_order, win.title), WINDOW_EDGE);
+        }
+    }
+
+    let summary = format!("{} windows, {} filtered, {} rain zones", windows.len(), skipped.len(), zones.len());
+    let bottom = canvas.image.height() as i64;
+    canvas.text(4, bottom - 7 * TEXT_SCALE - 4, &summary, MONITOR_EDGE);
+
+    canvas.image
+}
+
+/// Detect windows now (including the filtered ones) and draw them over the current layout.
+pub(crate) fn capture(app: &tauri::AppHandle) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let desktop = crate::commands::get_virtual_desktop(app.clone())?;
+    let (mut data, mut skipped) = window_detector::get_windows_for_diagnostics()?;
+
+    let mappings = geometry::monitor_mappings(&desktop);
+    let dock_rects = docks::occluders(&data.native_docks, &mappings);
+    geometry::project_windows(&mut data.windows, &mappings, &dock_rects);
+    // Each on its own: occlusion among filtered windows means nothing
+    for skip in &mut skipped {
+        geometry::project_windows(std::slice::from_mut(&mut skip.window), &mappings, &[]);
+    }
+
+    let zones = rain_zones::projected(app, &desktop);
+    let image = render(&desktop, &zones, &data.windows, &skipped);
+
+    let mut png = Vec::new();
+    image.write_to(&mut Cursor::new(&mut png), ImageFormat::Png)?;
+    log::info!("[Diagnostics] Layout image {}x{}: {} windows, {} filtered ({} bytes)",
+        image.width(), image.height(), data.windows.len(), skipped.len(), png.len());
+    Ok(png)
+}
+
+/// Capture and write to logs/diagnostics; returns the file path.
+pub(crate) fn save(app: &tauri::AppHandle) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let png = capture(app)?;
+    let dir = log_dir().join("diagnostics");
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("Layout_{}.png", Local::now().format("%Y-%m-%d_%H-%M-%S")));
+    std::fs::write(&path, png)?;
+    log::info!("[Diagnostics] Saved {}", path.display());
+    Ok(path)
+}
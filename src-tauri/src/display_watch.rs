@@ -0,0 +1,351 @@
+// Display topology changes. The OS wakes the monitor thread when it has native notifications
+// (WM_DISPLAYCHANGE on Windows, RandR events on X11); a slow poll stays on as a safety net
+// for changes those miss (X11 scale changes, Windows DPI changes on secondary monitors).
+// Each settled change goes out as a diff keyed on monitor identity, not enumeration index.
+
+use std::sync::mpsc::{Receiver, RecvTimeoutError, Sender};
+use std::time::Duration;
+
+use crate::types::{Bounds, MonitorRegion, VirtualDesktop};
+
+/// Payload of `monitor-config-changed`
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MonitorConfigChange {
+    pub added: Vec<MonitorRegion>,
+    pub removed: Vec<MonitorRegion>,
+    /// Position or resolution changed (physical bounds)
+    pub moved: Vec<MonitorMove>,
+    pub rescaled: Vec<MonitorRescale>,
+    /// The new layout; the mega windows already cover it when this is emitted
+    pub desktop: VirtualDesktop,
+}
+
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MonitorMove {
+    pub id: String,
+    pub name: String,
+    /// Index in the new layout
+    pub index: usize,
+    pub from: Bounds,
+    pub to: Bounds,
+}
+
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MonitorRescale {
+    pub id: String,
+    pub name: String,
+    pub index: usize,
+    pub from: f64,
+    pub to: f64,
+}
+
+impl MonitorConfigChange {
+    /// One-line summary for the log
+    pub fn describe(&self) -> String {
+        let names = |regions: &[MonitorRegion]| regions.iter()
+            .map(|m| m.identity.name.clone())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let mut parts = Vec::new();
+        if !self.added.is_empty() { parts.push(format!("added [{}]", names(&self.added))); }
+        if !self.removed.is_empty() { parts.push(format!("removed [{}]", names(&self.removed))); }
+        for m in &self.moved {
+            parts.push(format!("moved {} ({}, {}) {}x{} -> ({}, {}) {}x{}",
+                m.name, m.from.x, m.from.y, m.from.width, m.from.height,
+                m.to.x, m.to.y, m.to.width, m.to.height));
+        }
+        for r in &self.rescaled {
+            parts.push(format!("rescaled {} {:.0}% -> {:.0}%", r.name, r.from * 100.0, r.to * 100.0));
+        }
+        if parts.is_empty() { "no monitor changes".to_string() } else { parts.join("; ") }
+    }
+}
+
+/// Differences between two layouts, matching monitors by identity id.
+pub(crate) fn diff(old: &VirtualDesktop, new: &VirtualDesktop) -> MonitorConfigChange {
+    let find = |monitors: &[MonitorRegion], id: &str| monitors.iter().find(|m| m.identity.id == id).cloned();
+
+    let added = new.monitors.iter()
+        .filter(|m| find(&old.monitors, &m.identity.id).is_none())
+        .cloned()
+        .collect();
+    let removed = old.monitors.iter()
+        .filter(|m| find(&new.monitors, &m.identity.id).is_none())
+        .cloned()
+        .collect();
+
+    let mut moved = Vec::new();
+    let mut rescaled = Vec::new();
+    for after in &new.monitors {
+        let Some(before) = find(&old.monitors, &after.identity.id) else { continue };
+        if before.physical != after.physical {
+            moved.push(MonitorMove {
+                id: after.identity.id.clone(),
+                name: after.identity.name.clone(),
+                index: after.index,
+                from: before.physical.clone(),
+                to: after.physical.clone(),
+            });
+        }
+        if (before.scale_factor - after.scale_factor).abs() > 0.001 {
+            rescaled.push(MonitorRescale {
+                id: after.identity.id.clone(),
+                name: after.identity.name.clone(),
+                index: after.index,
+                from: before.scale_factor,
+                to: after.scale_factor,
+            });
+        }
+    }
+
+    MonitorConfigChange { added, removed, moved, rescaled, desktop: new.clone() }
+}
+
+/// Block until a native notification arrives or `timeout` passes; several queued
+/// notifications count as one. Without a listener this is a plain sleep.
+pub(crate) fn wait(wakeups: &Receiver<()>, timeout: Duration) {
+    match wakeups.recv_timeout(timeout) {
+        Ok(()) => while wakeups.try_recv().is_ok() {},
+        Err(RecvTimeoutError::Timeout) => {}
+        Err(RecvTimeoutError::Disconnected) => std::thread::sleep(timeout),
+    }
+}
+
+/// Start the platform's display-change listener on its own thread; each notification
+/// sends on `wake`. Returns false when there is none (or it failed to start).
+pub(crate) fn spawn_native_listener(wake: Sender<()>) -> bool {
+    let (ready_tx, ready_rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        if let Err(e) = listen(wake, &ready_tx) {
+            log::warn!("[DisplayWatch] Native display notifications unavailable: {}", e);
+            let _ = ready_tx.send(false);
+        }
+    });
+    ready_rx.recv_timeout(Duration::from_secs(2)).unwrap_or(false)
+}
+
+// Hidden top-level window: WM_DISPLAYCHANGE is broadcast to top-level windows only,
+// so a message-only (HWND_MESSAGE) window would never see it
+#[cfg(target_os = "windows")]
+fn listen(wake: Sender<()>, ready: &Sender<bool>) -> Result<(), Box<dyn std::error::Error>> {
+    use std::cell::RefCell;
+    use windows::core::w;
+    use windows::Win32::Foundation::{HWND, LPARAM, LRESULT, WPARAM};
+    use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        CreateWindowExW, DefWindowProcW, DispatchMessageW, GetMessageW, RegisterClassW,
+        MSG, WINDOW_EX_STYLE, WINDOW_STYLE, WM_DISPLAYCHANGE, WM_DPICHANGED, WNDCLASSW,
+    };
+
+    thread_local! {
+        static WAKE: RefCell<Option<Sender<()>>> = const { RefCell::new(None) };
+    }
+
+    unsafe extern "system" fn wnd_proc(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+        if msg == WM_DISPLAYCHANGE || msg == WM_DPICHANGED {
+            WAKE.with(|wake| {
+                if let Some(wake) = wake.borrow().as_ref() {
+                    let _ = wake.send(());
+                }
+            });
+            return LRESULT(0);
+        }
+        unsafe { DefWindowProcW(hwnd, msg, wparam, lparam) }
+    }
+
+    WAKE.with(|slot| *slot.borrow_mut() = Some(wake));
+
+    unsafe {
+        let instance = GetModuleHandleW(None)?;
+        let class = WNDCLASSW {
+            lpfnWndProc: Some(wnd_proc),
+            hInstance: instance.into(),
+            lpszClassName: w!("RainyDeskDisplayWatch"),
+            ..Default::default()
+        };
+        if RegisterClassW(&class) == 0 {
+            return Err(windows::core::Error::from_thread().into());
+        }
+        CreateWindowExW(
+            WINDOW_EX_STYLE(0),
+            w!("RainyDeskDisplayWatch"),
+            w!("RainyDesk Display Watch"),
+            WINDOW_STYLE(0),
+            0, 0, 0, 0,
+            None, None, Some(instance.into()), None,
+        )?;
+
+        let _ = ready.send(true);
+        log::info!("[DisplayWatch] Listening for WM_DISPLAYCHANGE");
+
+        let mut msg = MSG::default();
+        while GetMessageW(&mut msg, None, 0, 0).as_bool() {
+            DispatchMessageW(&msg);
+        }
+    }
+    Ok(())
+}
+
+// RandR screen/CRTC/output notifications on a dedicated connection
+#[cfg(target_os = "linux")]
+fn listen(wake: Sender<()>, ready: &Sender<bool>) -> Result<(), Box<dyn std::error::Error>> {
+    use x11rb::connection::Connection;
+    use x11rb::protocol::randr::{ConnectionExt, NotifyMask};
+    use x11rb::protocol::Event;
+
+    let (conn, screen) = x11rb::connect(None)?;
+    let root = conn.setup().roots[screen].root;
+    conn.randr_query_version(1, 2)?.reply()?;
+    conn.randr_select_input(root, NotifyMask::SCREEN_CHANGE | NotifyMask::CRTC_CHANGE | NotifyMask::OUTPUT_CHANGE)?
+        .check()?;
+
+    let _ = ready.send(true);
+    log::info!("[DisplayWatch] Listening for RandR notifications");
+
+    loop {
+        match conn.wait_for_event()? {
+            // Receiver gone: the monitor thread has exited
+            Event::RandrScreenChangeNotify(_) | Event::RandrNotify(_) if wake.send(()).is_err() => return Ok(()),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+fn listen(_wake: Sender<()>, ready: &Sender<bool>) -> Result<(), Box<dyn std::error::Error>> {
+    let _ = ready.send(false);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor_identity::MonitorIdentity;
+
+    fn bounds(x: i32, y: i32, width: u32, height: u32) -> Bounds {
+        Bounds { x, y, width, height }
+    }
+
+    // Only the fields diff looks at: identity, physical bounds, scale
+    fn region(index: usize, id: &str, physical: Bounds, scale_factor: f64) -> MonitorRegion {
+        MonitorRegion {
+            index,
+            x: 0,
+            y: 0,
+            width: physical.width,
+            height: physical.height,
+            work_x: 0,
+            work_y: 0,
+            work_width: physical.width,
+            work_height: physical.height,
+            scale_factor,
+            refresh_rate: 60,
+            physical,
+            identity: MonitorIdentity { id: id.to_string(), name: id.to_uppercase(), ..Default::default() },
+            transform: Default::default(),
+        }
+    }
+
+    fn desktop(monitors: Vec<MonitorRegion>) -> VirtualDesktop {
+        VirtualDesktop {
+            origin_x: 0,
+            origin_y: 0,
+            width: 0,
+            height: 0,
+            monitors,
+            primary_index: 0,
+            primary_scale_factor: 1.0,
+        }
+    }
+
+    fn laptop(index: usize) -> MonitorRegion {
+        region(index, "laptop", bounds(0, 0, 1920, 1080), 1.0)
+    }
+
+    fn external(index: usize, x: i32) -> MonitorRegion {
+        region(index, "external", bounds(x, 0, 2560, 1440), 1.0)
+    }
+
+    fn ids(regions: &[MonitorRegion]) -> Vec<&str> {
+        regions.iter().map(|m| m.identity.id.as_str()).collect()
+    }
+
+    fn is_empty(change: &MonitorConfigChange) -> bool {
+        change.added.is_empty() && change.removed.is_empty() && change.moved.is_empty() && change.rescaled.is_empty()
+    }
+
+    #[test]
+    fn unchanged_layout() {
+        let layout = desktop(vec![laptop(0), external(1, 1920)]);
+        let change = diff(&layout, &layout);
+        assert!(is_empty(&change));
+        assert_eq!(change.describe(), "no monitor changes");
+    }
+
+    #[test]
+    fn enumeration_order_alone_is_no_change() {
+        let old = desktop(vec![laptop(0), external(1, 1920)]);
+        let new = desktop(vec![external(0, 1920), laptop(1)]);
+        assert!(is_empty(&diff(&old, &new)));
+    }
+
+    #[test]
+    fn added_and_removed() {
+        let undocked = desktop(vec![laptop(0)]);
+        let docked = desktop(vec![laptop(0), external(1, 1920)]);
+
+        let change = diff(&undocked, &docked);
+        assert_eq!(ids(&change.added), ["external"]);
+        assert!(change.removed.is_empty() && change.moved.is_empty());
+        assert_eq!(change.describe(), "added [EXTERNAL]");
+
+        let change = diff(&docked, &undocked);
+        assert_eq!(ids(&change.removed), ["external"]);
+        assert!(change.added.is_empty() && change.moved.is_empty());
+
+        // Same spot, different monitor: a swap, not a move
+        let swapped = desktop(vec![laptop(0), region(1, "projector", bounds(1920, 0, 2560, 1440), 1.0)]);
+        let change = diff(&docked, &swapped);
+        assert_eq!(ids(&change.added), ["projector"]);
+        assert_eq!(ids(&change.removed), ["external"]);
+        assert!(change.moved.is_empty());
+    }
+
+    #[test]
+    fn moved_by_identity() {
+        let old = desktop(vec![laptop(0), external(1, 1920)]);
+        // External moved to the left and now enumerates first
+        let new = desktop(vec![external(0, -2560), laptop(1)]);
+
+        let change = diff(&old, &new);
+        assert!(change.added.is_empty() && change.removed.is_empty() && change.rescaled.is_empty());
+        assert_eq!(change.moved.len(), 1);
+        let m = &change.moved[0];
+        assert_eq!((m.id.as_str(), m.index), ("external", 0));
+        assert_eq!(m.from, bounds(1920, 0, 2560, 1440));
+        assert_eq!(m.to, bounds(-2560, 0, 2560, 1440));
+    }
+
+    #[test]
+    fn rescaled_without_moving() {
+        let old = desktop(vec![laptop(0), external(1, 1920)]);
+        let mut scaled = laptop(0);
+        scaled.scale_factor = 1.5;
+        let new = desktop(vec![scaled, external(1, 1920)]);
+
+        let change = diff(&old, &new);
+        assert!(change.added.is_empty() && change.removed.is_empty() && change.moved.is_empty());
+        assert_eq!(change.rescaled.len(), 1);
+        let r = &change.rescaled[0];
+        assert_eq!((r.id.as_str(), r.from, r.to), ("laptop", 1.0, 1.5));
+        assert_eq!(change.describe(), "rescaled LAPTOP 100% -> 150%");
+
+        // Float noise below the threshold is ignored
+        let mut noisy = laptop(0);
+        noisy.scale_factor = 1.0004;
+        assert!(is_empty(&diff(&old, &desktop(vec![noisy, external(1, 1920)]))));
+    }
+}
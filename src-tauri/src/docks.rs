@@ -0,0 +1,126 @@
+// Taskbars, docks and panels as collision surfaces. Native dock windows (Windows
+// taskbars) are used where the detector reports them; the gap between each monitor's
+// bounds and its work area covers everything else (Linux panels, third-party appbars).
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::geometry::{split_by_monitor, subtract_all, MonitorMapping, Rect};
+use crate::settings::{load_settings, save_settings};
+use crate::window_detector::{Bounds, WindowData};
+use crate::DOCKS_MENU_ITEM;
+
+static RAIN_ON_DOCKS: AtomicBool = AtomicBool::new(false);
+
+// Thinner strips are auto-hidden taskbars peeking out or rounding slivers (logical px)
+const MIN_THICKNESS: u32 = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum DockEdge {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DockSurface {
+    pub monitor: usize,
+    /// Monitor edge the dock is attached to
+    pub edge: DockEdge,
+    /// Virtual-desktop logical coords, like window bounds
+    pub bounds: Bounds,
+    /// From a native dock window rather than the work-area difference
+    pub native: bool,
+}
+
+pub(crate) fn rain_on_docks() -> bool {
+    RAIN_ON_DOCKS.load(Ordering::Relaxed)
+}
+
+/// Apply without persisting (startup), keeping the tray check mark in sync.
+pub(crate) fn set_rain_on_docks(enabled: bool) {
+    RAIN_ON_DOCKS.store(enabled, Ordering::Relaxed);
+    if let Ok(guard) = DOCKS_MENU_ITEM.lock() {
+        if let Some(item) = guard.as_ref() {
+            let _ = item.set_checked(enabled);
+        }
+    }
+}
+
+/// Change from the panel or tray: apply, persist, log.
+pub(crate) fn apply(app: &tauri::AppHandle, enabled: bool) {
+    set_rain_on_docks(enabled);
+    let mut settings = load_settings(app);
+    settings.rain_on_docks = enabled;
+    save_settings(app, &settings);
+    log::info!("Rain on docks {}", if enabled { "enabled" } else { "disabled" });
+}
+
+// Which monitor edge a strip hugs: wide strips are top/bottom, tall ones left/right
+fn classify_edge(strip: &Rect, monitor: &Rect) -> DockEdge {
+    let strip_cx = strip.left + strip.right;
+    let strip_cy = strip.top + strip.bottom;
+    if strip.width() >= strip.height() {
+        if strip_cy < monitor.top + monitor.bottom { DockEdge::Top } else { DockEdge::Bottom }
+    } else if strip_cx < monitor.left + monitor.right {
+        DockEdge::Left
+    } else {
+        DockEdge::Right
+    }
+}
+
+fn is_thick_enough(r: &Rect) -> bool {
+    r.width().min(r.height()) >= MIN_THICKNESS
+}
+
+/// Dock surfaces per monitor in logical coords. Native rects are physical (detector).
+pub(crate) fn surfaces(native: &[Bounds], mappings: &[MonitorMapping]) -> Vec<DockSurface> {
+    let monitors: Vec<Rect> = mappings.iter().map(|m| m.physical).collect();
+    let mut docks = Vec::new();
+
+    for (i, mapping) in mappings.iter().enumerate() {
+        let native_here: Vec<Rect> = native.iter()
+            .flat_map(|b| split_by_monitor(&Rect::from_bounds(b), &monitors))
+            .filter(|&(m, _)| m == i)
+            .map(|(_, piece)| mapping.map_rect(&piece))
+            .collect();
+
+        // Work-area gap minus whatever a native dock already accounts for
+        let derived = subtract_all(mapping.logical.subtract(&mapping.work), &native_here);
+
+        let tagged = native_here.iter().map(|r| (*r, true))
+            .chain(derived.into_iter().map(|r| (r, false)));
+        for (rect, is_native) in tagged {
+            if !is_thick_enough(&rect) { continue; }
+            docks.push(DockSurface {
+                monitor: mapping.index,
+                edge: classify_edge(&rect, &mapping.logical),
+                bounds: rect.to_bounds(),
+                native: is_native,
+            });
+        }
+    }
+
+    docks
+}
+
+/// Physical rects docks cover on screen, for occlusion: native dock windows plus
+/// each monitor's work-area gap. Independent of whether rain lands on docks.
+pub(crate) fn occluders(native: &[Bounds], mappings: &[MonitorMapping]) -> Vec<Rect> {
+    native.iter()
+        .map(Rect::from_bounds)
+        .chain(mappings.iter().flat_map(|m| m.physical.subtract(&m.physical_work())))
+        .filter(|r| !r.is_empty())
+        .collect()
+}
+
+/// Fill `data.docks` from the detector's native rects, or clear it when disabled.
+pub(crate) fn project_docks(data: &mut WindowData, mappings: &[MonitorMapping]) {
+    data.docks = if rain_on_docks() && !mappings.is_empty() {
+        surfaces(&data.native_docks, mappings)
+    } else {
+        Vec::new()
+    };
+}
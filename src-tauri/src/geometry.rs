@@ -0,0 +1,787 @@
+// Pure rectangle math for collision geometry: intersection, subtraction, occlusion,
+// the mixed-DPI logical layout of monitors, and the per-monitor physical -> virtual-desktop
+// logical transform for window rects.
+
+use crate::types::{MonitorRegion, RegionTransform, VirtualDesktop};
+use crate::window_detector::{Bounds, EdgeSpan, WindowInfo, WindowPiece};
+
+/// Half-open rectangle [left, right) x [top, bottom)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Rect {
+    pub left: i32,
+    pub top: i32,
+    pub right: i32,
+    pub bottom: i32,
+}
+
+impl Rect {
+    pub(crate) fn from_xywh(x: i32, y: i32, width: u32, height: u32) -> Self {
+        Self {
+            left: x,
+            top: y,
+            right: x + width as i32,
+            bottom: y + height as i32,
+        }
+    }
+
+    pub(crate) fn from_bounds(b: &Bounds) -> Self {
+        Self::from_xywh(b.x, b.y, b.width, b.height)
+    }
+
+    pub(crate) fn to_bounds(self) -> Bounds {
+        Bounds {
+            x: self.left,
+            y: self.top,
+            width: self.width(),
+            height: self.height(),
+        }
+    }
+
+    pub(crate) fn width(&self) -> u32 {
+        (self.right - self.left).max(0) as u32
+    }
+
+    pub(crate) fn height(&self) -> u32 {
+        (self.bottom - self.top).max(0) as u32
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.right <= self.left || self.bottom <= self.top
+    }
+
+    pub(crate) fn intersect(&self, other: &Rect) -> Option<Rect> {
+        let r = Rect {
+            left: self.left.max(other.left),
+            top: self.top.max(other.top),
+            right: self.right.min(other.right),
+            bottom: self.bottom.min(other.bottom),
+        };
+        if r.is_empty() { None } else { Some(r) }
+    }
+
+    /// Split self minus other into up to 4 non-overlapping pieces.
+    /// Full-width bands above/below come first so top edges stay in one piece.
+    pub(crate) fn subtract(&self, other: &Rect) -> Vec<Rect> {
+        let Some(cut) = self.intersect(other) else { return vec![*self] };
+        let mut pieces = Vec::with_capacity(4);
+
+        if cut.top > self.top {
+            pieces.push(Rect { left: self.left, top: self.top, right: self.right, bottom: cut.top });
+        }
+        if cut.bottom < self.bottom {
+            pieces.push(Rect { left: self.left, top: cut.bottom, right: self.right, bottom: self.bottom });
+        }
+        if cut.left > self.left {
+            pieces.push(Rect { left: self.left, top: cut.top, right: cut.left, bottom: cut.bottom });
+        }
+        if cut.right < self.right {
+            pieces.push(Rect { left: cut.right, top: cut.top, right: self.right, bottom: cut.bottom });
+        }
+
+        pieces
+    }
+}
+
+// Monitor layout
+
+fn scaled(v: i32, scale: f64) -> i32 {
+    (v as f64 / scale).round() as i32
+}
+
+// Separation between two rects along each axis: positive = gap, 0 = touching, negative = overlap
+fn separation(a: &Rect, b: &Rect) -> (i32, i32) {
+    (
+        (b.left - a.right).max(a.left - b.right),
+        (b.top - a.bottom).max(a.top - b.bottom),
+    )
+}
+
+// Cross-axis start of `b` (spans are start..end; `b_len` logical) beside `a`, placed at `a_log`.
+// Shared top/bottom (or left/right) edges and centred layouts stay that way; any other offset
+// is measured in `a`'s logical units. Touching monitors keep at least 1px of shared edge.
+fn cross_start(a: (i32, i32), b: (i32, i32), a_log: (i32, i32), b_len: i32, scale: f64, touching: bool) -> i32 {
+    let ((a_start, a_end), (b_start, b_end), (a_log_start, a_log_end)) = (a, b, a_log);
+    let start = if b_start == a_start {
+        a_log_start
+    } else if b_end == a_end {
+        a_log_end - b_len
+    } else if b_start + b_end == a_start + a_end {
+        (a_log_start + a_log_end - b_len) / 2
+    } else {
+        a_log_start + scaled(b_start - a_start, scale)
+    };
+    let shares_edge = b_start < a_end && b_end > a_start;
+    if touching && shares_edge {
+        start.clamp(a_log_start - b_len + 1, a_log_end - 1)
+    } else {
+        start
+    }
+}
+
+// Logical rect for monitor `b` placed relative to already-placed monitor `a`.
+// Returns the rect and the direction it may be pushed in to resolve overlaps.
+fn place_beside(a_phys: &Rect, a_log: &Rect, a_scale: f64, b_phys: &Rect, size: (i32, i32)) -> (Rect, (i32, i32)) {
+    let (w, h) = size;
+    let (gap_x, gap_y) = separation(a_phys, b_phys);
+
+    if gap_x >= 0 && (gap_y < 0 || gap_x >= gap_y) {
+        let top = cross_start((a_phys.top, a_phys.bottom), (b_phys.top, b_phys.bottom), (a_log.top, a_log.bottom), h, a_scale, gap_x == 0);
+        if b_phys.left >= a_phys.right {
+            let left = a_log.right + scaled(gap_x, a_scale);
+            (Rect { left, top, right: left + w, bottom: top + h }, (1, 0))
+        } else {
+            let right = a_log.left - scaled(gap_x, a_scale);
+            (Rect { left: right - w, top, right, bottom: top + h }, (-1, 0))
+        }
+    } else if gap_y >= 0 {
+        let left = cross_start((a_phys.left, a_phys.right), (b_phys.left, b_phys.right), (a_log.left, a_log.right), w, a_scale, gap_y == 0);
+        if b_phys.top >= a_phys.bottom {
+            let top = a_log.bottom + scaled(gap_y, a_scale);
+            (Rect { left, top, right: left + w, bottom: top + h }, (0, 1))
+        } else {
+            let bottom = a_log.top - scaled(gap_y, a_scale);
+            (Rect { left, top: bottom - h, right: left + w, bottom }, (0, -1))
+        }
+    } else {
+        // Physically overlapping (mirrored/cloned): keep the offset, overlap is intended
+        let left = a_log.left + scaled(b_phys.left - a_phys.left, a_scale);
+        let top = a_log.top + scaled(b_phys.top - a_phys.top, a_scale);
+        (Rect { left, top, right: left + w, bottom: top + h }, (0, 0))
+    }
+}
+
+/// Logical rects for monitors with mixed scale factors. Each monitor's size is its physical
+/// size over its own scale. The anchor (primary) keeps its position; the rest are placed
+/// outward from monitors already laid out, physically touching neighbours first, so
+/// monitors that touch physically still touch logically and shared edges stay aligned.
+/// Gaps are scaled by the neighbour they are measured from.
+pub(crate) fn layout_monitors(physical: &[Rect], scales: &[f64], anchor: usize) -> Vec<Rect> {
+    let count = physical.len();
+    if count == 0 {
+        return Vec::new();
+    }
+    let anchor = anchor.min(count - 1);
+    let sizes: Vec<(i32, i32)> = (0..count)
+        .map(|i| (scaled(physical[i].width() as i32, scales[i]), scaled(physical[i].height() as i32, scales[i])))
+        .collect();
+
+    let mut logical: Vec<Option<Rect>> = vec![None; count];
+    let origin = physical[anchor];
+    logical[anchor] = Some(Rect::from_xywh(
+        scaled(origin.left, scales[anchor]),
+        scaled(origin.top, scales[anchor]),
+        sizes[anchor].0 as u32,
+        sizes[anchor].1 as u32,
+    ));
+    let mut order = vec![anchor];
+
+    while order.len() < count {
+        // Touching neighbour of the earliest placed monitor, else the closest unplaced one
+        let mut best: Option<(bool, i64, usize, usize)> = None;
+        for (rank, &a) in order.iter().enumerate() {
+            for b in (0..count).filter(|&b| logical[b].is_none()) {
+                let (gap_x, gap_y) = separation(&physical[a], &physical[b]);
+                let touching = (gap_x == 0 && gap_y < 0) || (gap_y == 0 && gap_x < 0);
+                let distance = gap_x.max(0) as i64 + gap_y.max(0) as i64;
+                let key = (!touching, if touching { rank as i64 } else { distance }, a, b);
+                match best {
+                    Some(k) if (k.0, k.1) <= (key.0, key.1) => {}
+                    _ => best = Some(key),
+                }
+            }
+        }
+        let Some((_, _, a, b)) = best else { break };
+
+        let a_log = logical[a].unwrap_or(physical[a]);
+        let (mut rect, (dx, dy)) = place_beside(&physical[a], &a_log, scales[a], &physical[b], sizes[b]);
+
+        // Mixed scales can make a monitor overlap one placed earlier on another side;
+        // push it further out in its placement direction until it's clear
+        if (dx, dy) != (0, 0) {
+            for _ in 0..count {
+                let overlap = order.iter()
+                    .filter(|&&c| { let (x, y) = separation(&physical[c], &physical[b]); x >= 0 || y >= 0 })
+                    .filter_map(|&c| logical[c].and_then(|r| r.intersect(&rect)))
+                    .map(|cut| if dx != 0 { cut.width() as i32 } else { cut.height() as i32 })
+                    .max();
+                let Some(push) = overlap else { break };
+                rect = Rect {
+                    left: rect.left + dx * push,
+                    top: rect.top + dy * push,
+                    right: rect.right + dx * push,
+                    bottom: rect.bottom + dy * push,
+                };
+            }
+        }
+
+        logical[b] = Some(rect);
+        order.push(b);
+    }
+
+    logical.into_iter().enumerate().map(|(i, r)| r.unwrap_or(physical[i])).collect()
+}
+
+impl RegionTransform {
+    pub(crate) fn to_logical(&self, x: i32, y: i32) -> (i32, i32) {
+        (self.logical_x + scaled(x - self.physical_x, self.scale),
+         self.logical_y + scaled(y - self.physical_y, self.scale))
+    }
+}
+
+/// Physical monitor rect paired with its logical rect in virtual-desktop space
+/// (absolute, same convention as VirtualDesktop::origin_x/origin_y).
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct MonitorMapping {
+    pub index: usize,
+    pub physical: Rect,
+    pub logical: Rect,
+    /// Work area in the same logical space as `logical`
+    pub work: Rect,
+}
+
+impl MonitorMapping {
+    pub(crate) fn from_region(region: &MonitorRegion, desktop: &VirtualDesktop) -> Self {
+        let p = &region.physical;
+        Self {
+            index: region.index,
+            physical: Rect::from_xywh(p.x, p.y, p.width, p.height),
+            logical: Rect::from_xywh(
+                desktop.origin_x + region.x as i32,
+                desktop.origin_y + region.y as i32,
+                region.width,
+                region.height,
+            ),
+            work: Rect::from_xywh(
+                desktop.origin_x + region.work_x as i32,
+                desktop.origin_y + region.work_y as i32,
+                region.work_width,
+                region.work_height,
+            ),
+        }
+    }
+
+    fn map_x(&self, x: i32) -> i32 {
+        let scale = self.logical.width() as f64 / self.physical.width().max(1) as f64;
+        self.logical.left + ((x - self.physical.left) as f64 * scale).round() as i32
+    }
+
+    fn map_y(&self, y: i32) -> i32 {
+        let scale = self.logical.height() as f64 / self.physical.height().max(1) as f64;
+        self.logical.top + ((y - self.physical.top) as f64 * scale).round() as i32
+    }
+
+    /// Map a physical rect through this monitor's transform. Edges are mapped
+    /// (not width/height) so pieces that touch physically still touch logically.
+    pub(crate) fn map_rect(&self, r: &Rect) -> Rect {
+        Rect {
+            left: self.map_x(r.left),
+            top: self.map_y(r.top),
+            right: self.map_x(r.right),
+            bottom: self.map_y(r.bottom),
+        }
+    }
+
+    /// Inverse of `map_rect`: a logical rect on this monitor back in physical pixels
+    pub(crate) fn unmap_rect(&self, r: &Rect) -> Rect {
+        let unmap = |v: i32, logical: i32, logical_len: u32, physical: i32, physical_len: u32| {
+            physical + ((v - logical) as f64 * physical_len as f64 / logical_len.max(1) as f64).round() as i32
+        };
+        let (l, p) = (&self.logical, &self.physical);
+        Rect {
+            left: unmap(r.left, l.left, l.width(), p.left, p.width()),
+            top: unmap(r.top, l.top, l.height(), p.top, p.height()),
+            right: unmap(r.right, l.left, l.width(), p.left, p.width()),
+            bottom: unmap(r.bottom, l.top, l.height(), p.top, p.height()),
+        }
+    }
+
+    /// The work area back in physical pixels
+    pub(crate) fn physical_work(&self) -> Rect {
+        self.unmap_rect(&self.work)
+    }
+
+    /// Whether a physical point lies on this monitor
+    pub(crate) fn contains_physical(&self, x: i32, y: i32) -> bool {
+        x >= self.physical.left && x < self.physical.right && y >= self.physical.top && y < self.physical.bottom
+    }
+
+    pub(crate) fn map_point(&self, x: i32, y: i32) -> (i32, i32) {
+        (self.map_x(x), self.map_y(y))
+    }
+
+    fn center_distance_sq(&self, r: &Rect) -> i64 {
+        let dx = (self.physical.left + self.physical.right - r.left - r.right) as i64;
+        let dy = (self.physical.top + self.physical.bottom - r.top - r.bottom) as i64;
+        dx * dx + dy * dy
+    }
+}
+
+pub(crate) fn monitor_mappings(desktop: &VirtualDesktop) -> Vec<MonitorMapping> {
+    desktop.monitors.iter()
+        .map(|region| MonitorMapping::from_region(region, desktop))
+        .collect()
+}
+
+/// Remove every cutter from a set of disjoint pieces; result stays disjoint.
+pub(crate) fn subtract_all<'a>(mut pieces: Vec<Rect>, cutters: impl IntoIterator<Item = &'a Rect>) -> Vec<Rect> {
+    for cutter in cutters {
+        if pieces.is_empty() { break; }
+        pieces = pieces.iter().flat_map(|p| p.subtract(cutter)).collect();
+    }
+    pieces
+}
+
+/// Split a physical rect into per-monitor pieces, tagged with the index into
+/// `monitors`. Monitors are de-overlapped first (mirrored/cloned displays) so no
+/// area is reported twice. Anything outside every monitor is dropped.
+pub(crate) fn split_by_monitor(rect: &Rect, monitors: &[Rect]) -> Vec<(usize, Rect)> {
+    let mut pieces = Vec::new();
+    for (i, monitor) in monitors.iter().enumerate() {
+        if let Some(inside) = rect.intersect(monitor) {
+            for piece in subtract_all(vec![inside], &monitors[..i]) {
+                pieces.push((i, piece));
+            }
+        }
+    }
+    pieces
+}
+
+/// Spans of the window's top edge that are actually uncovered, merged and sorted.
+pub(crate) fn exposed_top_spans(window: &Rect, visible: &[Rect]) -> Vec<(i32, i32)> {
+    let mut spans: Vec<(i32, i32)> = visible.iter()
+        .filter(|r| r.top == window.top)
+        .map(|r| (r.left, r.right))
+        .collect();
+    spans.sort_unstable();
+
+    let mut merged: Vec<(i32, i32)> = Vec::with_capacity(spans.len());
+    for (left, right) in spans {
+        match merged.last_mut() {
+            Some(last) if left <= last.1 => last.1 = last.1.max(right),
+            _ => merged.push((left, right)),
+        }
+    }
+    merged
+}
+
+// Merge logical top-edge spans that ended up touching at the same height
+fn merge_spans(mut spans: Vec<EdgeSpan>) -> Vec<EdgeSpan> {
+    spans.sort_unstable_by_key(|s| (s.y, s.x));
+    let mut merged: Vec<EdgeSpan> = Vec::with_capacity(spans.len());
+    for span in spans {
+        match merged.last_mut() {
+            Some(last) if last.y == span.y && span.x <= last.x + last.width as i32 => {
+                let right = (last.x + last.width as i32).max(span.x + span.width as i32);
+                last.width = (right - last.x) as u32;
+            }
+            _ => merged.push(span),
+        }
+    }
+    merged
+}
+
+/// Convert detector output (physical pixels, z-ordered with index 0 = topmost) into
+/// virtual-desktop logical coordinates. Occlusion is resolved in physical space, then
+/// each per-monitor piece goes through its own monitor's transform. `docks` (physical)
+/// sit above every window except fullscreen ones, which cover the taskbar.
+/// Callers must wait for the mappings; with none, windows are left untouched.
+pub(crate) fn project_windows(windows: &mut [WindowInfo], mappings: &[MonitorMapping], docks: &[Rect]) {
+    if mappings.is_empty() {
+        return;
+    }
+
+    let monitors: Vec<Rect> = mappings.iter().map(|m| m.physical).collect();
+    let rects: Vec<Rect> = windows.iter().map(|w| Rect::from_bounds(&w.bounds)).collect();
+
+    for (i, win) in windows.iter_mut().enumerate() {
+        let rect = rects[i];
+        let pieces = split_by_monitor(&rect, &monitors);
+
+        let mut visible_rects = Vec::new();
+        let mut exposed_top = Vec::new();
+        for &(m, piece) in &pieces {
+            let mapping = &mappings[m];
+            let mut visible = subtract_all(vec![piece], &rects[..i]);
+            if !win.is_fullscreen {
+                visible = subtract_all(visible, docks);
+            }
+            for (left, right) in exposed_top_spans(&rect, &visible) {
+                let edge = mapping.map_rect(&Rect { left, top: rect.top, right, bottom: rect.top });
+                exposed_top.push(EdgeSpan { x: edge.left, y: edge.top, width: edge.width() });
+            }
+            visible_rects.extend(visible.iter().map(|r| mapping.map_rect(r).to_bounds()));
+        }
+
+        win.pieces = pieces.iter()
+            .map(|&(m, piece)| WindowPiece {
+                monitor: mappings[m].index,
+                bounds: mappings[m].map_rect(&piece).to_bounds(),
+            })
+            .collect();
+        win.visible_rects = visible_rects;
+        win.exposed_top = merge_spans(exposed_top);
+
+        // Overall bounds: union of the logical pieces, or the nearest monitor's
+        // transform when the window sits entirely off-screen
+        win.bounds = match win.pieces.iter().map(|p| Rect::from_bounds(&p.bounds)).reduce(|a, b| Rect {
+            left: a.left.min(b.left),
+            top: a.top.min(b.top),
+            right: a.right.max(b.right),
+            bottom: a.bottom.max(b.bottom),
+        }) {
+            Some(union) => union.to_bounds(),
+            None => mappings.iter()
+                .min_by_key(|m| m.center_distance_sq(&rect))
+                .map(|m| m.map_rect(&rect).to_bounds())
+                .unwrap_or_else(|| rect.to_bounds()),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn r(x: i32, y: i32, width: u32, height: u32) -> Rect {
+        Rect::from_xywh(x, y, width, height)
+    }
+
+    fn area(pieces: &[Rect]) -> u64 {
+        pieces.iter().map(|p| p.width() as u64 * p.height() as u64).sum()
+    }
+
+    fn assert_disjoint(pieces: &[Rect]) {
+        for (i, a) in pieces.iter().enumerate() {
+            for b in &pieces[i + 1..] {
+                assert!(a.intersect(b).is_none(), "{:?} overlaps {:?}", a, b);
+            }
+        }
+    }
+
+    fn window(id: u64, rect: Rect) -> WindowInfo {
+        WindowInfo {
+            id,
+            bounds: rect.to_bounds(),
+            title: String::new(),
+            is_maximized: false,
+            z_order: 0,
+            process_name: String::new(),
+            class_name: String::new(),
+            pid: 0,
+            is_focused: false,
+            is_topmost: false,
+            is_fullscreen: false,
+            pieces: Vec::new(),
+            visible_rects: Vec::new(),
+            exposed_top: Vec::new(),
+        }
+    }
+
+    // Monitor whose logical rect is its physical rect (100%)
+    fn unscaled(index: usize, rect: Rect) -> MonitorMapping {
+        MonitorMapping { index, physical: rect, logical: rect, work: rect }
+    }
+
+    #[test]
+    fn subtract_partial_overlap() {
+        let base = r(0, 0, 100, 100);
+        let pieces = base.subtract(&r(50, 50, 100, 100));
+        assert_eq!(pieces, [r(0, 0, 100, 50), r(0, 50, 50, 50)]);
+        assert_eq!(area(&pieces), 100 * 100 - 50 * 50);
+        assert_disjoint(&pieces);
+    }
+
+    #[test]
+    fn subtract_hole_keeps_top_band_whole() {
+        let pieces = r(0, 0, 100, 100).subtract(&r(25, 25, 50, 50));
+        assert_eq!(pieces.len(), 4);
+        assert_eq!(pieces[0], r(0, 0, 100, 25));
+        assert_eq!(area(&pieces), 100 * 100 - 50 * 50);
+        assert_disjoint(&pieces);
+    }
+
+    #[test]
+    fn subtract_full_containment() {
+        assert!(r(10, 10, 20, 20).subtract(&r(0, 0, 100, 100)).is_empty());
+        assert!(r(0, 0, 100, 100).subtract(&r(0, 0, 100, 100)).is_empty());
+    }
+
+    #[test]
+    fn edge_touching_rects_do_not_intersect() {
+        let a = r(0, 0, 100, 100);
+        let right = r(100, 0, 100, 100);
+        let below = r(0, 100, 100, 100);
+        assert_eq!(a.intersect(&right), None);
+        assert_eq!(a.intersect(&below), None);
+        assert_eq!(a.subtract(&right), [a]);
+        assert_eq!(a.subtract(&below), [a]);
+        // Zero-area cutters change nothing
+        assert_eq!(a.subtract(&r(50, 0, 0, 100)), [a]);
+        assert!(r(50, 50, 0, 10).is_empty());
+    }
+
+    #[test]
+    fn subtract_all_stays_disjoint() {
+        let cutters = [r(20, 0, 20, 60), r(30, 40, 50, 20), r(0, 80, 100, 40)];
+        let pieces = subtract_all(vec![r(0, 0, 100, 100)], &cutters);
+        assert_disjoint(&pieces);
+        // 10000 - 1200 (first) - 1000 (second, 200 shared with first) - 2000 (third)
+        assert_eq!(area(&pieces), 10_000 - 1_200 - 800 - 2_000);
+        for p in &pieces {
+            assert!(cutters.iter().all(|c| p.intersect(c).is_none()));
+        }
+    }
+
+    #[test]
+    fn exposed_top_under_partly_covering_window() {
+        let window = r(0, 100, 300, 200);
+        // Covered in the middle by a window above it
+        let visible = subtract_all(vec![window], &[r(100, 50, 100, 100)]);
+        assert_eq!(exposed_top_spans(&window, &visible), [(0, 100), (200, 300)]);
+
+        // Covered from the left: one span on the right
+        let visible = subtract_all(vec![window], &[r(-50, 0, 150, 400)]);
+        assert_eq!(exposed_top_spans(&window, &visible), [(100, 300)]);
+
+        // Fully covered top edge, visible lower part
+        let visible = subtract_all(vec![window], &[r(0, 0, 300, 150)]);
+        assert!(exposed_top_spans(&window, &visible).is_empty());
+    }
+
+    #[test]
+    fn exposed_top_merges_adjacent_spans() {
+        let window = r(0, 0, 300, 100);
+        let visible = [r(0, 0, 100, 10), r(100, 0, 50, 10), r(200, 0, 100, 10), r(150, 5, 50, 5)];
+        assert_eq!(exposed_top_spans(&window, &visible), [(0, 150), (200, 300)]);
+    }
+
+    #[test]
+    fn split_clips_to_monitors() {
+        let monitors = [r(0, 0, 1920, 1080), r(1920, 0, 1920, 1080)];
+        let pieces = split_by_monitor(&r(1800, 1000, 400, 200), &monitors);
+        assert_eq!(pieces, [(0, r(1800, 1000, 120, 80)), (1, r(1920, 1000, 280, 80))]);
+
+        // Off every monitor: nothing
+        assert!(split_by_monitor(&r(0, 2000, 100, 100), &monitors).is_empty());
+
+        // Mirrored monitors: the overlap is only reported once
+        let mirrored = [r(0, 0, 1920, 1080), r(0, 0, 1920, 1080)];
+        assert_eq!(split_by_monitor(&r(10, 10, 100, 100), &mirrored), [(0, r(10, 10, 100, 100))]);
+    }
+
+    #[test]
+    fn stacked_windows_occlude_in_z_order() {
+        let mappings = [unscaled(0, r(0, 0, 1000, 1000))];
+        // Index 0 is topmost
+        let mut windows = vec![
+            window(1, r(100, 100, 200, 200)),
+            window(2, r(200, 50, 200, 200)),
+            window(3, r(0, 0, 500, 500)),
+        ];
+        project_windows(&mut windows, &mappings, &[]);
+
+        assert_eq!(windows[0].visible_rects, [r(100, 100, 200, 200).to_bounds()]);
+        assert_eq!(windows[0].exposed_top.len(), 1);
+
+        // Top-left corner under the topmost window
+        assert_eq!(area(&windows[1].visible_rects.iter().map(Rect::from_bounds).collect::<Vec<_>>()), 200 * 200 - 100 * 150);
+        assert_eq!(windows[1].exposed_top.len(), 1);
+        assert_eq!((windows[1].exposed_top[0].x, windows[1].exposed_top[0].width), (200, 200));
+
+        // Bottom window: everything above minus both
+        let bottom: Vec<Rect> = windows[2].visible_rects.iter().map(Rect::from_bounds).collect();
+        assert_disjoint(&bottom);
+        let covered = area(&subtract_all(vec![r(0, 0, 500, 500)], &[r(100, 100, 200, 200), r(200, 50, 200, 200)]));
+        assert_eq!(area(&bottom), covered);
+        assert_eq!(windows[2].exposed_top.len(), 1);
+        assert_eq!(windows[2].exposed_top[0].width, 500);
+    }
+
+    #[test]
+    fn projection_clips_and_scales_per_monitor() {
+        // 150% laptop at 0,0 and a 100% external to its right
+        let mappings = [
+            MonitorMapping { index: 0, physical: r(0, 0, 2880, 1800), logical: r(0, 0, 1920, 1200), work: r(0, 0, 1920, 1152) },
+            unscaled(1, r(2880, 0, 1920, 1080)).with_logical(r(1920, 0, 1920, 1080)),
+        ];
+        let mut windows = vec![window(1, r(2580, 300, 600, 300)), window(2, r(9000, 0, 100, 100))];
+        project_windows(&mut windows, &mappings, &[]);
+
+        let pieces: Vec<(usize, Bounds)> = windows[0].pieces.iter().map(|p| (p.monitor, p.bounds.clone())).collect();
+        assert_eq!(pieces, [
+            (0, r(1720, 200, 200, 200).to_bounds()),
+            (1, r(1920, 300, 300, 300).to_bounds()),
+        ]);
+        // Overall bounds: union of the logical pieces
+        assert_eq!(windows[0].bounds, r(1720, 200, 500, 400).to_bounds());
+
+        // Off-screen windows have no pieces but still get logical bounds
+        assert!(windows[1].pieces.is_empty());
+        assert_eq!(windows[1].bounds, r(8040, 0, 100, 100).to_bounds());
+    }
+
+    #[test]
+    fn docks_occlude_windows_behind_them() {
+        // 1000x1000 monitor with a 50px taskbar at the bottom
+        let mapping = MonitorMapping { work: r(0, 0, 1000, 950), ..unscaled(0, r(0, 0, 1000, 1000)) };
+        assert_eq!(mapping.physical_work(), r(0, 0, 1000, 950));
+        let taskbar = mapping.physical.subtract(&mapping.physical_work());
+        assert_eq!(taskbar, [r(0, 950, 1000, 50)]);
+
+        // Top edge hidden behind the taskbar: no exposed top, only the part above it visible
+        let mut windows = vec![window(1, r(100, 960, 300, 200)), window(2, r(500, 800, 300, 400))];
+        project_windows(&mut windows, &[mapping], &taskbar);
+        assert!(windows[0].exposed_top.is_empty());
+        assert!(windows[0].visible_rects.is_empty());
+        assert_eq!(windows[1].exposed_top.len(), 1);
+        assert_eq!(windows[1].visible_rects, [r(500, 800, 300, 150).to_bounds()]);
+
+        // Fullscreen windows cover the taskbar
+        let mut fullscreen = window(3, r(0, 0, 1000, 1000));
+        fullscreen.is_fullscreen = true;
+        let mut windows = vec![fullscreen];
+        project_windows(&mut windows, &[mapping], &taskbar);
+        assert_eq!(windows[0].visible_rects, [r(0, 0, 1000, 1000).to_bounds()]);
+    }
+
+    #[test]
+    fn physical_work_inverts_scaling() {
+        // 150%: 72px physical taskbar is 48 logical
+        let mapping = MonitorMapping {
+            index: 0,
+            physical: r(-2880, 0, 2880, 1800),
+            logical: r(-1920, 0, 1920, 1200),
+            work: r(-1920, 0, 1920, 1152),
+        };
+        assert_eq!(mapping.physical_work(), r(-2880, 0, 2880, 1728));
+    }
+
+    // Shares a stretch of edge (not just a corner)
+    fn touching(a: &Rect, b: &Rect) -> bool {
+        let vertical_edge = (a.right == b.left || b.right == a.left) && a.top < b.bottom && b.top < a.bottom;
+        let horizontal_edge = (a.bottom == b.top || b.bottom == a.top) && a.left < b.right && b.left < a.right;
+        vertical_edge || horizontal_edge
+    }
+
+    fn assert_no_overlap(logical: &[Rect]) {
+        for (i, a) in logical.iter().enumerate() {
+            for b in &logical[i + 1..] {
+                assert!(a.intersect(b).is_none(), "{:?} overlaps {:?}", a, b);
+            }
+        }
+    }
+
+    fn mappings_for(physical: &[Rect], logical: &[Rect]) -> Vec<MonitorMapping> {
+        physical.iter().zip(logical).enumerate()
+            .map(|(index, (&physical, &logical))| MonitorMapping { index, physical, logical, work: logical })
+            .collect()
+    }
+
+    // Physical -> logical -> physical lands within a pixel; logical -> physical -> logical is exact
+    fn assert_round_trips(mapping: &MonitorMapping, scale: f64) {
+        assert_eq!(mapping.map_rect(&mapping.physical), mapping.logical);
+        assert_eq!(mapping.unmap_rect(&mapping.logical), mapping.physical);
+
+        let p = mapping.physical;
+        let probes = [
+            r(p.left, p.top, 1, 1),
+            r(p.left + 101, p.top + 37, 333, 251),
+            r(p.right - 641, p.bottom - 479, 640, 480),
+        ];
+        for probe in probes {
+            let back = mapping.unmap_rect(&mapping.map_rect(&probe));
+            for (a, b) in [(back.left, probe.left), (back.top, probe.top), (back.right, probe.right), (back.bottom, probe.bottom)] {
+                assert!((a - b).abs() <= 1, "{:?} came back as {:?}", probe, back);
+            }
+            let logical = mapping.map_rect(&probe);
+            assert_eq!(mapping.map_rect(&mapping.unmap_rect(&logical)), logical);
+        }
+
+        // The region transform the layout publishes agrees with the mapping
+        let transform = RegionTransform {
+            scale,
+            physical_x: p.left,
+            physical_y: p.top,
+            logical_x: mapping.logical.left,
+            logical_y: mapping.logical.top,
+        };
+        for (x, y) in [(p.left, p.top), (p.left + 150, p.top + 90), (p.right - 3, p.bottom - 3)] {
+            let (tx, ty) = transform.to_logical(x, y);
+            let (mx, my) = mapping.map_point(x, y);
+            assert!((tx - mx).abs() <= 1 && (ty - my).abs() <= 1, "({}, {}): {:?} vs {:?}", x, y, (tx, ty), (mx, my));
+        }
+    }
+
+    #[test]
+    fn layout_laptop_150_beside_external_100() {
+        let physical = [r(0, 0, 2880, 1800), r(2880, 0, 1920, 1080)];
+        let scales = [1.5, 1.0];
+        let logical = layout_monitors(&physical, &scales, 0);
+        assert_eq!(logical, [r(0, 0, 1920, 1200), r(1920, 0, 1920, 1080)]);
+        assert!(touching(&logical[0], &logical[1]));
+
+        // Same result with the external as primary, shifted so it keeps its own origin
+        let logical = layout_monitors(&physical, &scales, 1);
+        assert_eq!(logical[1], r(2880, 0, 1920, 1080));
+        assert_eq!(logical[0], r(960, 0, 1920, 1200));
+        assert!(touching(&logical[0], &logical[1]));
+
+        for (mapping, scale) in mappings_for(&physical, &logical).iter().zip(scales) {
+            assert_round_trips(mapping, scale);
+        }
+    }
+
+    #[test]
+    fn layout_100_over_200_stacked() {
+        let physical = [r(0, 0, 1920, 1080), r(0, 1080, 3840, 2160)];
+        let scales = [1.0, 2.0];
+        let logical = layout_monitors(&physical, &scales, 0);
+        assert_eq!(logical, [r(0, 0, 1920, 1080), r(0, 1080, 1920, 1080)]);
+        assert!(touching(&logical[0], &logical[1]));
+        for (mapping, scale) in mappings_for(&physical, &logical).iter().zip(scales) {
+            assert_round_trips(mapping, scale);
+        }
+    }
+
+    #[test]
+    fn layout_negative_origin_secondary_at_125() {
+        let physical = [r(0, 0, 1920, 1080), r(-2560, -200, 2560, 1440)];
+        let scales = [1.0, 1.25];
+        let logical = layout_monitors(&physical, &scales, 0);
+        assert_eq!(logical[0], r(0, 0, 1920, 1080));
+        // 2048x1152 logical, right edge on the primary's left edge, offset kept in primary units
+        assert_eq!(logical[1], r(-2048, -200, 2048, 1152));
+        assert!(touching(&logical[0], &logical[1]));
+        assert_no_overlap(&logical);
+        for (mapping, scale) in mappings_for(&physical, &logical).iter().zip(scales) {
+            assert_round_trips(mapping, scale);
+        }
+    }
+
+    #[test]
+    fn layout_three_in_a_row_with_scaled_middle() {
+        let physical = [r(-1920, 0, 1920, 1080), r(0, 0, 3840, 2160), r(3840, 0, 1920, 1080)];
+        let scales = [1.0, 2.0, 1.0];
+        for anchor in 0..3 {
+            let logical = layout_monitors(&physical, &scales, anchor);
+            assert_eq!((logical[0].width(), logical[0].height()), (1920, 1080));
+            assert_eq!((logical[1].width(), logical[1].height()), (1920, 1080));
+            assert!(touching(&logical[0], &logical[1]), "anchor {}: {:?}", anchor, logical);
+            assert!(touching(&logical[1], &logical[2]), "anchor {}: {:?}", anchor, logical);
+            assert_eq!(logical[0].top, logical[1].top);
+            assert_eq!(logical[2].top, logical[1].top);
+            assert_no_overlap(&logical);
+            for (mapping, scale) in mappings_for(&physical, &logical).iter().zip(scales) {
+                assert_round_trips(mapping, scale);
+            }
+        }
+    }
+
+    impl MonitorMapping {
+        fn with_logical(mut self, logical: Rect) -> Self {
+            self.logical = logical;
+            self.work = logical;
+            self
+        }
+    }
+}
@@ -0,0 +1,261 @@
+// Interaction mode: the overlay is normally click-through. While the configured modifier
+// is held, or for a timed session started from the tray, it accepts the mouse and the
+// renderer turns pointer events into rain interactions. Click-through is owned by the
+// watcher here, which restores it on release, timeout, or when focus moves to another
+// window, so a lost webview message can never leave the desktop blocked.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::Emitter;
+
+use crate::platform::{get_foreground_window, get_modifier_state, ModifierState};
+use crate::settings::{load_settings, save_settings};
+use crate::surfaces::overlay_windows;
+
+static STATE: Mutex<InteractionState> = Mutex::new(InteractionState {
+    modifier: InteractionModifier::Off,
+    session: None,
+});
+static WATCHER_RUNNING: AtomicBool = AtomicBool::new(false);
+
+pub(crate) const DEFAULT_TIMED_SECS: u64 = 30;
+const MAX_TIMED_SECS: u64 = 300;
+
+// Fast enough that a held modifier feels immediate; slower when it can't matter
+const ACTIVE_INTERVAL: Duration = Duration::from_millis(30);
+const DISABLED_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum InteractionModifier {
+    /// Only timed sessions from the tray. The default: a held modifier is opt-in from
+    /// the panel, since Alt/Ctrl/Shift are also everyday shortcuts
+    #[default]
+    Off,
+    Ctrl,
+    Alt,
+    Shift,
+}
+
+impl InteractionModifier {
+    fn is_held(self, state: ModifierState) -> bool {
+        match self {
+            InteractionModifier::Off => false,
+            InteractionModifier::Ctrl => state.ctrl,
+            InteractionModifier::Alt => state.alt,
+            InteractionModifier::Shift => state.shift,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum InteractionSource {
+    Modifier,
+    Timed,
+}
+
+struct Session {
+    source: InteractionSource,
+    deadline: Option<Instant>,
+    /// Foreground window when the session began; any other window taking focus ends it
+    foreground: Option<u64>,
+}
+
+struct InteractionState {
+    modifier: InteractionModifier,
+    session: Option<Session>,
+}
+
+impl InteractionState {
+    fn status(&self) -> InteractionStatus {
+        InteractionStatus {
+            active: self.session.is_some(),
+            source: self.session.as_ref().map(|s| s.source),
+            remaining_ms: self.session.as_ref()
+                .and_then(|s| s.deadline)
+                .map(|d| d.saturating_duration_since(Instant::now()).as_millis() as u64),
+            modifier: self.modifier,
+        }
+    }
+}
+
+/// Payload of `interaction-mode` and `get_interaction_status`
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct InteractionStatus {
+    pub active: bool,
+    pub source: Option<InteractionSource>,
+    /// Time left in a timed session
+    pub remaining_ms: Option<u64>,
+    pub modifier: InteractionModifier,
+}
+
+fn set_window_input(window: &tauri::WebviewWindow, accept: bool) {
+    if let Err(e) = window.set_ignore_cursor_events(!accept) {
+        log::error!("[Interaction] Failed to set {} click-through: {}", window.label(), e);
+    }
+    // Linux overlays refuse focus outside sessions; on_overlay_blur needs it during one
+    #[cfg(target_os = "linux")]
+    if let Err(e) = window.set_focusable(accept) {
+        log::error!("[Interaction] Failed to set {} focusable: {}", window.label(), e);
+    }
+}
+
+fn set_overlay_input(app: &tauri::AppHandle, accept: bool) {
+    for window in overlay_windows(app) {
+        set_window_input(&window, accept);
+    }
+}
+
+/// Overlays are created click-through; one (re)created mid-session takes input like the rest.
+pub(crate) fn apply_to_new_overlay(window: &tauri::WebviewWindow) {
+    let active = STATE.lock().map(|s| s.session.is_some()).unwrap_or(false);
+    if active {
+        set_window_input(window, true);
+    }
+}
+
+fn emit_status(app: &tauri::AppHandle, status: &InteractionStatus) {
+    if let Err(e) = app.emit("interaction-mode", status) {
+        log::error!("Failed to emit interaction-mode: {}", e);
+    }
+}
+
+fn overlay_focused(app: &tauri::AppHandle) -> bool {
+    overlay_windows(app).iter().any(|w| w.is_focused().unwrap_or(false))
+}
+
+// Caller holds the lock; overlay input is switched before the status goes out
+fn begin(app: &tauri::AppHandle, state: &mut InteractionState, source: InteractionSource, duration: Option<Duration>) -> InteractionStatus {
+    let replaced = state.session.is_some();
+    state.session = Some(Session {
+        source,
+        deadline: duration.map(|d| Instant::now() + d),
+        foreground: get_foreground_window(),
+    });
+    if !replaced {
+        set_overlay_input(app, true);
+    }
+    log::info!("[Interaction] Started ({:?}{})", source,
+        duration.map(|d| format!(", {}s", d.as_secs())).unwrap_or_default());
+    state.status()
+}
+
+fn end(app: &tauri::AppHandle, state: &mut InteractionState, reason: &str) -> Option<InteractionStatus> {
+    state.session.take()?;
+    set_overlay_input(app, false);
+    log::info!("[Interaction] Ended: {}", reason);
+    Some(state.status())
+}
+
+/// Startup: apply the persisted modifier and start the watcher.
+pub(crate) fn init(app: &tauri::AppHandle, modifier: InteractionModifier) {
+    if let Ok(mut state) = STATE.lock() {
+        state.modifier = modifier;
+    }
+    if !WATCHER_RUNNING.swap(true, Ordering::SeqCst) {
+        spawn_watcher(app.clone());
+    }
+}
+
+pub(crate) fn status() -> Option<InteractionStatus> {
+    STATE.lock().ok().map(|s| s.status())
+}
+
+/// Timed session (tray or panel); replaces a modifier session in progress.
+pub(crate) fn start_timed(app: &tauri::AppHandle, seconds: u64) -> Result<InteractionStatus, String> {
+    let seconds = seconds.clamp(1, MAX_TIMED_SECS);
+    let status = {
+        let mut state = STATE.lock().map_err(|e| format!("Interaction lock poisoned: {}", e))?;
+        begin(app, &mut state, InteractionSource::Timed, Some(Duration::from_secs(seconds)))
+    };
+    emit_status(app, &status);
+    Ok(status)
+}
+
+pub(crate) fn stop(app: &tauri::AppHandle, reason: &str) {
+    let status = match STATE.lock() {
+        Ok(mut state) => end(app, &mut state, reason),
+        Err(_) => {
+            // Never leave the desktop blocked, even with a poisoned lock
+            set_overlay_input(app, false);
+            None
+        }
+    };
+    if let Some(status) = status {
+        emit_status(app, &status);
+    }
+}
+
+/// Overlay lost focus (another window was activated)
+pub(crate) fn on_overlay_blur(app: &tauri::AppHandle) {
+    // Moving between per-monitor overlays isn't leaving
+    if overlay_focused(app) {
+        return;
+    }
+    stop(app, "focus left the overlay");
+}
+
+pub(crate) fn set_modifier(app: &tauri::AppHandle, modifier: InteractionModifier) -> InteractionStatus {
+    let status = {
+        let Ok(mut state) = STATE.lock() else {
+            return InteractionStatus { active: false, source: None, remaining_ms: None, modifier };
+        };
+        state.modifier = modifier;
+        state.status()
+    };
+
+    let mut settings = load_settings(app);
+    settings.interaction_modifier = modifier;
+    save_settings(app, &settings);
+
+    log::info!("[Interaction] Modifier set to {:?}", modifier);
+    emit_status(app, &status);
+    status
+}
+
+// One watcher tick: start/end sessions, returns the status to emit (if any)
+fn tick(app: &tauri::AppHandle, state: &mut InteractionState) -> Option<InteractionStatus> {
+    let held = state.modifier != InteractionModifier::Off && state.modifier.is_held(get_modifier_state());
+
+    let Some(session) = state.session.as_mut() else {
+        return held.then(|| begin(app, state, InteractionSource::Modifier, None));
+    };
+
+    if session.source == InteractionSource::Modifier && !held {
+        return end(app, state, "modifier released");
+    }
+    if session.deadline.is_some_and(|d| Instant::now() >= d) {
+        return end(app, state, "timed out");
+    }
+
+    let foreground = get_foreground_window();
+    if foreground != session.foreground {
+        // The overlay itself taking focus (e.g. clicked on Linux) isn't leaving
+        if overlay_focused(app) {
+            session.foreground = foreground;
+        } else {
+            return end(app, state, "focus moved to another window");
+        }
+    }
+    None
+}
+
+fn spawn_watcher(app: tauri::AppHandle) {
+    std::thread::spawn(move || loop {
+        let (status, idle) = match STATE.lock() {
+            Ok(mut state) => {
+                let status = tick(&app, &mut state);
+                let idle = state.session.is_none() && state.modifier == InteractionModifier::Off;
+                (status, idle)
+            }
+            Err(_) => (None, true),
+        };
+        if let Some(status) = status {
+            emit_status(&app, &status);
+        }
+        std::thread::sleep(if idle { DISABLED_INTERVAL } else { ACTIVE_INTERVAL });
+    });
+}
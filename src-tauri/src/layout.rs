@@ -0,0 +1,560 @@
+// Pure display layout: plain monitor descriptors in, geometry out (virtual desktop, panel
+// and help window placement). No Tauri or OS calls in here; platform::describe_monitors
+// builds the descriptors and the commands/window code apply the results.
+//
+// Two coordinate spaces appear:
+// - virtual-desktop logical (VirtualDesktop/MonitorRegion): every monitor mapped at its own
+//   scale and laid out by geometry::layout_monitors so neighbours keep touching
+// - window-positioning logical: physical / the scale of the monitor a point is on, which is
+//   what a LogicalPosition means for a window sitting on that monitor (panel, help)
+//
+// Panel and help positions are remembered per layout as WindowAnchors: an offset from a
+// monitor's work-area corner, keyed by that monitor's stable id.
+
+use std::collections::BTreeMap;
+
+use crate::geometry::{self, Rect};
+use crate::monitor_identity::MonitorIdentity;
+use crate::types::{Bounds, LayoutPositions, MonitorRegion, RegionTransform, VirtualDesktop, WindowAnchor};
+
+/// Default (unscaled) Rainscaper panel size
+pub(crate) const PANEL_SIZE: (i32, i32) = (400, 500);
+/// Panel UI scale range (the panel's slider steps run 0.5–2.5)
+pub(crate) const UI_SCALE_RANGE: (f64, f64) = (0.5, 2.5);
+
+// Gap between the panel and its work-area edges when placed near the tray or clamped
+const PANEL_MARGIN: i32 = 8;
+// Gap from the work-area corner for the panel's home position
+const PANEL_HOME_MARGIN: i32 = 12;
+// Gap around the help window when it has to shrink to fit
+const HELP_MARGIN: f64 = 16.0;
+// Taskbar height assumed when the tray is at the top of its monitor (physical px)
+const TOP_TASKBAR_HEIGHT: f64 = 40.0;
+
+/// One monitor as the OS reports it, in physical pixels
+#[derive(Clone)]
+pub(crate) struct MonitorDesc {
+    pub bounds: Bounds,
+    pub work_area: Bounds,
+    pub scale: f64,
+    pub refresh_rate: u32,
+    pub identity: MonitorIdentity,
+}
+
+/// Every monitor in enumeration order, plus which one is primary
+#[derive(Clone, Default)]
+pub(crate) struct Displays {
+    pub monitors: Vec<MonitorDesc>,
+    pub primary: usize,
+}
+
+/// Rect in window-positioning logical coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Area {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Area {
+    // Integer corners, truncated the way LogicalPosition values have always been stored
+    fn truncated(&self) -> (i32, i32, i32, i32) {
+        (self.x as i32, self.y as i32, self.width as i32, self.height as i32)
+    }
+}
+
+impl MonitorDesc {
+    fn contains_physical(&self, x: i32, y: i32) -> bool {
+        let b = &self.bounds;
+        x >= b.x && x < b.x + b.width as i32 && y >= b.y && y < b.y + b.height as i32
+    }
+
+    fn logical(&self, b: &Bounds) -> Area {
+        Area {
+            x: b.x as f64 / self.scale,
+            y: b.y as f64 / self.scale,
+            width: b.width as f64 / self.scale,
+            height: b.height as f64 / self.scale,
+        }
+    }
+
+    fn contains_logical(&self, x: i32, y: i32) -> bool {
+        let (mx, my, mw, mh) = self.logical(&self.bounds).truncated();
+        x >= mx && x < mx + mw && y >= my && y < my + mh
+    }
+
+    /// Work area in window-positioning logical coordinates
+    pub(crate) fn logical_work_area(&self) -> Area {
+        self.logical(&self.work_area)
+    }
+}
+
+impl Displays {
+    pub(crate) fn primary(&self) -> Option<&MonitorDesc> {
+        self.monitors.get(self.primary).or(self.monitors.first())
+    }
+}
+
+/// Bounding box and per-monitor regions of the virtual desktop; None without monitors.
+pub(crate) fn virtual_desktop(displays: &Displays) -> Option<VirtualDesktop> {
+    let primary = displays.primary()?;
+    let primary_index = if displays.primary < displays.monitors.len() { displays.primary } else { 0 };
+
+    // Each monitor is mapped with its own scale; the layout keeps touching monitors touching
+    let physical: Vec<Rect> = displays.monitors.iter().map(|m| Rect::from_xywh(m.bounds.x, m.bounds.y, m.bounds.width, m.bounds.height)).collect();
+    let scales: Vec<f64> = displays.monitors.iter().map(|m| m.scale).collect();
+    let logical = geometry::layout_monitors(&physical, &scales, primary_index);
+
+    let origin_x = logical.iter().map(|r| r.left).min().unwrap_or(0);
+    let origin_y = logical.iter().map(|r| r.top).min().unwrap_or(0);
+    let width = (logical.iter().map(|r| r.right).max().unwrap_or(0) - origin_x) as u32;
+    let height = (logical.iter().map(|r| r.bottom).max().unwrap_or(0) - origin_y) as u32;
+
+    let monitors = displays.monitors.iter().zip(&logical).enumerate()
+        .map(|(index, (monitor, rect))| {
+            let transform = RegionTransform {
+                scale: monitor.scale,
+                physical_x: monitor.bounds.x,
+                physical_y: monitor.bounds.y,
+                logical_x: rect.left,
+                logical_y: rect.top,
+            };
+
+            let work = &monitor.work_area;
+            let (work_left, work_top) = transform.to_logical(work.x, work.y);
+            let (work_right, work_bottom) = transform.to_logical(
+                work.x + work.width as i32,
+                work.y + work.height as i32,
+            );
+
+            MonitorRegion {
+                index,
+                x: (rect.left - origin_x) as u32,
+                y: (rect.top - origin_y) as u32,
+                width: rect.width(),
+                height: rect.height(),
+                work_x: (work_left - origin_x) as u32,
+                work_y: (work_top - origin_y) as u32,
+                work_width: (work_right - work_left).max(0) as u32,
+                work_height: (work_bottom - work_top).max(0) as u32,
+                scale_factor: monitor.scale,
+                refresh_rate: monitor.refresh_rate,
+                physical: monitor.bounds.clone(),
+                identity: monitor.identity.clone(),
+                transform,
+            }
+        })
+        .collect();
+
+    Some(VirtualDesktop {
+        origin_x,
+        origin_y,
+        width,
+        height,
+        monitors,
+        primary_index,
+        primary_scale_factor: primary.scale,
+    })
+}
+
+/// Panel size (window logical) at a UI scale, clamped to UI_SCALE_RANGE
+pub(crate) fn panel_size(ui_scale: f64) -> (i32, i32) {
+    let scale = ui_scale.clamp(UI_SCALE_RANGE.0, UI_SCALE_RANGE.1);
+    ((PANEL_SIZE.0 as f64 * scale).round() as i32, (PANEL_SIZE.1 as f64 * scale).round() as i32)
+}
+
+// Clamp a window position inside a work area, keeping PANEL_MARGIN clear
+fn clamp_to(area: &Area, x: i32, y: i32, panel_w: i32, panel_h: i32) -> (i32, i32) {
+    let (work_x, work_y, work_w, work_h) = area.truncated();
+    let x_min = work_x + PANEL_MARGIN;
+    let y_min = work_y + PANEL_MARGIN;
+    let x_max = (work_x + work_w - panel_w - PANEL_MARGIN).max(x_min);
+    let y_max = (work_y + work_h - panel_h - PANEL_MARGIN).max(y_min);
+    (x.max(x_min).min(x_max), y.max(y_min).min(y_max))
+}
+
+/// Panel position (window logical) next to a tray click at physical `tray_x`/`tray_y`:
+/// centred on the click, above a bottom taskbar or below a top one, inside the work area.
+pub(crate) fn panel_near_tray(displays: &Displays, tray_x: i32, tray_y: i32, panel_w: i32, panel_h: i32) -> (i32, i32) {
+    let monitor = displays.monitors.iter().find(|m| m.contains_physical(tray_x, tray_y));
+
+    let scale = monitor.map(|m| m.scale).unwrap_or(1.0);
+    let work = monitor.map(|m| m.logical_work_area())
+        .unwrap_or(Area { x: 0.0, y: 0.0, width: 1920.0, height: 1080.0 });
+    let taskbar_at_top = monitor.is_some_and(|m| tray_y < m.bounds.y + 100);
+
+    let tray_lx = (tray_x as f64 / scale) as i32;
+    let tray_ly = (tray_y as f64 / scale) as i32;
+    let (_, work_y, _, work_h) = work.truncated();
+
+    let x = tray_lx - (panel_w / 2);
+    let y = if taskbar_at_top {
+        tray_ly + (TOP_TASKBAR_HEIGHT / scale) as i32 + PANEL_MARGIN
+    } else {
+        // Align panel bottom edge with work area bottom
+        work_y + work_h - panel_h - PANEL_MARGIN
+    };
+    clamp_to(&work, x, y, panel_w, panel_h)
+}
+
+/// Clamp a saved panel position to the work area of the monitor it's on so it doesn't
+/// overlap the taskbar. A position on no current monitor lands on the primary.
+pub(crate) fn clamp_panel(displays: &Displays, x: i32, y: i32, panel_w: i32, panel_h: i32) -> (i32, i32) {
+    let Some(monitor) = displays.monitors.iter()
+        .find(|m| m.contains_logical(x, y))
+        .or(displays.primary())
+    else {
+        return (x, y);
+    };
+    clamp_to(&monitor.logical_work_area(), x, y, panel_w, panel_h)
+}
+
+/// Panel home: bottom-right corner of the primary monitor's work area.
+pub(crate) fn panel_home(displays: &Displays, panel_w: i32, panel_h: i32) -> Option<(i32, i32)> {
+    let (work_x, work_y, work_w, work_h) = displays.primary()?.logical_work_area().truncated();
+    Some((work_x + work_w - panel_w - PANEL_HOME_MARGIN, work_y + work_h - panel_h - PANEL_HOME_MARGIN))
+}
+
+/// Initial help window: 16:9 at 75% of the primary work-area width (85% height cap),
+/// centred. Falls back to 1120x630 at (100, 100) without monitors.
+pub(crate) fn help_window_area(displays: &Displays) -> Area {
+    let Some(primary) = displays.primary() else {
+        return Area { x: 100.0, y: 100.0, width: 1120.0, height: 630.0 };
+    };
+    let work = primary.logical_work_area();
+
+    // Try 75% of work width at 16:9; if too tall, constrain by height
+    let mut w = (work.width * 0.75).round();
+    let mut h = (w * 9.0 / 16.0).round();
+    if h > work.height * 0.85 {
+        h = (work.height * 0.85).round();
+        w = (h * 16.0 / 9.0).round();
+    }
+
+    Area { x: work.x + (work.width - w) / 2.0, y: work.y + (work.height - h) / 2.0, width: w, height: h }
+}
+
+/// Re-centre a help window of physical size `width`x`height` on the primary work area,
+/// shrinking it to fit. Returns the new area and whether it was shrunk.
+pub(crate) fn center_help_window(displays: &Displays, width: u32, height: u32) -> Option<(Area, bool)> {
+    let primary = displays.primary()?;
+    let work = primary.logical_work_area();
+
+    let max_w = (work.width - HELP_MARGIN * 2.0).max(200.0);
+    let max_h = (work.height - HELP_MARGIN * 2.0).max(150.0);
+    let w = width as f64 / primary.scale;
+    let h = height as f64 / primary.scale;
+    let clamped = w > max_w || h > max_h;
+    let (w, h) = (w.min(max_w), h.min(max_h));
+
+    Some((Area { x: work.x + (work.width - w) / 2.0, y: work.y + (work.height - h) / 2.0, width: w, height: h }, clamped))
+}
+
+/// A window whose position is remembered per layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Remembered {
+    Panel,
+    Help,
+}
+
+impl Remembered {
+    pub(crate) fn get(self, positions: &LayoutPositions) -> Option<&WindowAnchor> {
+        match self {
+            Remembered::Panel => positions.panel.as_ref(),
+            Remembered::Help => positions.help.as_ref(),
+        }
+    }
+
+    pub(crate) fn set(self, positions: &mut LayoutPositions, anchor: WindowAnchor) {
+        match self {
+            Remembered::Panel => positions.panel = Some(anchor),
+            Remembered::Help => positions.help = Some(anchor),
+        }
+    }
+}
+
+/// Anchor a window (window logical) to the monitor under its centre, or to the primary
+/// when its centre is over none.
+pub(crate) fn anchor_window(displays: &Displays, x: i32, y: i32, width: i32, height: i32, saved_at: i64) -> Option<WindowAnchor> {
+    let (cx, cy) = (x + width / 2, y + height / 2);
+    let monitor = displays.monitors.iter()
+        .find(|m| m.contains_logical(cx, cy))
+        .or(displays.primary())?;
+    let (work_x, work_y, _, _) = monitor.logical_work_area().truncated();
+    Some(WindowAnchor { monitor_id: monitor.identity.id.clone(), dx: x - work_x, dy: y - work_y, saved_at })
+}
+
+/// Where an anchored window goes now, kept inside its monitor's work area; None when
+/// that monitor isn't connected.
+pub(crate) fn place_anchor(displays: &Displays, anchor: &WindowAnchor, width: i32, height: i32) -> Option<(i32, i32)> {
+    let monitor = displays.monitors.iter().find(|m| m.identity.id == anchor.monitor_id)?;
+    let work = monitor.logical_work_area();
+    let (work_x, work_y, _, _) = work.truncated();
+    Some(clamp_to(&work, work_x + anchor.dx, work_y + anchor.dy, width, height))
+}
+
+/// Remembered position of `window` for the layout `fingerprint`. Failing that, the newest
+/// position remembered on any layout whose monitor is still connected (the laptop screen
+/// shared by the docked and undocked layouts). None means fall back to the default spot.
+pub(crate) fn recall(
+    displays: &Displays,
+    layouts: &BTreeMap<String, LayoutPositions>,
+    fingerprint: &str,
+    window: Remembered,
+    width: i32,
+    height: i32,
+) -> Option<(i32, i32)> {
+    let same_layout = layouts.get(fingerprint)
+        .and_then(|p| window.get(p))
+        .and_then(|anchor| place_anchor(displays, anchor, width, height));
+    if same_layout.is_some() {
+        return same_layout;
+    }
+
+    layouts.values()
+        .filter_map(|p| window.get(p))
+        .filter(|anchor| displays.monitors.iter().any(|m| m.identity.id == anchor.monitor_id))
+        .max_by_key(|anchor| anchor.saved_at)
+        .and_then(|anchor| place_anchor(displays, anchor, width, height))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bounds(x: i32, y: i32, width: u32, height: u32) -> Bounds {
+        Bounds { x, y, width, height }
+    }
+
+    fn monitor(id: &str, bounds: Bounds, work_area: Bounds, scale: f64) -> MonitorDesc {
+        MonitorDesc {
+            bounds,
+            work_area,
+            scale,
+            refresh_rate: 60,
+            identity: MonitorIdentity { id: id.to_string(), ..Default::default() },
+        }
+    }
+
+    fn displays(monitors: Vec<MonitorDesc>) -> Displays {
+        Displays { monitors, primary: 0 }
+    }
+
+    fn area(x: f64, y: f64, width: f64, height: f64) -> Area {
+        Area { x, y, width, height }
+    }
+
+    // Bounding box as origin + size
+    fn bounding_box(desktop: &VirtualDesktop) -> (i32, i32, u32, u32) {
+        (desktop.origin_x, desktop.origin_y, desktop.width, desktop.height)
+    }
+
+    fn regions(desktop: &VirtualDesktop) -> Vec<(u32, u32, u32, u32)> {
+        desktop.monitors.iter().map(|m| (m.x, m.y, m.width, m.height)).collect()
+    }
+
+    fn work_areas(desktop: &VirtualDesktop) -> Vec<(u32, u32, u32, u32)> {
+        desktop.monitors.iter().map(|m| (m.work_x, m.work_y, m.work_width, m.work_height)).collect()
+    }
+
+    const PANEL: (i32, i32) = PANEL_SIZE;
+
+    #[test]
+    fn vertical_monitor_beside_landscape() {
+        // Portrait 1080x1920 centred on the right of a 1080p primary
+        let d = displays(vec![
+            monitor("landscape", bounds(0, 0, 1920, 1080), bounds(0, 0, 1920, 1040), 1.0),
+            monitor("portrait", bounds(1920, -420, 1080, 1920), bounds(1920, -420, 1080, 1920), 1.0),
+        ]);
+        let desktop = virtual_desktop(&d).unwrap();
+        assert_eq!(bounding_box(&desktop), (0, -420, 3000, 1920));
+        assert_eq!(regions(&desktop), [(0, 420, 1920, 1080), (1920, 0, 1080, 1920)]);
+        assert_eq!(work_areas(&desktop), [(0, 420, 1920, 1040), (1920, 0, 1080, 1920)]);
+
+        assert_eq!(panel_near_tray(&d, 1800, 1060, PANEL.0, PANEL.1), (1512, 532));
+        assert_eq!(clamp_panel(&d, 2500, 1400, PANEL.0, PANEL.1), (2500, 992));
+        assert_eq!(panel_home(&d, PANEL.0, PANEL.1), Some((1508, 528)));
+        assert_eq!(center_help_window(&d, 1440, 810), Some((area(240.0, 115.0, 1440.0, 810.0), false)));
+    }
+
+    #[test]
+    fn stacked_monitor_above_primary() {
+        let d = displays(vec![
+            monitor("bottom", bounds(0, 0, 2560, 1440), bounds(0, 0, 2560, 1400), 1.0),
+            monitor("top", bounds(320, -1080, 1920, 1080), bounds(320, -1080, 1920, 1080), 1.0),
+        ]);
+        let desktop = virtual_desktop(&d).unwrap();
+        assert_eq!(bounding_box(&desktop), (0, -1080, 2560, 2520));
+        assert_eq!(regions(&desktop), [(0, 1080, 2560, 1440), (320, 0, 1920, 1080)]);
+        assert_eq!(work_areas(&desktop), [(0, 1080, 2560, 1400), (320, 0, 1920, 1080)]);
+
+        assert_eq!(panel_near_tray(&d, 2500, 1420, PANEL.0, PANEL.1), (2152, 892));
+        // On the upper monitor: kept inside it, not pulled down onto the primary
+        assert_eq!(clamp_panel(&d, 2000, -100, PANEL.0, PANEL.1), (1832, -508));
+        assert_eq!(panel_home(&d, PANEL.0, PANEL.1), Some((2148, 888)));
+        // Too big for the work area: shrunk to fit inside the margins
+        assert_eq!(center_help_window(&d, 3000, 2000), Some((area(16.0, 16.0, 2528.0, 1368.0), true)));
+    }
+
+    #[test]
+    fn negative_origin_secondary() {
+        let d = displays(vec![
+            monitor("primary", bounds(0, 0, 1920, 1080), bounds(0, 0, 1920, 1040), 1.0),
+            monitor("left", bounds(-1920, 200, 1920, 1080), bounds(-1920, 200, 1920, 1040), 1.0),
+        ]);
+        let desktop = virtual_desktop(&d).unwrap();
+        assert_eq!(bounding_box(&desktop), (-1920, 0, 3840, 1280));
+        assert_eq!(regions(&desktop), [(1920, 0, 1920, 1080), (0, 200, 1920, 1080)]);
+        assert_eq!(work_areas(&desktop), [(1920, 0, 1920, 1040), (0, 200, 1920, 1040)]);
+        assert_eq!(desktop.primary_index, 0);
+
+        assert_eq!(panel_near_tray(&d, -100, 1260, PANEL.0, PANEL.1), (-408, 732));
+        // Off every monitor: onto the primary
+        assert_eq!(clamp_panel(&d, -5000, 50, PANEL.0, PANEL.1), (8, 50));
+        assert_eq!(panel_home(&d, PANEL.0, PANEL.1), Some((1508, 528)));
+        assert_eq!(center_help_window(&d, 1600, 900), Some((area(160.0, 70.0, 1600.0, 900.0), false)));
+    }
+
+    #[test]
+    fn gapped_monitors() {
+        let d = displays(vec![
+            monitor("primary", bounds(0, 0, 1920, 1080), bounds(0, 0, 1920, 1040), 1.0),
+            monitor("sxga", bounds(2020, 0, 1280, 1024), bounds(2020, 0, 1280, 1024), 1.0),
+        ]);
+        let desktop = virtual_desktop(&d).unwrap();
+        assert_eq!(bounding_box(&desktop), (0, 0, 3300, 1080));
+        assert_eq!(regions(&desktop), [(0, 0, 1920, 1080), (2020, 0, 1280, 1024)]);
+        assert_eq!(work_areas(&desktop), [(0, 0, 1920, 1040), (2020, 0, 1280, 1024)]);
+
+        assert_eq!(panel_near_tray(&d, 3250, 1000, PANEL.0, PANEL.1), (2892, 516));
+        // In the gap: onto the primary
+        assert_eq!(clamp_panel(&d, 1950, 300, PANEL.0, PANEL.1), (1512, 300));
+        assert_eq!(panel_home(&d, PANEL.0, PANEL.1), Some((1508, 528)));
+        assert_eq!(center_help_window(&d, 1280, 720), Some((area(320.0, 160.0, 1280.0, 720.0), false)));
+    }
+
+    #[test]
+    fn mixed_dpi_laptop_and_external() {
+        // 150% laptop (72px physical taskbar) with a 100% 1440p external on its right
+        let d = displays(vec![
+            monitor("laptop", bounds(0, 0, 2880, 1800), bounds(0, 0, 2880, 1728), 1.5),
+            monitor("external", bounds(2880, 0, 2560, 1440), bounds(2880, 0, 2560, 1400), 1.0),
+        ]);
+        let desktop = virtual_desktop(&d).unwrap();
+        assert_eq!(bounding_box(&desktop), (0, 0, 4480, 1440));
+        assert_eq!(regions(&desktop), [(0, 0, 1920, 1200), (1920, 0, 2560, 1440)]);
+        assert_eq!(work_areas(&desktop), [(0, 0, 1920, 1152), (1920, 0, 2560, 1400)]);
+        assert_eq!(desktop.primary_scale_factor, 1.5);
+
+        // Window positions use each monitor's own scale: the external starts at 2880, not 1920
+        assert_eq!(panel_near_tray(&d, 2800, 1780, PANEL.0, PANEL.1), (1512, 644));
+        assert_eq!(panel_near_tray(&d, 5400, 1420, PANEL.0, PANEL.1), (5032, 892));
+        assert_eq!(clamp_panel(&d, 3000, 1300, PANEL.0, PANEL.1), (3000, 892));
+        assert_eq!(panel_home(&d, PANEL.0, PANEL.1), Some((1508, 640)));
+        assert_eq!(help_window_area(&d), area(240.0, 171.0, 1440.0, 810.0));
+        // Physical size on the 150% primary
+        assert_eq!(center_help_window(&d, 2400, 1350), Some((area(160.0, 126.0, 1600.0, 900.0), false)));
+    }
+
+    #[test]
+    fn taskbar_on_top() {
+        let d = displays(vec![monitor("primary", bounds(0, 0, 1920, 1080), bounds(0, 40, 1920, 1040), 1.0)]);
+        let desktop = virtual_desktop(&d).unwrap();
+        assert_eq!(bounding_box(&desktop), (0, 0, 1920, 1080));
+        assert_eq!(regions(&desktop), [(0, 0, 1920, 1080)]);
+        assert_eq!(work_areas(&desktop), [(0, 40, 1920, 1040)]);
+
+        // Below the taskbar rather than at the bottom of the screen
+        assert_eq!(panel_near_tray(&d, 1800, 20, PANEL.0, PANEL.1), (1512, 68));
+        assert_eq!(clamp_panel(&d, 100, 0, PANEL.0, PANEL.1), (100, 48));
+        assert_eq!(panel_home(&d, PANEL.0, PANEL.1), Some((1508, 568)));
+        assert_eq!(help_window_area(&d), area(240.0, 155.0, 1440.0, 810.0));
+        assert_eq!(center_help_window(&d, 1920, 1080), Some((area(16.0, 56.0, 1888.0, 1008.0), true)));
+    }
+
+    fn laptop() -> MonitorDesc {
+        monitor("laptop", bounds(0, 0, 1920, 1080), bounds(0, 0, 1920, 1040), 1.0)
+    }
+
+    fn external_at(x: i32) -> MonitorDesc {
+        monitor("external", bounds(x, 0, 2560, 1440), bounds(x, 0, 2560, 1440), 1.0)
+    }
+
+    fn anchor(monitor_id: &str, dx: i32, dy: i32, saved_at: i64) -> WindowAnchor {
+        WindowAnchor { monitor_id: monitor_id.to_string(), dx, dy, saved_at }
+    }
+
+    fn panel_at(anchor: WindowAnchor) -> LayoutPositions {
+        LayoutPositions { panel: Some(anchor), help: None }
+    }
+
+    #[test]
+    fn anchor_round_trip() {
+        let d = displays(vec![laptop(), external_at(1920)]);
+        // Centre lands on the external monitor
+        let a = anchor_window(&d, 2200, 300, PANEL.0, PANEL.1, 10).unwrap();
+        assert_eq!(a, anchor("external", 280, 300, 10));
+        assert_eq!(place_anchor(&d, &a, PANEL.0, PANEL.1), Some((2200, 300)));
+
+        let layouts = BTreeMap::from([("docked".to_string(), panel_at(a))]);
+        assert_eq!(recall(&d, &layouts, "docked", Remembered::Panel, PANEL.0, PANEL.1), Some((2200, 300)));
+        // Nothing remembered for the help window
+        assert_eq!(recall(&d, &layouts, "docked", Remembered::Help, PANEL.0, PANEL.1), None);
+    }
+
+    #[test]
+    fn anchor_follows_monitor_moved_within_layout() {
+        let a = anchor("external", 280, 300, 10);
+        let layouts = BTreeMap::from([("docked".to_string(), panel_at(a.clone()))]);
+
+        // External moved from the right of the laptop to its left
+        let d = displays(vec![laptop(), external_at(-2560)]);
+        assert_eq!(place_anchor(&d, &a, PANEL.0, PANEL.1), Some((-2280, 300)));
+        assert_eq!(recall(&d, &layouts, "docked", Remembered::Panel, PANEL.0, PANEL.1), Some((-2280, 300)));
+
+        // Still kept inside the work area when the offset no longer fits
+        let far = anchor("external", 2400, 1300, 10);
+        assert_eq!(place_anchor(&d, &far, PANEL.0, PANEL.1), Some((-408, 932)));
+    }
+
+    #[test]
+    fn recall_falls_back_to_newest_on_connected_monitor() {
+        let layouts = BTreeMap::from([
+            ("docked".to_string(), panel_at(anchor("external", 280, 300, 30))),
+            ("desk".to_string(), panel_at(anchor("laptop", 100, 200, 20))),
+            ("travel".to_string(), panel_at(anchor("laptop", 900, 100, 5))),
+        ]);
+
+        // Undocked: this layout has no entry and the newest anchor's monitor is gone
+        let d = displays(vec![laptop()]);
+        assert_eq!(recall(&d, &layouts, "undocked", Remembered::Panel, PANEL.0, PANEL.1), Some((100, 200)));
+
+        // Same fallback when this layout's own anchor points at a missing monitor
+        let mut layouts = layouts;
+        layouts.insert("undocked".to_string(), panel_at(anchor("projector", 0, 0, 40)));
+        assert_eq!(recall(&d, &layouts, "undocked", Remembered::Panel, PANEL.0, PANEL.1), Some((100, 200)));
+    }
+
+    #[test]
+    fn recall_without_match_leaves_panel_home() {
+        let layouts = BTreeMap::from([
+            ("docked".to_string(), panel_at(anchor("external", 280, 300, 30))),
+            ("desk".to_string(), panel_at(anchor("laptop", 100, 200, 20))),
+        ]);
+        let d = displays(vec![monitor("tv", bounds(0, 0, 1920, 1080), bounds(0, 0, 1920, 1040), 1.0)]);
+
+        assert_eq!(recall(&d, &layouts, "tv", Remembered::Panel, PANEL.0, PANEL.1), None);
+        // What the caller falls back to
+        assert_eq!(panel_home(&d, PANEL.0, PANEL.1), Some((1508, 528)));
+    }
+
+    #[test]
+    fn no_monitors() {
+        let d = Displays::default();
+        assert!(virtual_desktop(&d).is_none());
+        assert_eq!(panel_home(&d, PANEL.0, PANEL.1), None);
+        assert_eq!(center_help_window(&d, 1280, 720), None);
+        assert_eq!(help_window_area(&d), area(100.0, 100.0, 1120.0, 630.0));
+    }
+}
@@ -1,20 +1,39 @@
-use tauri::{Emitter, Listener, Manager, menu::MenuItem};
+use tauri::{Emitter, Listener, Manager, menu::{CheckMenuItem, MenuItem}};
+use std::collections::BTreeMap;
 use std::sync::Mutex;
-use std::sync::atomic::AtomicBool;
-use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
 
 mod commands;
+mod diagnostics;
+mod display_watch;
+mod docks;
+mod geometry;
+mod interaction;
+mod layout;
 mod logging;
+mod monitor_identity;
+mod monitor_state;
+mod obstacles;
 mod platform;
+mod pointer;
+mod poll_scheduler;
+mod privacy;
+mod rain_zones;
 mod rainscape;
+mod recording;
+mod settings;
+mod surfaces;
+mod target_window;
 mod tray;
 mod types;
+mod void_mask;
 mod window_detector;
 mod window_mgmt;
+mod window_tracker;
 
 use commands::*;
-use logging::setup_session_log;
+use logging::{log_dir, setup_session_log};
 use types::*;
 use window_mgmt::*;
 
@@ -27,15 +46,29 @@ static PAUSE_MENU_ITEM: Mutex<Option<MenuItem<tauri::Wry>>> = Mutex::new(None);
 // Global reference to rainscaper menu item for Open/Close text sync
 static RAINSCAPER_MENU_ITEM: Mutex<Option<MenuItem<tauri::Wry>>> = Mutex::new(None);
 
+// Tray Privacy submenu entries, one per title privacy mode (checked = active)
+static PRIVACY_MENU_ITEMS: Mutex<Vec<(privacy::TitlePrivacy, CheckMenuItem<tauri::Wry>)>> = Mutex::new(Vec::new());
+
+// Tray "Rain on Taskbar" toggle (checked = docks are collision surfaces)
+static DOCKS_MENU_ITEM: Mutex<Option<CheckMenuItem<tauri::Wry>>> = Mutex::new(None);
+
+// Tray "Per-Monitor Windows" toggle (checked = one overlay/background per monitor)
+static SURFACES_MENU_ITEM: Mutex<Option<CheckMenuItem<tauri::Wry>>> = Mutex::new(None);
+
 // Rainscaper panel visibility state
 static RAINSCAPER_VISIBLE: AtomicBool = AtomicBool::new(false);
 
 // Last tray click position (physical coords) for snap-to-tray positioning
 pub(crate) static LAST_TRAY_POSITION: Mutex<(i32, i32)> = Mutex::new((0, 0));
 
-// WebView health tracking for crash detection
-pub(crate) static OVERLAY_HEALTH: Mutex<Option<WindowHealth>> = Mutex::new(None);
-pub(crate) static BACKGROUND_HEALTH: Mutex<Option<WindowHealth>> = Mutex::new(None);
+// Per-monitor physical -> logical transforms for window projection; refreshed on monitor changes
+pub(crate) static MONITOR_MAPPINGS: Mutex<Vec<geometry::MonitorMapping>> = Mutex::new(Vec::new());
+
+// Last per-monitor occupancy summary sent to the renderer
+pub(crate) static MONITOR_STATES: Mutex<Vec<monitor_state::MonitorState>> = Mutex::new(Vec::new());
+
+// WebView health tracking for crash detection, keyed by surface window label
+pub(crate) static WINDOW_HEALTH: Mutex<BTreeMap<String, WindowHealth>> = Mutex::new(BTreeMap::new());
 
 // Disable unnecessary WebView2 components (~50+ MB of DRM, speech, ad filters, ML models).
 // Must be set before any WebView2 initialization.
@@ -69,11 +102,7 @@ WebGPU \
 
 // Per-session log file w/ rolling cleanup; keeps 5 most recent, max 1 MB each
 fn build_logging_plugin() -> tauri::plugin::TauriPlugin<tauri::Wry> {
-    let log_dir = dirs::data_local_dir()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join("com.rainydesk.app")
-        .join("logs");
-    let log_path = setup_session_log(&log_dir, 5, 1_048_576);
+    let log_path = setup_session_log(&log_dir(), 5, 1_048_576);
     let log_filename = log_path.file_name()
         .and_then(|n| n.to_str())
         .map(|s| s.trim_end_matches(".log").to_string())
@@ -93,10 +122,8 @@ fn build_logging_plugin() -> tauri::plugin::TauriPlugin<tauri::Wry> {
 
 // Preload Rainscaper panel + Help window hidden so first open is instant
 fn preload_windows(app: &tauri::App) {
-    let preload_pos = load_panel_config(app.handle())
-        .and_then(|c| c.x.zip(c.y))
-        .map(|(sx, sy)| clamp_panel_to_work_area(app.handle(), sx, sy, 400, 500))
-        .unwrap_or((0, 0));
+    let (panel_w, panel_h) = scaled_panel_size(app.handle());
+    let preload_pos = saved_panel_position(app.handle(), panel_w, panel_h).unwrap_or((0, 0));
     if let Err(e) = create_rainscaper_window_at(app.handle(), preload_pos.0, preload_pos.1, false) {
         log::warn!("[Rainscaper] Failed to preload panel: {}", e);
     } else {
@@ -134,30 +161,75 @@ fn handle_first_launch(app: &tauri::App) {
     });
 }
 
-// Start window detection polling (16ms = 60 Hz, matching physics tick rate)
+// Start window detection polling. Rate adapts (see poll_scheduler.rs): refresh rate
+// while windows move, backing off when static, suspended while paused.
+// Only changes are emitted (plus a periodic keyframe), so a static desktop is quiet.
 fn start_window_polling(app: &tauri::App) {
     let app_handle = app.handle().clone();
     std::thread::spawn(move || {
+        let mut tracker = window_tracker::WindowTracker::new();
+        let mut scheduler = poll_scheduler::PollScheduler::new(Instant::now());
+        let mut was_replaying = false;
         loop {
-            std::thread::sleep(std::time::Duration::from_millis(16));
+            if RAIN_PAUSED.load(Ordering::Relaxed) {
+                poll_scheduler::wait_while_paused();
+                scheduler.reset(Instant::now());
+            }
+
+            // A replay owns window-data until it's stopped; then start fresh so
+            // the live desktop goes out in full right away
+            if recording::is_replaying() {
+                was_replaying = true;
+                std::thread::sleep(Duration::from_millis(100));
+                continue;
+            }
+            if was_replaying {
+                was_replaying = false;
+                tracker = window_tracker::WindowTracker::new();
+                scheduler.reset(Instant::now());
+            }
+
+            // Until the monitors are laid out there's nothing to project into; raw
+            // physical bounds would land in the wrong place on scaled displays
+            let mappings = MONITOR_MAPPINGS.lock().map(|m| m.clone()).unwrap_or_default();
+            if mappings.is_empty() {
+                std::thread::sleep(Duration::from_millis(100));
+                continue;
+            }
+
+            let mut changed = false;
+            let mut all_fullscreen = false;
             match window_detector::get_visible_windows() {
-                Ok(window_data) => {
-                    if let Err(e) = app_handle.emit("window-data", &window_data) {
-                        log::error!("Failed to emit window-data: {}", e);
-                    }
+                Ok(mut window_data) => {
+                    let dock_rects = docks::occluders(&window_data.native_docks, &mappings);
+                    geometry::project_windows(&mut window_data.windows, &mappings, &dock_rects);
+                    docks::project_docks(&mut window_data, &mappings);
+
+                    let states = monitor_state::summarize(&window_data.windows, &mappings);
+                    all_fullscreen = !states.is_empty() && states.iter().all(|s| s.fullscreen_window.is_some());
+                    monitor_state::publish(&app_handle, states);
+
+                    target_window::track(&app_handle, &window_data);
+
+                    let update = tracker.update(&window_data, Instant::now());
+                    changed = update.changed;
+                    window_tracker::emit_update(&app_handle, &update, &window_data);
+                    recording::record_frame(&window_data, changed);
                 }
                 Err(e) => log::error!("Failed to get windows: {}", e),
             }
+
+            std::thread::sleep(scheduler.record(changed, all_fullscreen, Instant::now()));
         }
     });
     log::info!("Window detection polling started");
 }
 
 // Initialize health tracking for a window, preserving crash_count across recreations
-fn init_health(health_mutex: &Mutex<Option<WindowHealth>>) {
-    let mut guard = health_mutex.lock().unwrap();
-    let crash_count = guard.as_ref().map(|h| h.crash_count).unwrap_or(0);
-    *guard = Some(WindowHealth {
+fn init_health(label: &str) {
+    let mut guard = WINDOW_HEALTH.lock().unwrap();
+    let crash_count = guard.get(label).map(|h| h.crash_count).unwrap_or(0);
+    guard.insert(label.to_string(), WindowHealth {
         created_at: Instant::now(),
         last_heartbeat: None,
         init_complete: false,
@@ -165,16 +237,15 @@ fn init_health(health_mutex: &Mutex<Option<WindowHealth>>) {
     });
 }
 
-fn recover_window(handle: &tauri::AppHandle, label: &str) {
-    let health_mutex = match label {
-        "overlay" => &OVERLAY_HEALTH,
-        "background" => &BACKGROUND_HEALTH,
-        _ => return,
-    };
+// Stop tracking a surface that was closed on purpose (mode switch, monitor removed)
+fn forget_health(label: &str) {
+    WINDOW_HEALTH.lock().unwrap().remove(label);
+}
 
+fn recover_window(handle: &tauri::AppHandle, label: &str) {
     let crash_count = {
-        let mut guard = health_mutex.lock().unwrap();
-        let health = match guard.as_mut() {
+        let mut guard = WINDOW_HEALTH.lock().unwrap();
+        let health = match guard.get_mut(label) {
             Some(h) => h,
             None => return,
         };
@@ -209,6 +280,12 @@ fn recover_window(handle: &tauri::AppHandle, label: &str) {
         log::info!("[Recovery] Waiting {:?} before recreating {}...", backoff, label);
         std::thread::sleep(backoff);
 
+        // Closed on purpose while we waited
+        if !WINDOW_HEALTH.lock().unwrap().contains_key(&label) {
+            log::info!("[Recovery] {} no longer wanted, skipping", label);
+            return;
+        }
+
         let desktop = match get_virtual_desktop(handle.clone()) {
             Ok(d) => d,
             Err(e) => {
@@ -217,26 +294,8 @@ fn recover_window(handle: &tauri::AppHandle, label: &str) {
             }
         };
 
-        let health_mutex = match label.as_str() {
-            "overlay" => &OVERLAY_HEALTH,
-            "background" => &BACKGROUND_HEALTH,
-            _ => return,
-        };
-
-        match label.as_str() {
-            "overlay" => {
-                init_health(health_mutex);
-                if let Err(e) = create_mega_overlay(&handle, &desktop) {
-                    log::error!("[Recovery] Failed to recreate overlay: {}", e);
-                }
-            }
-            "background" => {
-                init_health(health_mutex);
-                if let Err(e) = create_mega_background(&handle, &desktop) {
-                    log::error!("[Recovery] Failed to recreate background: {}", e);
-                }
-            }
-            _ => {}
+        if let Err(e) = surfaces::create(&handle, &label, &desktop) {
+            log::error!("[Recovery] Failed to recreate {}: {}", label, e);
         }
     });
 }
@@ -251,10 +310,11 @@ fn start_health_monitor(handle: tauri::AppHandle) {
         loop {
             std::thread::sleep(Duration::from_secs(5));
 
-            for (label, health_mutex) in [("overlay", &OVERLAY_HEALTH), ("background", &BACKGROUND_HEALTH)] {
+            let labels: Vec<String> = WINDOW_HEALTH.lock().unwrap().keys().cloned().collect();
+            for label in labels {
                 let needs_recovery = {
-                    let guard = health_mutex.lock().unwrap();
-                    let Some(health) = guard.as_ref() else { continue };
+                    let guard = WINDOW_HEALTH.lock().unwrap();
+                    let Some(health) = guard.get(&label) else { continue };
 
                     // Already given up
                     if health.crash_count > 3 { continue; }
@@ -284,47 +344,86 @@ fn start_health_monitor(handle: tauri::AppHandle) {
                 };
 
                 if needs_recovery {
-                    recover_window(&handle, label);
+                    recover_window(&handle, &label);
                 }
             }
         }
     });
 }
 
-// Monitor hot-swap detection: polls every 5s, emits event on geometry change (2s debounce)
+fn store_monitor_mappings(desktop: &VirtualDesktop) {
+    if let Ok(mut guard) = MONITOR_MAPPINGS.lock() {
+        *guard = geometry::monitor_mappings(desktop);
+    }
+    let fastest = desktop.monitors.iter().map(|m| m.refresh_rate).max().unwrap_or(60);
+    poll_scheduler::set_fastest_refresh_rate(fastest);
+}
+
+// Bring everything that depends on the display layout up to date, then tell the renderers
+fn apply_display_change(handle: &tauri::AppHandle, previous: &VirtualDesktop, desktop: &VirtualDesktop) {
+    store_monitor_mappings(desktop);
+    obstacles::publish(handle, desktop);
+
+    surfaces::sync(handle, desktop);
+    reclamp_panel(handle);
+
+    let change = display_watch::diff(previous, desktop);
+    log::info!("[MonitorPoll] Emitting monitor-config-changed ({} monitors): {}", desktop.monitors.len(), change.describe());
+    let _ = handle.emit("virtual-desktop", desktop);
+    let _ = handle.emit("monitor-config-changed", &change);
+}
+
+// Monitor hot-swap detection: woken by native display notifications where available, with
+// a poll as fallback (5s without notifications, 30s with). Changes are applied once the
+// layout has been stable for 2s, since plugging a monitor fires several notifications.
 fn start_monitor_polling(app: &tauri::App) {
+    const DEBOUNCE: Duration = Duration::from_secs(2);
+
     let handle = app.handle().clone();
+    let (wake, wakeups) = std::sync::mpsc::channel();
+    let native = display_watch::spawn_native_listener(wake);
+    let interval = Duration::from_secs(if native { 30 } else { 5 });
+
     std::thread::spawn(move || {
         // Capture initial state
         let mut last_snapshot = platform::get_monitor_snapshot(&handle);
-        let mut pending_change: Option<Instant> = None;
+        let mut last_desktop = get_virtual_desktop(handle.clone()).ok();
+        let mut pending: Option<(Instant, platform::MonitorSnapshot)> = None;
 
         loop {
-            std::thread::sleep(Duration::from_secs(5));
+            // Re-check often while a change is settling
+            display_watch::wait(&wakeups, if pending.is_some() { Duration::from_millis(500) } else { interval });
 
             let current = platform::get_monitor_snapshot(&handle);
-            if current != last_snapshot {
-                if pending_change.is_none() {
+            if current == last_snapshot {
+                // Config reverted (e.g. rapid plug/unplug) — cancel pending
+                pending = None;
+                continue;
+            }
+            match &pending {
+                Some((_, settling)) if *settling == current => {}
+                _ => {
                     log::info!("[MonitorPoll] Display config change detected, debouncing 2s...");
-                    pending_change = Some(Instant::now());
+                    pending = Some((Instant::now(), current.clone()));
                 }
-            } else {
-                // Config reverted (e.g. rapid plug/unplug) — cancel pending
-                pending_change = None;
             }
 
-            // Emit after 2s debounce
-            if let Some(changed_at) = pending_change {
-                if changed_at.elapsed() >= Duration::from_secs(2) {
-                    log::info!("[MonitorPoll] Emitting monitor-config-changed ({} monitors)", current.len());
-                    let _ = handle.emit("monitor-config-changed", ());
-                    last_snapshot = current;
-                    pending_change = None;
+            if pending.as_ref().is_some_and(|(since, _)| since.elapsed() >= DEBOUNCE) {
+                match get_virtual_desktop(handle.clone()) {
+                    Ok(desktop) => {
+                        let previous = last_desktop.take().unwrap_or_else(|| desktop.clone());
+                        apply_display_change(&handle, &previous, &desktop);
+                        last_desktop = Some(desktop);
+                    }
+                    Err(e) => log::warn!("[MonitorPoll] Failed to read new display layout: {}", e),
                 }
+                last_snapshot = current;
+                pending = None;
             }
         }
     });
-    log::info!("Monitor hot-swap polling started (5s interval)");
+    log::info!("Monitor hot-swap detection started ({}, {}s poll)",
+        if native { "native notifications" } else { "polling only" }, interval.as_secs());
 }
 
 fn setup_application(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
@@ -348,15 +447,20 @@ fn setup_application(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Err
         );
     }
 
-    init_health(&BACKGROUND_HEALTH);
-    if let Err(e) = create_mega_background(app.handle(), &desktop) {
-        log::error!("Failed to create mega-background: {}", e);
-    }
+    store_monitor_mappings(&desktop);
 
-    init_health(&OVERLAY_HEALTH);
-    if let Err(e) = create_mega_overlay(app.handle(), &desktop) {
-        log::error!("Failed to create mega-overlay: {}", e);
-    }
+    // Before polling starts so no unredacted title is ever sent or logged
+    let app_settings = settings::load_settings(app.handle());
+    privacy::set_mode(app_settings.title_privacy);
+    docks::set_rain_on_docks(app_settings.rain_on_docks);
+    target_window::init(app_settings.target_window.clone());
+    interaction::init(app.handle(), app_settings.interaction_modifier);
+    log::info!("Title privacy: {:?}, rain on docks: {}", app_settings.title_privacy, app_settings.rain_on_docks);
+
+    surfaces::set_mode(app_settings.window_mode);
+    log::info!("Window mode: {:?}", app_settings.window_mode);
+    // Nothing to close at startup, so sync only creates (and doesn't block)
+    surfaces::sync(app.handle(), &desktop);
 
     preload_windows(app);
     handle_first_launch(app);
@@ -409,6 +513,43 @@ pub fn run() {
             get_display_info,
             get_all_displays,
             get_virtual_desktop,
+            get_monitor_states,
+            get_window_poll_stats,
+            get_title_privacy,
+            set_title_privacy,
+            save_diagnostics_image,
+            get_diagnostics_image,
+            start_window_recording,
+            stop_window_recording,
+            start_window_replay,
+            set_window_replay_playing,
+            seek_window_replay,
+            stop_window_replay,
+            get_window_replay_status,
+            get_rain_on_docks,
+            set_rain_on_docks,
+            get_window_mode,
+            set_window_mode,
+            get_surface_desktop,
+            handoff_drops,
+            list_rain_zones,
+            add_rain_zone,
+            update_rain_zone,
+            delete_rain_zone,
+            list_target_windows,
+            get_target_window,
+            set_target_window,
+            clear_target_window,
+            get_interaction_status,
+            start_interaction,
+            stop_interaction,
+            set_interaction_modifier,
+            list_obstacles,
+            add_obstacle,
+            update_obstacle,
+            delete_obstacle,
+            get_static_geometry,
+            get_void_mask,
             get_system_specs,
             set_rainscape,
             set_ignore_mouse_events,
@@ -424,6 +565,8 @@ pub fn run() {
             hide_rainscaper,
             toggle_rainscaper,
             resize_rainscaper,
+            get_ui_scale,
+            set_ui_scale,
             get_panel_detached,
             set_panel_detached,
             snap_panel_to_tray,
@@ -441,7 +584,7 @@ pub fn run() {
             save_user_themes
         ])
         .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
-            if let Some(window) = app.get_webview_window("overlay") {
+            if let Some(window) = surfaces::overlay_windows(app).first() {
                 let _ = window.set_focus();
             }
             log::info!("Second instance blocked: RainyDesk is already running");
@@ -4,6 +4,15 @@ use std::fs;
 use std::path::PathBuf;
 use chrono::Local;
 
+/// Session logs live here, with window recordings under `recordings/` and layout
+/// images under `diagnostics/`.
+pub(crate) fn log_dir() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("com.rainydesk.app")
+        .join("logs")
+}
+
 fn is_rainydesk_log(entry: &fs::DirEntry) -> bool {
     let name = entry.file_name();
     let name = name.to_string_lossy();
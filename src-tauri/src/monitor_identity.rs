@@ -0,0 +1,283 @@
+// Stable monitor identity. Enumeration indices change whenever monitors are reordered or
+// re-plugged, so per-monitor settings key on `id` instead: EDID manufacturer/model/serial
+// where readable, the connector otherwise, and a hash of whatever names the OS gives the
+// monitor as a last resort (never its size or scale, which the user can change). Also
+// carries a human-readable name and the rotation.
+
+use std::collections::HashMap;
+
+use crate::platform::{get_display_descriptors, DisplayDescriptor};
+
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MonitorIdentity {
+    /// Stable key for per-monitor settings ("edid:DEL-4090-ABC123", "connector:HDMI-1", "hash:…")
+    pub id: String,
+    /// Human-readable ("DELL U2720Q"), falling back to "Display N"
+    pub name: String,
+    pub connector: Option<String>,
+    /// Three-letter PnP manufacturer id from the EDID ("DEL")
+    pub manufacturer: Option<String>,
+    /// EDID product code as hex ("4090")
+    pub model: Option<String>,
+    pub serial: Option<String>,
+    /// Clockwise degrees: 0, 90, 180, 270
+    pub rotation: u32,
+}
+
+struct EdidInfo {
+    manufacturer: String,
+    product: u16,
+    serial: Option<String>,
+    name: Option<String>,
+}
+
+const EDID_HEADER: [u8; 8] = [0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00];
+
+// Display descriptor text: up to 13 bytes, terminated by 0x0A, space padded
+fn descriptor_text(raw: &[u8]) -> Option<String> {
+    let text: String = raw.iter()
+        .take_while(|&&b| b != 0x0A && b != 0x00)
+        .map(|&b| b as char)
+        .collect();
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+fn parse_edid(bytes: &[u8]) -> Option<EdidInfo> {
+    if bytes.len() < 128 || bytes[..8] != EDID_HEADER {
+        return None;
+    }
+
+    // Manufacturer: three 5-bit letters, big-endian ('A' = 1)
+    let id = u16::from_be_bytes([bytes[8], bytes[9]]);
+    let letter = |shift: u16| (((id >> shift) & 0x1F) as u8 + b'A' - 1) as char;
+    let manufacturer: String = [letter(10), letter(5), letter(0)].iter().collect();
+    let product = u16::from_le_bytes([bytes[10], bytes[11]]);
+    let serial_number = u32::from_le_bytes([bytes[12], bytes[13], bytes[14], bytes[15]]);
+
+    let mut name = None;
+    let mut serial_text = None;
+    for offset in [54, 72, 90, 108] {
+        let block = &bytes[offset..offset + 18];
+        // Display descriptors start with a zero pixel clock
+        if block[0] != 0 || block[1] != 0 {
+            continue;
+        }
+        match block[3] {
+            0xFC => name = descriptor_text(&block[5..18]),
+            0xFF => serial_text = descriptor_text(&block[5..18]),
+            _ => {}
+        }
+    }
+
+    Some(EdidInfo {
+        manufacturer,
+        product,
+        serial: serial_text.or_else(|| (serial_number != 0).then(|| serial_number.to_string())),
+        name,
+    })
+}
+
+// FNV-1a: stable across runs and Rust versions, unlike the std hashers
+fn stable_hash(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, b| (hash ^ b as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
+/// `monitor_name` is the OS device name Tauri reports (e.g. "\\.\DISPLAY1").
+fn build(descriptor: Option<&DisplayDescriptor>, monitor_name: Option<&str>, index: usize) -> MonitorIdentity {
+    let edid = descriptor.and_then(|d| d.edid.as_deref()).and_then(parse_edid);
+    let pnp_id = descriptor.and_then(|d| d.pnp_id.as_deref()).filter(|p| p.len() > 3);
+    let connector = descriptor.and_then(|d| d.connector.clone());
+
+    let manufacturer = edid.as_ref().map(|e| e.manufacturer.clone())
+        .or_else(|| pnp_id.map(|p| p[..3].to_string()));
+    let model = edid.as_ref().map(|e| format!("{:04X}", e.product))
+        .or_else(|| pnp_id.map(|p| p[3..].to_string()));
+    let serial = edid.as_ref().and_then(|e| e.serial.clone());
+
+    let id = match (&manufacturer, &model, &serial, &connector) {
+        (Some(m), Some(p), Some(s), _) => format!("edid:{}-{}-{}", m, p, s),
+        // Same model twice without serials: the connector tells them apart
+        (Some(m), Some(p), None, Some(c)) => format!("edid:{}-{}@{}", m, p, c),
+        (_, _, _, Some(c)) => format!("connector:{}", c),
+        // Only attributes that survive a resolution or scale change; monitors that end
+        // up with the same key are told apart by identify's #n suffix
+        _ => {
+            let key = [
+                monitor_name,
+                manufacturer.as_deref(),
+                model.as_deref(),
+                descriptor.and_then(|d| d.friendly_name.as_deref()),
+            ].map(|part| part.unwrap_or("")).join("|");
+            format!("hash:{:016x}", stable_hash(&key))
+        }
+    };
+
+    let name = edid.as_ref().and_then(|e| e.name.clone())
+        .or_else(|| descriptor.and_then(|d| d.friendly_name.clone()))
+        .or_else(|| match (&manufacturer, &model) {
+            (Some(m), Some(p)) => Some(format!("{} {}", m, p)),
+            _ => None,
+        })
+        .unwrap_or_else(|| format!("Display {}", index + 1));
+
+    MonitorIdentity {
+        id,
+        name,
+        connector,
+        manufacturer,
+        model,
+        serial,
+        rotation: descriptor.map_or(0, |d| d.rotation),
+    }
+}
+
+/// Identities for Tauri's monitors, in the same order. Ids are unique within the result.
+pub(crate) fn identify(monitors: &[tauri::Monitor]) -> Vec<MonitorIdentity> {
+    let descriptors = get_display_descriptors();
+    let mut identities: Vec<MonitorIdentity> = monitors.iter()
+        .enumerate()
+        .map(|(index, monitor)| {
+            let pos = monitor.position();
+            let descriptor = descriptors.iter().find(|d| d.x == pos.x && d.y == pos.y);
+            build(descriptor, monitor.name().map(|n| n.as_str()), index)
+        })
+        .collect();
+    disambiguate(&mut identities);
+    identities
+}
+
+// Cloned setups (identical EDIDs, no connector) still need distinct keys: later
+// duplicates get "#2", "#3", … in enumeration order
+fn disambiguate(identities: &mut [MonitorIdentity]) {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    for identity in identities {
+        let count = seen.entry(identity.id.clone()).or_default();
+        *count += 1;
+        if *count > 1 {
+            identity.id = format!("{}#{}", identity.id, count);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Minimal EDID 1.3 base block: DEL 0x4090, numeric serial, optional text descriptors
+    fn edid(serial_number: u32, name: Option<&str>, serial_text: Option<&str>) -> Vec<u8> {
+        let mut bytes = vec![0u8; 128];
+        bytes[..8].copy_from_slice(&EDID_HEADER);
+        // 'D' = 4, 'E' = 5, 'L' = 12
+        let id: u16 = (4 << 10) | (5 << 5) | 12;
+        bytes[8..10].copy_from_slice(&id.to_be_bytes());
+        bytes[10..12].copy_from_slice(&0x4090u16.to_le_bytes());
+        bytes[12..16].copy_from_slice(&serial_number.to_le_bytes());
+
+        // First descriptor is a detailed timing (non-zero pixel clock)
+        bytes[54] = 0x02;
+        bytes[55] = 0x3A;
+        let mut put_text = |offset: usize, tag: u8, text: &str| {
+            bytes[offset + 3] = tag;
+            let mut field = [b' '; 13];
+            field[..text.len()].copy_from_slice(text.as_bytes());
+            if text.len() < 13 {
+                field[text.len()] = 0x0A;
+            }
+            bytes[offset + 5..offset + 18].copy_from_slice(&field);
+        };
+        if let Some(name) = name {
+            put_text(72, 0xFC, name);
+        }
+        if let Some(serial) = serial_text {
+            put_text(90, 0xFF, serial);
+        }
+        bytes
+    }
+
+    fn descriptor(connector: Option<&str>, edid: Option<Vec<u8>>) -> DisplayDescriptor {
+        DisplayDescriptor {
+            connector: connector.map(str::to_string),
+            edid,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parses_manufacturer_product_name_and_serial() {
+        let info = parse_edid(&edid(0x1234_5678, Some("DELL U2720Q"), Some("ABC123"))).unwrap();
+        assert_eq!(info.manufacturer, "DEL");
+        assert_eq!(info.product, 0x4090);
+        assert_eq!(info.name.as_deref(), Some("DELL U2720Q"));
+        // Text serial wins over the numeric one
+        assert_eq!(info.serial.as_deref(), Some("ABC123"));
+    }
+
+    #[test]
+    fn numeric_serial_when_no_text_serial() {
+        let info = parse_edid(&edid(0x1234_5678, None, None)).unwrap();
+        assert_eq!(info.serial.as_deref(), Some("305419896"));
+        assert_eq!(info.name, None);
+
+        let info = parse_edid(&edid(0, None, None)).unwrap();
+        assert_eq!(info.serial, None);
+    }
+
+    #[test]
+    fn thirteen_character_name_without_terminator() {
+        let info = parse_edid(&edid(0, Some("ABCDEFGHIJKLM"), None)).unwrap();
+        assert_eq!(info.name.as_deref(), Some("ABCDEFGHIJKLM"));
+    }
+
+    #[test]
+    fn rejects_bad_header_and_short_blocks() {
+        let mut bad = edid(1, None, None);
+        bad[0] = 0x01;
+        assert!(parse_edid(&bad).is_none());
+        assert!(parse_edid(&edid(1, None, None)[..127]).is_none());
+        assert!(parse_edid(&[]).is_none());
+    }
+
+    #[test]
+    fn id_prefers_edid_then_connector() {
+        let full = descriptor(Some("DP-1"), Some(edid(0, Some("DELL U2720Q"), Some("ABC123"))));
+        let identity = build(Some(&full), None, 0);
+        assert_eq!(identity.id, "edid:DEL-4090-ABC123");
+        assert_eq!(identity.name, "DELL U2720Q");
+
+        let no_serial = descriptor(Some("HDMI-1"), Some(edid(0, None, None)));
+        let identity = build(Some(&no_serial), None, 1);
+        assert_eq!(identity.id, "edid:DEL-4090@HDMI-1");
+        assert_eq!(identity.name, "DEL 4090");
+
+        let bare = descriptor(Some("eDP-1"), None);
+        assert_eq!(build(Some(&bare), None, 2).id, "connector:eDP-1");
+    }
+
+    #[test]
+    fn hash_fallback_ignores_size_and_scale() {
+        // Same device name: same id whatever Tauri reports for size/scale
+        let a = build(None, Some(r"\\.\DISPLAY1"), 0);
+        let b = build(None, Some(r"\\.\DISPLAY1"), 3);
+        assert!(a.id.starts_with("hash:"));
+        assert_eq!(a.id, b.id);
+        assert_eq!(b.name, "Display 4");
+
+        assert_ne!(a.id, build(None, Some(r"\\.\DISPLAY2"), 0).id);
+        // Known stable value: changing the hash would orphan saved settings
+        assert_eq!(stable_hash(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(stable_hash("a"), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn duplicate_ids_get_numbered_suffixes() {
+        let mut identities: Vec<MonitorIdentity> = ["edid:DEL-4090", "connector:DP-1", "edid:DEL-4090", "edid:DEL-4090"]
+            .iter()
+            .map(|id| MonitorIdentity { id: id.to_string(), ..Default::default() })
+            .collect();
+        disambiguate(&mut identities);
+        let ids: Vec<&str> = identities.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, ["edid:DEL-4090", "connector:DP-1", "edid:DEL-4090#2", "edid:DEL-4090#3"]);
+    }
+}
@@ -0,0 +1,194 @@
+// Per-monitor occupancy summary: fullscreen/maximized occupant, work-area coverage,
+// topmost window. Built from projected (logical) window data; emitted only on change.
+
+use tauri::Emitter;
+
+use crate::geometry::{MonitorMapping, Rect};
+use crate::window_detector::WindowInfo;
+use crate::MONITOR_STATES;
+
+// Rounding slack when a piece must cover the whole monitor (logical px)
+const FULLSCREEN_TOLERANCE: i32 = 2;
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MonitorState {
+    pub index: usize,
+    /// Topmost window occupying the whole monitor (native flag or geometry)
+    pub fullscreen_window: Option<u64>,
+    /// Topmost maximized window on this monitor
+    pub maximized_window: Option<u64>,
+    /// Fraction of the work area hidden behind windows, 0.0–1.0 (2 decimals)
+    pub work_area_coverage: f64,
+    pub topmost_window: Option<u64>,
+}
+
+fn covers(piece: &Rect, target: &Rect, tolerance: i32) -> bool {
+    piece.left <= target.left + tolerance
+        && piece.top <= target.top + tolerance
+        && piece.right >= target.right - tolerance
+        && piece.bottom >= target.bottom - tolerance
+}
+
+fn piece_on(win: &WindowInfo, monitor: usize) -> Option<Rect> {
+    win.pieces.iter()
+        .find(|p| p.monitor == monitor)
+        .map(|p| Rect::from_bounds(&p.bounds))
+}
+
+fn is_fullscreen_on(win: &WindowInfo, mapping: &MonitorMapping) -> bool {
+    let Some(piece) = piece_on(win, mapping.index) else { return false };
+    // Native flag wins; otherwise a non-maximized window filling the monitor (borderless games)
+    win.is_fullscreen || (!win.is_maximized && covers(&piece, &mapping.logical, FULLSCREEN_TOLERANCE))
+}
+
+/// Windows must already be projected and in z-order (index 0 = topmost).
+pub(crate) fn summarize(windows: &[WindowInfo], mappings: &[MonitorMapping]) -> Vec<MonitorState> {
+    mappings.iter().map(|mapping| {
+        let on_monitor = || windows.iter().filter(|w| piece_on(w, mapping.index).is_some());
+
+        // Visible rects are disjoint across windows, so their areas can simply be summed
+        let work = mapping.work;
+        let work_area = work.width() as f64 * work.height() as f64;
+        let covered: f64 = windows.iter()
+            .flat_map(|w| w.visible_rects.iter())
+            .filter_map(|b| Rect::from_bounds(b).intersect(&work))
+            .map(|r| r.width() as f64 * r.height() as f64)
+            .sum();
+        let coverage = if work_area > 0.0 { (covered / work_area).min(1.0) } else { 0.0 };
+
+        MonitorState {
+            index: mapping.index,
+            fullscreen_window: on_monitor().find(|w| is_fullscreen_on(w, mapping)).map(|w| w.id),
+            maximized_window: on_monitor().find(|w| w.is_maximized).map(|w| w.id),
+            work_area_coverage: (coverage * 100.0).round() / 100.0,
+            topmost_window: on_monitor().next().map(|w| w.id),
+        }
+    }).collect()
+}
+
+/// Emit `monitor-state` if the summary differs from the last one sent.
+pub(crate) fn publish(app: &tauri::AppHandle, states: Vec<MonitorState>) {
+    let Ok(mut last) = MONITOR_STATES.lock() else { return };
+    if *last == states {
+        return;
+    }
+
+    if let Err(e) = app.emit("monitor-state", &states) {
+        log::error!("Failed to emit monitor-state: {}", e);
+    }
+    *last = states;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry;
+
+    // Two 1080p monitors side by side; a 40px taskbar on the left one
+    fn mappings() -> Vec<MonitorMapping> {
+        let left = Rect::from_xywh(0, 0, 1920, 1080);
+        let right = Rect::from_xywh(1920, 0, 1920, 1080);
+        vec![
+            MonitorMapping { index: 0, physical: left, logical: left, work: Rect::from_xywh(0, 0, 1920, 1040) },
+            MonitorMapping { index: 1, physical: right, logical: right, work: right },
+        ]
+    }
+
+    fn window(id: u64, x: i32, y: i32, width: u32, height: u32) -> WindowInfo {
+        WindowInfo {
+            id,
+            bounds: Rect::from_xywh(x, y, width, height).to_bounds(),
+            title: String::new(),
+            is_maximized: false,
+            z_order: 0,
+            process_name: String::new(),
+            class_name: String::new(),
+            pid: 0,
+            is_focused: false,
+            is_topmost: false,
+            is_fullscreen: false,
+            pieces: Vec::new(),
+            visible_rects: Vec::new(),
+            exposed_top: Vec::new(),
+        }
+    }
+
+    // Topmost first, as the poll loop passes them
+    fn summarize_stack(mut windows: Vec<WindowInfo>) -> Vec<MonitorState> {
+        let mappings = mappings();
+        geometry::project_windows(&mut windows, &mappings, &[]);
+        summarize(&windows, &mappings)
+    }
+
+    #[test]
+    fn native_fullscreen_flag() {
+        // Flagged fullscreen by the OS even though it doesn't fill the monitor
+        let mut game = window(1, 100, 100, 800, 600);
+        game.is_fullscreen = true;
+        let states = summarize_stack(vec![game]);
+        assert_eq!(states[0].fullscreen_window, Some(1));
+        assert_eq!(states[1].fullscreen_window, None);
+    }
+
+    #[test]
+    fn geometric_fullscreen_needs_non_maximized() {
+        // Borderless window filling the right monitor, within the rounding slack
+        let states = summarize_stack(vec![window(1, 1921, 1, 1918, 1079)]);
+        assert_eq!(states[1].fullscreen_window, Some(1));
+        assert_eq!(states[1].maximized_window, None);
+
+        // A maximized window of the same size is maximized, not fullscreen
+        let mut maximized = window(2, 1920, 0, 1920, 1080);
+        maximized.is_maximized = true;
+        let states = summarize_stack(vec![maximized]);
+        assert_eq!(states[1].fullscreen_window, None);
+        assert_eq!(states[1].maximized_window, Some(2));
+
+        // Short of the slack: just a big window
+        let states = summarize_stack(vec![window(3, 1920, 0, 1920, 1000)]);
+        assert_eq!(states[1].fullscreen_window, None);
+    }
+
+    #[test]
+    fn window_spanning_two_monitors() {
+        // 420px on the left monitor, 580px on the right
+        let states = summarize_stack(vec![window(1, 1500, 0, 1000, 520)]);
+        assert_eq!(states[0].topmost_window, Some(1));
+        assert_eq!(states[1].topmost_window, Some(1));
+        assert_eq!(states[0].work_area_coverage, 0.11);
+        assert_eq!(states[1].work_area_coverage, 0.15);
+        assert!(states.iter().all(|s| s.fullscreen_window.is_none()));
+    }
+
+    #[test]
+    fn partial_coverage_counts_overlap_once() {
+        // A quarter of the left work area
+        let states = summarize_stack(vec![window(1, 0, 0, 960, 520)]);
+        assert_eq!(states[0].work_area_coverage, 0.25);
+        assert_eq!(states[1].work_area_coverage, 0.0);
+
+        // A second quarter overlapping the first by a quarter of itself: 7/16
+        let states = summarize_stack(vec![window(2, 480, 260, 960, 520), window(1, 0, 0, 960, 520)]);
+        assert_eq!(states[0].work_area_coverage, 0.44);
+
+        // Windows over the taskbar don't push coverage past the work area
+        let states = summarize_stack(vec![window(3, 0, 0, 1920, 1080)]);
+        assert_eq!(states[0].work_area_coverage, 1.0);
+    }
+
+    #[test]
+    fn topmost_window_per_monitor() {
+        let states = summarize_stack(vec![
+            window(1, 2000, 100, 400, 300),
+            window(2, 100, 100, 400, 300),
+            window(3, 50, 50, 400, 300),
+        ]);
+        assert_eq!(states[0].topmost_window, Some(2));
+        assert_eq!(states[1].topmost_window, Some(1));
+
+        let states = summarize_stack(Vec::new());
+        assert_eq!(states[0].topmost_window, None);
+        assert_eq!(states[0].work_area_coverage, 0.0);
+    }
+}
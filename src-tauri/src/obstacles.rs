@@ -0,0 +1,337 @@
+// Custom static obstacles (ledges, gutters, shelves) stored per monitor layout.
+// Shapes are kept as fractions of their monitor's logical size, keyed by a layout
+// fingerprint (monitor identities + sizes + scales, not positions or enumeration order),
+// and attached to a monitor by stable id, so rearranging or re-plugging monitors just
+// re-projects them. Projected rects go to the renderer as extra collision geometry.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tauri::{Emitter, Manager};
+
+use crate::rain_zones::{self, ProjectedZone};
+use crate::types::{MonitorRegion, VirtualDesktop};
+use crate::window_detector::Bounds;
+
+/// Rect in fractions (0.0–1.0) of the monitor's logical width/height
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RelativeRect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Obstacle {
+    pub id: u64,
+    pub name: String,
+    /// Monitor index when last edited (display only; `monitor_id` is authoritative)
+    pub monitor: usize,
+    /// Stable monitor id (empty for obstacles saved before monitor identity)
+    #[serde(default)]
+    pub monitor_id: String,
+    pub rect: RelativeRect,
+}
+
+/// Obstacle fields a caller can set (id is assigned on add)
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ObstacleDraft {
+    pub name: String,
+    pub monitor: usize,
+    pub rect: RelativeRect,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ProjectedObstacle {
+    pub id: u64,
+    pub name: String,
+    pub monitor: usize,
+    /// Virtual-desktop logical coords, like window bounds
+    pub bounds: Bounds,
+}
+
+/// Payload of `static-geometry` and `get_static_geometry`
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StaticGeometry {
+    pub layout: String,
+    pub obstacles: Vec<ProjectedObstacle>,
+    /// Rain zones (already folded into the void mask; a change means reload it)
+    pub rain_zones: Vec<ProjectedZone>,
+}
+
+// obstacles.json: layout fingerprint -> obstacles drawn on that layout
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ObstacleStore {
+    layouts: HashMap<String, Vec<Obstacle>>,
+    /// Next id to hand out. Only ever grows, so a deleted obstacle's id is never reused
+    /// (the renderer and the panel key edits by id)
+    #[serde(default)]
+    next_id: u64,
+}
+
+impl ObstacleStore {
+    // Ids start at 1; stores written before the counter continue past every id in use
+    fn settle_counter(mut self) -> Self {
+        let highest = self.layouts.values().flatten().map(|o| o.id).max().unwrap_or(0);
+        self.next_id = self.next_id.max(highest + 1);
+        self
+    }
+
+    fn allocate_id(&mut self) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+}
+
+fn get_store_path(app: &tauri::AppHandle) -> Option<PathBuf> {
+    app.path().app_data_dir().ok().map(|d| d.join("obstacles.json"))
+}
+
+fn load_store(app: &tauri::AppHandle) -> ObstacleStore {
+    get_store_path(app)
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|s| serde_json::from_str::<ObstacleStore>(&s).ok())
+        .unwrap_or_default()
+        .settle_counter()
+}
+
+fn save_store(app: &tauri::AppHandle, store: &ObstacleStore) -> Result<(), String> {
+    let path = get_store_path(app).ok_or("Failed to get app data directory")?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+    let json = serde_json::to_string_pretty(store).map_err(|e| format!("Failed to serialize: {}", e))?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write obstacles: {}", e))
+}
+
+/// Layout identity: each monitor's id, logical size and scale, sorted by id.
+/// Positions and enumeration order are left out so moving or re-plugging monitors
+/// re-projects instead of resetting.
+pub(crate) fn layout_fingerprint(desktop: &VirtualDesktop) -> String {
+    let mut monitors: Vec<String> = desktop.monitors.iter()
+        .map(|m| format!("{}={}x{}@{:.2}", m.identity.id, m.width, m.height, m.scale_factor))
+        .collect();
+    monitors.sort();
+    monitors.join(";")
+}
+
+// Pre-identity fingerprint (sizes in index order); stored layouts under it are migrated
+fn legacy_fingerprint(desktop: &VirtualDesktop) -> String {
+    desktop.monitors.iter()
+        .map(|m| format!("{}x{}@{:.2}", m.width, m.height, m.scale_factor))
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+// The obstacle's monitor: by stable id, or by index for obstacles saved without one
+fn region_for<'a>(obstacle: &Obstacle, desktop: &'a VirtualDesktop) -> Option<&'a MonitorRegion> {
+    if obstacle.monitor_id.is_empty() {
+        desktop.monitors.iter().find(|m| m.index == obstacle.monitor)
+    } else {
+        desktop.monitors.iter().find(|m| m.identity.id == obstacle.monitor_id)
+    }
+}
+
+fn project_rect(rect: &RelativeRect, region: &MonitorRegion, desktop: &VirtualDesktop) -> Bounds {
+    let left = desktop.origin_x as f64 + region.x as f64 + rect.x * region.width as f64;
+    let top = desktop.origin_y as f64 + region.y as f64 + rect.y * region.height as f64;
+    let right = left + rect.width * region.width as f64;
+    let bottom = top + rect.height * region.height as f64;
+    Bounds {
+        x: left.round() as i32,
+        y: top.round() as i32,
+        width: (right.round() - left.round()).max(1.0) as u32,
+        height: (bottom.round() - top.round()).max(1.0) as u32,
+    }
+}
+
+/// Project the current layout's obstacles into logical virtual-desktop coords.
+/// Obstacles on a monitor the layout doesn't have are skipped.
+pub(crate) fn project(obstacles: &[Obstacle], desktop: &VirtualDesktop) -> Vec<ProjectedObstacle> {
+    obstacles.iter()
+        .filter_map(|o| {
+            let region = region_for(o, desktop)?;
+            Some(ProjectedObstacle {
+                id: o.id,
+                name: o.name.clone(),
+                monitor: region.index,
+                bounds: project_rect(&o.rect, region, desktop),
+            })
+        })
+        .collect()
+}
+
+fn validate(draft: &ObstacleDraft, desktop: &VirtualDesktop) -> Result<(), String> {
+    if !desktop.monitors.iter().any(|m| m.index == draft.monitor) {
+        return Err(format!("Monitor {} not in current layout", draft.monitor));
+    }
+    let r = &draft.rect;
+    let in_range = |v: f64| (0.0..=1.0).contains(&v);
+    if !(in_range(r.x) && in_range(r.y) && in_range(r.x + r.width) && in_range(r.y + r.height)) {
+        return Err("Obstacle must lie within its monitor (fractions 0.0–1.0)".to_string());
+    }
+    if r.width <= 0.0 || r.height <= 0.0 {
+        return Err("Obstacle width and height must be positive".to_string());
+    }
+    Ok(())
+}
+
+pub(crate) fn list(app: &tauri::AppHandle, desktop: &VirtualDesktop) -> Vec<Obstacle> {
+    let mut layouts = load_store(app).layouts;
+    let mut obstacles = layouts.remove(&layout_fingerprint(desktop))
+        .or_else(|| layouts.remove(&legacy_fingerprint(desktop)))
+        .unwrap_or_default();
+    // Report today's index for monitors that moved in the enumeration order
+    for o in &mut obstacles {
+        if let Some(region) = region_for(o, desktop) {
+            o.monitor = region.index;
+        }
+    }
+    obstacles
+}
+
+fn monitor_id_for(index: usize, desktop: &VirtualDesktop) -> String {
+    desktop.monitors.iter()
+        .find(|m| m.index == index)
+        .map(|m| m.identity.id.clone())
+        .unwrap_or_default()
+}
+
+pub(crate) fn static_geometry(app: &tauri::AppHandle, desktop: &VirtualDesktop) -> StaticGeometry {
+    StaticGeometry {
+        layout: layout_fingerprint(desktop),
+        obstacles: project(&list(app, desktop), desktop),
+        rain_zones: rain_zones::projected(app, desktop),
+    }
+}
+
+/// Send the current layout's projected obstacles (after edits or a layout change).
+pub(crate) fn publish(app: &tauri::AppHandle, desktop: &VirtualDesktop) {
+    let geometry = static_geometry(app, desktop);
+    log::info!("[Obstacles] Layout {}: {} obstacle(s), {} rain zone(s)",
+        geometry.layout, geometry.obstacles.len(), geometry.rain_zones.len());
+    if let Err(e) = app.emit("static-geometry", &geometry) {
+        log::error!("Failed to emit static-geometry: {}", e);
+    }
+}
+
+// Load the store, edit the current layout's list, save, re-publish
+fn edit<T>(
+    app: &tauri::AppHandle,
+    desktop: &VirtualDesktop,
+    change: impl FnOnce(&mut Vec<Obstacle>, &mut ObstacleStore) -> Result<T, String>,
+) -> Result<T, String> {
+    let mut store = load_store(app);
+    let layout = layout_fingerprint(desktop);
+    if !store.layouts.contains_key(&layout) {
+        if let Some(legacy) = store.layouts.remove(&legacy_fingerprint(desktop)) {
+            store.layouts.insert(layout.clone(), legacy);
+        }
+    }
+    // Edited outside the store so `change` can still allocate ids from it
+    let mut obstacles = store.layouts.remove(&layout).unwrap_or_default();
+    // Attach migrated obstacles to the monitor their index points at today
+    for o in obstacles.iter_mut().filter(|o| o.monitor_id.is_empty()) {
+        o.monitor_id = monitor_id_for(o.monitor, desktop);
+    }
+    let result = change(&mut obstacles, &mut store)?;
+    if !obstacles.is_empty() {
+        store.layouts.insert(layout, obstacles);
+    }
+    save_store(app, &store)?;
+    publish(app, desktop);
+    Ok(result)
+}
+
+pub(crate) fn add(app: &tauri::AppHandle, desktop: &VirtualDesktop, draft: ObstacleDraft) -> Result<Obstacle, String> {
+    validate(&draft, desktop)?;
+    edit(app, desktop, |obstacles, store| {
+        let obstacle = Obstacle {
+            id: store.allocate_id(),
+            monitor_id: monitor_id_for(draft.monitor, desktop),
+            name: draft.name,
+            monitor: draft.monitor,
+            rect: draft.rect,
+        };
+        obstacles.push(obstacle.clone());
+        Ok(obstacle)
+    })
+}
+
+pub(crate) fn update(app: &tauri::AppHandle, desktop: &VirtualDesktop, id: u64, draft: ObstacleDraft) -> Result<Obstacle, String> {
+    validate(&draft, desktop)?;
+    edit(app, desktop, |obstacles, _| {
+        let obstacle = obstacles.iter_mut()
+            .find(|o| o.id == id)
+            .ok_or_else(|| format!("Obstacle {} not found", id))?;
+        obstacle.monitor_id = monitor_id_for(draft.monitor, desktop);
+        obstacle.name = draft.name;
+        obstacle.monitor = draft.monitor;
+        obstacle.rect = draft.rect;
+        Ok(obstacle.clone())
+    })
+}
+
+pub(crate) fn delete(app: &tauri::AppHandle, desktop: &VirtualDesktop, id: u64) -> Result<(), String> {
+    edit(app, desktop, |obstacles, _| {
+        let before = obstacles.len();
+        obstacles.retain(|o| o.id != id);
+        if obstacles.len() == before {
+            return Err(format!("Obstacle {} not found", id));
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn obstacle(id: u64) -> Obstacle {
+        Obstacle {
+            id,
+            name: format!("Ledge {}", id),
+            monitor: 0,
+            monitor_id: "test".to_string(),
+            rect: RelativeRect { x: 0.1, y: 0.5, width: 0.2, height: 0.01 },
+        }
+    }
+
+    #[test]
+    fn ids_start_at_one() {
+        let mut store = ObstacleStore::default().settle_counter();
+        assert_eq!(store.allocate_id(), 1);
+        assert_eq!(store.allocate_id(), 2);
+    }
+
+    #[test]
+    fn deleted_ids_are_not_reused() {
+        let mut store = ObstacleStore::default().settle_counter();
+        let ids: Vec<u64> = (0..3).map(|_| store.allocate_id()).collect();
+        store.layouts.insert("a".to_string(), ids.iter().map(|&id| obstacle(id)).collect());
+
+        // Delete the newest, round-trip through obstacles.json, add again
+        store.layouts.get_mut("a").unwrap().retain(|o| o.id != 3);
+        let json = serde_json::to_string(&store).unwrap();
+        let mut store = serde_json::from_str::<ObstacleStore>(&json).unwrap().settle_counter();
+        assert_eq!(store.allocate_id(), 4);
+    }
+
+    #[test]
+    fn legacy_store_continues_past_every_layout() {
+        // Written before the counter: no nextId
+        let json = r#"{"layouts": {}}"#;
+        let mut store = serde_json::from_str::<ObstacleStore>(json).unwrap();
+        store.layouts.insert("a".to_string(), vec![obstacle(2)]);
+        store.layouts.insert("b".to_string(), vec![obstacle(7), obstacle(5)]);
+        let mut store = store.settle_counter();
+        assert_eq!(store.allocate_id(), 8);
+    }
+}
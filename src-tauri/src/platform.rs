@@ -1,5 +1,6 @@
 // OS-specific utilities: registry, monitors, GPU detection.
 
+use crate::layout::{Displays, MonitorDesc};
 use crate::types::Bounds;
 
 // Shared registry helper — eliminates duplicated unsafe registry boilerplate
@@ -115,7 +116,15 @@ pub(crate) fn get_monitor_work_area(x: i32, y: i32, width: u32, height: u32) ->
     }
 }
 
-#[cfg(not(target_os = "windows"))]
+// X11: monitor minus the struts docks/panels reserve, falling back to _NET_WORKAREA
+// when nothing publishes struts, and to the old 48 px guess without an X server
+#[cfg(target_os = "linux")]
+pub(crate) fn get_monitor_work_area(x: i32, y: i32, width: u32, height: u32) -> Bounds {
+    x11_work_area(x, y, width, height)
+        .unwrap_or(Bounds { x, y, width, height: height.saturating_sub(48) })
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
 pub(crate) fn get_monitor_work_area(x: i32, y: i32, width: u32, height: u32) -> Bounds {
     Bounds { x, y, width, height: height.saturating_sub(48) }
 }
@@ -169,7 +178,12 @@ pub(crate) fn get_monitor_refresh_rate(x: i32, y: i32, width: u32, height: u32)
     query_refresh_rate_win32(x, y, width, height).unwrap_or(60)
 }
 
-#[cfg(not(target_os = "windows"))]
+#[cfg(target_os = "linux")]
+pub(crate) fn get_monitor_refresh_rate(x: i32, y: i32, width: u32, height: u32) -> u32 {
+    query_refresh_rate_randr(x, y, width, height).unwrap_or(60)
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
 pub(crate) fn get_monitor_refresh_rate(_x: i32, _y: i32, _width: u32, _height: u32) -> u32 {
     60
 }
@@ -205,7 +219,25 @@ pub(crate) fn get_primary_monitor_index(monitors: &[tauri::Monitor]) -> usize {
     0
 }
 
-#[cfg(not(target_os = "windows"))]
+// XRandR primary output, else whichever monitor sits at (0,0)
+#[cfg(target_os = "linux")]
+pub(crate) fn get_primary_monitor_index(monitors: &[tauri::Monitor]) -> usize {
+    let positions: Vec<(i32, i32)> = monitors.iter()
+        .map(|m| {
+            let pos = m.position();
+            (pos.x, pos.y)
+        })
+        .collect();
+    primary_index(&positions, query_primary_position_randr())
+}
+
+#[cfg(target_os = "linux")]
+fn primary_index(positions: &[(i32, i32)], primary: Option<(i32, i32)>) -> usize {
+    let at = |pos: (i32, i32)| positions.iter().position(|&p| p == pos);
+    primary.and_then(at).or_else(|| at((0, 0))).unwrap_or(0)
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
 pub(crate) fn get_primary_monitor_index(_monitors: &[tauri::Monitor]) -> usize {
     0
 }
@@ -288,16 +320,792 @@ pub(crate) fn get_gpu_vram_gb() -> Option<f64> {
     None
 }
 
+/// Sorted (identity id, x, y, width, height, scale permille), one entry per monitor
+pub(crate) type MonitorSnapshot = Vec<(String, i32, i32, u32, u32, i32)>;
+
 // Monitor snapshot for hot-swap detection: count + geometry + scale factors
-pub(crate) fn get_monitor_snapshot(handle: &tauri::AppHandle) -> Vec<(i32, i32, u32, u32, i32)> {
+pub(crate) fn get_monitor_snapshot(handle: &tauri::AppHandle) -> MonitorSnapshot {
     let monitors = handle.available_monitors().unwrap_or_default();
-    let mut snapshot: Vec<(i32, i32, u32, u32, i32)> = monitors.iter().map(|m| {
+    // Identity included so swapping one monitor for another of the same size still registers
+    let identities = crate::monitor_identity::identify(&monitors);
+    let mut snapshot: MonitorSnapshot = monitors.iter().zip(identities).map(|(m, identity)| {
         let pos = m.position();
         let size = m.size();
         // Store scale as integer permille (1000 = 1.0×) to avoid float comparison issues
         let scale_permille = (m.scale_factor() * 1000.0) as i32;
-        (pos.x, pos.y, size.width, size.height, scale_permille)
+        (identity.id, pos.x, pos.y, size.width, size.height, scale_permille)
     }).collect();
     snapshot.sort(); // Deterministic order for comparison
     snapshot
 }
+
+/// Every monitor as a plain layout descriptor (see layout.rs), plus the primary's index
+pub(crate) fn describe_monitors(app: &tauri::AppHandle) -> Displays {
+    let monitors = app.available_monitors().unwrap_or_default();
+    if monitors.is_empty() {
+        return Displays::default();
+    }
+    let primary = get_primary_monitor_index(&monitors);
+    let identities = crate::monitor_identity::identify(&monitors);
+    let monitors = monitors.iter().zip(identities).map(|(m, identity)| {
+        let pos = m.position();
+        let size = m.size();
+        MonitorDesc {
+            bounds: Bounds { x: pos.x, y: pos.y, width: size.width, height: size.height },
+            work_area: get_monitor_work_area(pos.x, pos.y, size.width, size.height),
+            scale: m.scale_factor(),
+            refresh_rate: get_monitor_refresh_rate(pos.x, pos.y, size.width, size.height),
+            identity,
+        }
+    }).collect();
+    Displays { monitors, primary }
+}
+
+/// One active output as the OS reports it, matched to Tauri monitors by its
+/// top-left in physical desktop pixels. Feeds monitor_identity.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DisplayDescriptor {
+    pub x: i32,
+    pub y: i32,
+    /// e.g. "HDMI-1", "DP-2", "eDP-1"
+    pub connector: Option<String>,
+    /// OS-provided monitor name (Windows friendly name)
+    pub friendly_name: Option<String>,
+    /// Raw EDID block(s), when readable
+    pub edid: Option<Vec<u8>>,
+    /// PnP id from the device path ("DEL4090"), used when the EDID isn't readable
+    pub pnp_id: Option<String>,
+    /// Clockwise degrees: 0, 90, 180, 270
+    pub rotation: u32,
+}
+
+#[cfg(target_os = "windows")]
+fn read_registry_binary(subkey: &str, value_name: &str) -> Option<Vec<u8>> {
+    use windows::Win32::System::Registry::{
+        RegOpenKeyExW, RegQueryValueExW, RegCloseKey, HKEY_LOCAL_MACHINE, KEY_READ,
+    };
+    use windows::core::PCWSTR;
+
+    unsafe {
+        let mut hkey = std::mem::zeroed();
+        let subkey_wide: Vec<u16> = format!("{}\0", subkey).encode_utf16().collect();
+
+        if RegOpenKeyExW(HKEY_LOCAL_MACHINE, PCWSTR(subkey_wide.as_ptr()), Some(0), KEY_READ, &mut hkey).is_err() {
+            return None;
+        }
+
+        let value_wide: Vec<u16> = format!("{}\0", value_name).encode_utf16().collect();
+        let mut data = vec![0u8; 1024];
+        let mut data_size = data.len() as u32;
+
+        let result = RegQueryValueExW(
+            hkey,
+            PCWSTR(value_wide.as_ptr()),
+            None,
+            None,
+            Some(data.as_mut_ptr()),
+            Some(&mut data_size),
+        );
+
+        let _ = RegCloseKey(hkey);
+
+        if result.is_ok() {
+            data.truncate(data_size as usize);
+            Some(data)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn wide_to_string(wide: &[u16]) -> String {
+    let len = wide.iter().position(|&c| c == 0).unwrap_or(wide.len());
+    String::from_utf16_lossy(&wide[..len])
+}
+
+#[cfg(target_os = "windows")]
+pub(crate) fn get_display_descriptors() -> Vec<DisplayDescriptor> {
+    use windows::Win32::Devices::Display::*;
+
+    let mut descriptors = Vec::new();
+    unsafe {
+        let mut path_count = 0u32;
+        let mut mode_count = 0u32;
+        if GetDisplayConfigBufferSizes(QDC_ONLY_ACTIVE_PATHS, &mut path_count, &mut mode_count).is_err() {
+            return descriptors;
+        }
+        let mut paths = vec![DISPLAYCONFIG_PATH_INFO::default(); path_count as usize];
+        let mut modes = vec![DISPLAYCONFIG_MODE_INFO::default(); mode_count as usize];
+        if QueryDisplayConfig(
+            QDC_ONLY_ACTIVE_PATHS,
+            &mut path_count, paths.as_mut_ptr(),
+            &mut mode_count, modes.as_mut_ptr(),
+            None,
+        ).is_err() {
+            return descriptors;
+        }
+        paths.truncate(path_count as usize);
+        modes.truncate(mode_count as usize);
+
+        for path in &paths {
+            let Some(source_mode) = modes.get(path.sourceInfo.Anonymous.modeInfoIdx as usize)
+                .filter(|m| m.infoType == DISPLAYCONFIG_MODE_INFO_TYPE_SOURCE)
+                .map(|m| m.Anonymous.sourceMode)
+            else { continue };
+
+            let mut target = DISPLAYCONFIG_TARGET_DEVICE_NAME::default();
+            target.header.r#type = DISPLAYCONFIG_DEVICE_INFO_GET_TARGET_NAME;
+            target.header.size = std::mem::size_of::<DISPLAYCONFIG_TARGET_DEVICE_NAME>() as u32;
+            target.header.adapterId = path.targetInfo.adapterId;
+            target.header.id = path.targetInfo.id;
+            let named = DisplayConfigGetDeviceInfo(&mut target.header) == 0;
+
+            let technology = match path.targetInfo.outputTechnology {
+                DISPLAYCONFIG_OUTPUT_TECHNOLOGY_HD15 => "VGA",
+                DISPLAYCONFIG_OUTPUT_TECHNOLOGY_DVI => "DVI",
+                DISPLAYCONFIG_OUTPUT_TECHNOLOGY_HDMI => "HDMI",
+                DISPLAYCONFIG_OUTPUT_TECHNOLOGY_LVDS => "LVDS",
+                DISPLAYCONFIG_OUTPUT_TECHNOLOGY_DISPLAYPORT_EXTERNAL => "DP",
+                DISPLAYCONFIG_OUTPUT_TECHNOLOGY_DISPLAYPORT_EMBEDDED => "eDP",
+                DISPLAYCONFIG_OUTPUT_TECHNOLOGY_DISPLAYPORT_USB_TUNNEL => "USB-C",
+                DISPLAYCONFIG_OUTPUT_TECHNOLOGY_INTERNAL => "Internal",
+                DISPLAYCONFIG_OUTPUT_TECHNOLOGY_MIRACAST => "Miracast",
+                DISPLAYCONFIG_OUTPUT_TECHNOLOGY_INDIRECT_WIRED | DISPLAYCONFIG_OUTPUT_TECHNOLOGY_INDIRECT_VIRTUAL => "Indirect",
+                _ => "Other",
+            };
+            let rotation = match path.targetInfo.rotation {
+                DISPLAYCONFIG_ROTATION_ROTATE90 => 90,
+                DISPLAYCONFIG_ROTATION_ROTATE180 => 180,
+                DISPLAYCONFIG_ROTATION_ROTATE270 => 270,
+                _ => 0,
+            };
+
+            let mut descriptor = DisplayDescriptor {
+                x: source_mode.position.x,
+                y: source_mode.position.y,
+                rotation,
+                ..Default::default()
+            };
+            if named {
+                descriptor.connector = Some(format!("{}-{}", technology, target.connectorInstance));
+                let friendly = wide_to_string(&target.monitorFriendlyDeviceName);
+                descriptor.friendly_name = (!friendly.is_empty()).then_some(friendly);
+
+                // \\?\DISPLAY#DEL4090#5&2b1b3f&0&UID4353#{guid} -> Enum\DISPLAY\DEL4090\5&...\Device Parameters
+                let device_path = wide_to_string(&target.monitorDevicePath);
+                let parts: Vec<&str> = device_path.split('#').collect();
+                if parts.len() >= 3 {
+                    descriptor.pnp_id = Some(parts[1].to_string());
+                    descriptor.edid = read_registry_binary(
+                        &format!(r"SYSTEM\CurrentControlSet\Enum\DISPLAY\{}\{}\Device Parameters", parts[1], parts[2]),
+                        "EDID",
+                    );
+                }
+            }
+            descriptors.push(descriptor);
+        }
+    }
+    descriptors
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn get_display_descriptors() -> Vec<DisplayDescriptor> {
+    use x11rb::protocol::randr::{ConnectionExt as _, Rotation};
+    use x11rb::protocol::xproto::{AtomEnum, ConnectionExt as _};
+
+    with_x11(|conn, root| {
+        let resources = conn.randr_get_screen_resources_current(root)?.reply()?;
+        let edid_atom = conn.intern_atom(false, b"EDID")?.reply()?.atom;
+        let mut descriptors = Vec::new();
+
+        for &output in &resources.outputs {
+            let info = conn.randr_get_output_info(output, resources.config_timestamp)?.reply()?;
+            if info.crtc == 0 {
+                continue; // Disconnected or disabled
+            }
+            let crtc = conn.randr_get_crtc_info(info.crtc, resources.config_timestamp)?.reply()?;
+            let rotation = if crtc.rotation.contains(Rotation::ROTATE90) {
+                90
+            } else if crtc.rotation.contains(Rotation::ROTATE180) {
+                180
+            } else if crtc.rotation.contains(Rotation::ROTATE270) {
+                270
+            } else {
+                0
+            };
+            // 256 bytes = base block + one extension, in 32-bit units
+            let edid = conn.randr_get_output_property(output, edid_atom, AtomEnum::ANY, 0, 64, false, false)?
+                .reply()
+                .ok()
+                .map(|r| r.data)
+                .filter(|d| !d.is_empty());
+
+            descriptors.push(DisplayDescriptor {
+                x: crtc.x as i32,
+                y: crtc.y as i32,
+                connector: Some(String::from_utf8_lossy(&info.name).into_owned()),
+                friendly_name: None,
+                edid,
+                pnp_id: None,
+                rotation,
+            });
+        }
+        Ok(descriptors)
+    })
+    .unwrap_or_default()
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+pub(crate) fn get_display_descriptors() -> Vec<DisplayDescriptor> {
+    Vec::new()
+}
+
+// Pointer position in physical screen pixels (for the umbrella cursor)
+#[cfg(target_os = "windows")]
+pub(crate) fn get_cursor_position() -> Option<(i32, i32)> {
+    use windows::Win32::Foundation::POINT;
+    use windows::Win32::UI::WindowsAndMessaging::GetCursorPos;
+
+    let mut point = POINT::default();
+    unsafe { GetCursorPos(&mut point).ok()?; }
+    Some((point.x, point.y))
+}
+
+// One X connection per polling thread; dropped (and reconnected next call) on any error
+#[cfg(target_os = "linux")]
+fn with_x11<T>(
+    f: impl FnOnce(&x11rb::rust_connection::RustConnection, u32) -> Result<T, Box<dyn std::error::Error>>,
+) -> Option<T> {
+    use x11rb::connection::Connection;
+    use x11rb::rust_connection::RustConnection;
+
+    thread_local! {
+        static X11: std::cell::RefCell<Option<(RustConnection, u32)>> = const { std::cell::RefCell::new(None) };
+    }
+
+    X11.with(|cell| {
+        let mut slot = cell.borrow_mut();
+        if slot.is_none() {
+            let (conn, screen) = x11rb::connect(None).ok()?;
+            let root = conn.setup().roots.get(screen)?.root;
+            *slot = Some((conn, root));
+        }
+        let (conn, root) = slot.as_ref()?;
+        match f(conn, *root) {
+            Ok(value) => Some(value),
+            Err(_) => {
+                *slot = None;
+                None
+            }
+        }
+    })
+}
+
+// _NET_WM_STRUT_PARTIAL order: left, right, top, bottom widths (from the root window
+// edges), then the start/end ranges for each: left_y, right_y, top_x, bottom_x (inclusive)
+#[cfg(target_os = "linux")]
+type Strut = [u32; 12];
+
+#[cfg(target_os = "linux")]
+fn strut_from_legacy(v: &[u32], root_w: u32, root_h: u32) -> Strut {
+    let (max_x, max_y) = (root_w.saturating_sub(1), root_h.saturating_sub(1));
+    [v[0], v[1], v[2], v[3], 0, max_y, 0, max_y, 0, max_x, 0, max_x]
+}
+
+/// Monitor rect (physical) shrunk by every strut that reaches into it.
+#[cfg(target_os = "linux")]
+fn apply_struts(x: i32, y: i32, width: u32, height: u32, root_w: u32, root_h: u32, struts: &[Strut]) -> Bounds {
+    let (x, y) = (x as i64, y as i64);
+    let (right_edge, bottom_edge) = (x + width as i64, y + height as i64);
+    let (mut left, mut top, mut right, mut bottom) = (x, y, right_edge, bottom_edge);
+    // Inclusive strut range [a0, a1] against the monitor's half-open span [m0, m1)
+    let spans = |a0: u32, a1: u32, m0: i64, m1: i64| (a0 as i64) < m1 && (a1 as i64) >= m0;
+
+    for s in struts {
+        if s[0] > 0 && spans(s[4], s[5], y, bottom_edge) {
+            left = left.max(s[0] as i64);
+        }
+        if s[1] > 0 && spans(s[6], s[7], y, bottom_edge) {
+            right = right.min(root_w as i64 - s[1] as i64);
+        }
+        if s[2] > 0 && spans(s[8], s[9], x, right_edge) {
+            top = top.max(s[2] as i64);
+        }
+        if s[3] > 0 && spans(s[10], s[11], x, right_edge) {
+            bottom = bottom.min(root_h as i64 - s[3] as i64);
+        }
+    }
+
+    if right <= left || bottom <= top {
+        return Bounds { x: x as i32, y: y as i32, width, height };
+    }
+    Bounds {
+        x: left as i32,
+        y: top as i32,
+        width: (right - left) as u32,
+        height: (bottom - top) as u32,
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn x11_work_area(x: i32, y: i32, width: u32, height: u32) -> Option<Bounds> {
+    use x11rb::protocol::xproto::{AtomEnum, ConnectionExt};
+
+    with_x11(|conn, root| {
+        let geometry = conn.get_geometry(root)?.reply()?;
+        let (root_w, root_h) = (geometry.width as u32, geometry.height as u32);
+        let atom = |name: &[u8]| -> Result<u32, Box<dyn std::error::Error>> {
+            Ok(conn.intern_atom(false, name)?.reply()?.atom)
+        };
+        let strut_partial = atom(b"_NET_WM_STRUT_PARTIAL")?;
+        let strut_legacy = atom(b"_NET_WM_STRUT")?;
+        let client_list = atom(b"_NET_CLIENT_LIST")?;
+
+        // Docks are usually in the client list; unmanaged panels are direct root children
+        let mut candidates: Vec<u32> = conn.get_property(false, root, client_list, AtomEnum::WINDOW, 0, u32::MAX)?
+            .reply()?
+            .value32()
+            .map(|v| v.collect())
+            .unwrap_or_default();
+        candidates.extend(conn.query_tree(root)?.reply()?.children);
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        // Pipelined: send every request, then collect (one round-trip, not one per window)
+        let cookies = candidates.iter()
+            .map(|&w| Ok((
+                conn.get_property(false, w, strut_partial, AtomEnum::CARDINAL, 0, 12)?,
+                conn.get_property(false, w, strut_legacy, AtomEnum::CARDINAL, 0, 4)?,
+            )))
+            .collect::<Result<Vec<_>, x11rb::errors::ConnectionError>>()?;
+        let mut struts = Vec::new();
+        for (partial, legacy) in cookies {
+            // Windows can vanish between the list and the query; skip them
+            let partial: Vec<u32> = partial.reply().ok().and_then(|r| r.value32().map(|v| v.collect())).unwrap_or_default();
+            let legacy: Vec<u32> = legacy.reply().ok().and_then(|r| r.value32().map(|v| v.collect())).unwrap_or_default();
+            if let Ok(strut) = Strut::try_from(partial.as_slice()) {
+                struts.push(strut);
+            } else if legacy.len() == 4 {
+                struts.push(strut_from_legacy(&legacy, root_w, root_h));
+            }
+        }
+
+        if !struts.is_empty() {
+            return Ok(Some(apply_struts(x, y, width, height, root_w, root_h, &struts)));
+        }
+
+        // No struts: the WM's single desktop-wide work area, clipped to this monitor
+        let workarea: Vec<u32> = conn.get_property(false, root, atom(b"_NET_WORKAREA")?, AtomEnum::CARDINAL, 0, 4)?
+            .reply()?
+            .value32()
+            .map(|v| v.collect())
+            .unwrap_or_default();
+        let [wx, wy, ww, wh] = match workarea.as_slice() {
+            &[wx, wy, ww, wh] => [wx as i64, wy as i64, ww as i64, wh as i64],
+            _ => return Ok(Some(Bounds { x, y, width, height })),
+        };
+        let left = (x as i64).max(wx);
+        let top = (y as i64).max(wy);
+        let right = (x as i64 + width as i64).min(wx + ww);
+        let bottom = (y as i64 + height as i64).min(wy + wh);
+        if right <= left || bottom <= top {
+            return Ok(Some(Bounds { x, y, width, height }));
+        }
+        Ok(Some(Bounds { x: left as i32, y: top as i32, width: (right - left) as u32, height: (bottom - top) as u32 }))
+    })
+    .flatten()
+}
+
+#[cfg(target_os = "linux")]
+fn mode_refresh_hz(mode: &x11rb::protocol::randr::ModeInfo) -> Option<u32> {
+    use x11rb::protocol::randr::ModeFlag;
+
+    let mut vtotal = mode.vtotal as f64;
+    if mode.mode_flags.contains(ModeFlag::DOUBLE_SCAN) { vtotal *= 2.0; }
+    if mode.mode_flags.contains(ModeFlag::INTERLACE) { vtotal /= 2.0; }
+    if mode.htotal == 0 || vtotal == 0.0 {
+        return None;
+    }
+    let hz = mode.dot_clock as f64 / (mode.htotal as f64 * vtotal);
+    if hz >= 1.0 { Some(hz.round() as u32) } else { None }
+}
+
+// Refresh rate of the CRTC showing the monitor's center
+#[cfg(target_os = "linux")]
+fn query_refresh_rate_randr(x: i32, y: i32, width: u32, height: u32) -> Option<u32> {
+    use x11rb::protocol::randr::ConnectionExt;
+
+    let center_x = x + (width as i32 / 2);
+    let center_y = y + (height as i32 / 2);
+    with_x11(|conn, root| {
+        let resources = conn.randr_get_screen_resources_current(root)?.reply()?;
+        for &crtc in &resources.crtcs {
+            let info = conn.randr_get_crtc_info(crtc, resources.config_timestamp)?.reply()?;
+            if info.mode == 0 { continue; }
+            let (cx, cy) = (info.x as i32, info.y as i32);
+            let inside = center_x >= cx && center_x < cx + info.width as i32
+                && center_y >= cy && center_y < cy + info.height as i32;
+            if inside {
+                return Ok(resources.modes.iter().find(|m| m.id == info.mode).and_then(mode_refresh_hz));
+            }
+        }
+        Ok(None)
+    })
+    .flatten()
+}
+
+// Top-left of the RandR primary output's CRTC (physical)
+#[cfg(target_os = "linux")]
+fn query_primary_position_randr() -> Option<(i32, i32)> {
+    use x11rb::protocol::randr::ConnectionExt;
+
+    with_x11(|conn, root| {
+        let output = conn.randr_get_output_primary(root)?.reply()?.output;
+        if output == 0 {
+            return Ok(None);
+        }
+        let resources = conn.randr_get_screen_resources_current(root)?.reply()?;
+        let info = conn.randr_get_output_info(output, resources.config_timestamp)?.reply()?;
+        if info.crtc == 0 {
+            return Ok(None);
+        }
+        let crtc = conn.randr_get_crtc_info(info.crtc, resources.config_timestamp)?.reply()?;
+        Ok(Some((crtc.x as i32, crtc.y as i32)))
+    })
+    .flatten()
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn get_cursor_position() -> Option<(i32, i32)> {
+    use x11rb::protocol::xproto::ConnectionExt;
+
+    with_x11(|conn, root| {
+        let reply = conn.query_pointer(root)?.reply()?;
+        Ok((reply.root_x as i32, reply.root_y as i32))
+    })
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+pub(crate) fn get_cursor_position() -> Option<(i32, i32)> {
+    None
+}
+
+/// Which interaction modifiers are held right now
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct ModifierState {
+    pub ctrl: bool,
+    pub alt: bool,
+    pub shift: bool,
+}
+
+#[cfg(target_os = "windows")]
+pub(crate) fn get_modifier_state() -> ModifierState {
+    use windows::Win32::UI::Input::KeyboardAndMouse::{GetAsyncKeyState, VK_CONTROL, VK_MENU, VK_SHIFT};
+
+    // High bit = key currently down
+    let down = |vk: u16| unsafe { GetAsyncKeyState(vk as i32) } < 0;
+    ModifierState {
+        ctrl: down(VK_CONTROL.0),
+        alt: down(VK_MENU.0),
+        shift: down(VK_SHIFT.0),
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn get_modifier_state() -> ModifierState {
+    use x11rb::protocol::xproto::{ConnectionExt, KeyButMask};
+
+    with_x11(|conn, root| {
+        let mask = conn.query_pointer(root)?.reply()?.mask;
+        Ok(ModifierState {
+            ctrl: mask.contains(KeyButMask::CONTROL),
+            alt: mask.contains(KeyButMask::MOD1),
+            shift: mask.contains(KeyButMask::SHIFT),
+        })
+    })
+    .unwrap_or_default()
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+pub(crate) fn get_modifier_state() -> ModifierState {
+    ModifierState::default()
+}
+
+// Foreground/active window id (HWND or X window), to notice focus moving elsewhere
+#[cfg(target_os = "windows")]
+pub(crate) fn get_foreground_window() -> Option<u64> {
+    use windows::Win32::UI::WindowsAndMessaging::GetForegroundWindow;
+
+    let hwnd = unsafe { GetForegroundWindow() };
+    if hwnd.is_invalid() { None } else { Some(hwnd.0 as usize as u64) }
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn get_foreground_window() -> Option<u64> {
+    use x11rb::protocol::xproto::{AtomEnum, ConnectionExt};
+
+    with_x11(|conn, root| {
+        let atom = conn.intern_atom(false, b"_NET_ACTIVE_WINDOW")?.reply()?.atom;
+        let reply = conn.get_property(false, root, atom, AtomEnum::WINDOW, 0, 1)?.reply()?;
+        Ok(reply.value32().and_then(|mut v| v.next()).filter(|&w| w != 0).map(u64::from))
+    })
+    .flatten()
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+pub(crate) fn get_foreground_window() -> Option<u64> {
+    None
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use x11rb::protocol::randr::{ModeFlag, ModeInfo};
+
+    fn bounds(x: i32, y: i32, width: u32, height: u32) -> Bounds {
+        Bounds { x, y, width, height }
+    }
+
+    // Left 1920x1080 at 0,0 and right 2560x1440 at 1920,0: root is 4480x1440
+    const ROOT: (u32, u32) = (4480, 1440);
+
+    fn left(struts: &[Strut]) -> Bounds {
+        apply_struts(0, 0, 1920, 1080, ROOT.0, ROOT.1, struts)
+    }
+
+    fn right(struts: &[Strut]) -> Bounds {
+        apply_struts(1920, 0, 2560, 1440, ROOT.0, ROOT.1, struts)
+    }
+
+    #[test]
+    fn no_struts_leave_monitors_whole() {
+        assert_eq!(left(&[]), bounds(0, 0, 1920, 1080));
+        assert_eq!(right(&[]), bounds(1920, 0, 2560, 1440));
+    }
+
+    #[test]
+    fn partial_strut_only_hits_its_monitor() {
+        // Bottom panel on the shorter left monitor: measured from the root's bottom edge
+        let panel: Strut = [0, 0, 0, 400, 0, 0, 0, 0, 0, 0, 0, 1919];
+        assert_eq!(left(&[panel]), bounds(0, 0, 1920, 1040));
+        assert_eq!(right(&[panel]), bounds(1920, 0, 2560, 1440));
+    }
+
+    #[test]
+    fn legacy_strut_spans_every_monitor() {
+        let legacy = strut_from_legacy(&[0, 0, 0, 400], ROOT.0, ROOT.1);
+        assert_eq!(legacy[10..], [0, 4479]);
+        assert_eq!(left(&[legacy]), bounds(0, 0, 1920, 1040));
+        assert_eq!(right(&[legacy]), bounds(1920, 0, 2560, 1040));
+    }
+
+    #[test]
+    fn top_panel_on_secondary_monitor() {
+        let panel: Strut = [0, 0, 32, 0, 0, 0, 0, 0, 1920, 4479, 0, 0];
+        assert_eq!(left(&[panel]), bounds(0, 0, 1920, 1080));
+        assert_eq!(right(&[panel]), bounds(1920, 32, 2560, 1408));
+    }
+
+    #[test]
+    fn strut_spanning_both_monitors() {
+        // Top bar across the whole root plus a dock on the far right edge
+        let bar: Strut = [0, 0, 28, 0, 0, 0, 0, 0, 0, 4479, 0, 0];
+        let dock: Strut = [0, 64, 0, 0, 0, 0, 200, 1200, 0, 0, 0, 0];
+        assert_eq!(left(&[bar, dock]), bounds(0, 28, 1920, 1052));
+        assert_eq!(right(&[bar, dock]), bounds(1920, 28, 2496, 1412));
+    }
+
+    #[test]
+    fn left_strut_on_the_left_monitor_only() {
+        let dock: Strut = [48, 0, 0, 0, 0, 1079, 0, 0, 0, 0, 0, 0];
+        assert_eq!(left(&[dock]), bounds(48, 0, 1872, 1080));
+        // Its vertical range also covers the right monitor's rows, but the width stops at x=48
+        assert_eq!(right(&[dock]), bounds(1920, 0, 2560, 1440));
+    }
+
+    #[test]
+    fn strut_covering_the_monitor_is_ignored() {
+        let bogus: Strut = [0, 0, 2000, 0, 0, 0, 0, 0, 0, 1919, 0, 0];
+        assert_eq!(left(&[bogus]), bounds(0, 0, 1920, 1080));
+    }
+
+    fn mode(dot_clock: u32, htotal: u16, vtotal: u16, flags: u32) -> ModeInfo {
+        ModeInfo { dot_clock, htotal, vtotal, mode_flags: ModeFlag::from(flags), ..Default::default() }
+    }
+
+    #[test]
+    fn refresh_from_mode_timings() {
+        // CEA 1080p60 and 1080p50, CVT-RBv2 1440p144
+        assert_eq!(mode_refresh_hz(&mode(148_500_000, 2200, 1125, 0)), Some(60));
+        assert_eq!(mode_refresh_hz(&mode(148_500_000, 2640, 1125, 0)), Some(50));
+        assert_eq!(mode_refresh_hz(&mode(556_740_000, 2640, 1464, 0)), Some(144));
+        // VGA 640x480 rounds 59.94 up
+        assert_eq!(mode_refresh_hz(&mode(25_175_000, 800, 525, 0)), Some(60));
+    }
+
+    #[test]
+    fn refresh_interlaced_and_double_scan() {
+        // 1080i: vtotal counts both fields, so half of it per refresh
+        let interlace = u32::from(ModeFlag::INTERLACE);
+        assert_eq!(mode_refresh_hz(&mode(74_250_000, 2200, 1125, interlace)), Some(60));
+        let double_scan = u32::from(ModeFlag::DOUBLE_SCAN);
+        assert_eq!(mode_refresh_hz(&mode(25_175_000, 800, 525, double_scan)), Some(30));
+    }
+
+    #[test]
+    fn refresh_rejects_degenerate_modes() {
+        assert_eq!(mode_refresh_hz(&mode(148_500_000, 0, 1125, 0)), None);
+        assert_eq!(mode_refresh_hz(&mode(148_500_000, 2200, 0, 0)), None);
+        assert_eq!(mode_refresh_hz(&mode(0, 2200, 1125, 0)), None);
+        // Under 1 Hz is a bogus mode, not a rate
+        assert_eq!(mode_refresh_hz(&mode(1, 2200, 1125, 0)), None);
+    }
+
+    #[test]
+    fn primary_from_randr_else_origin() {
+        let positions = [(-1920, 0), (0, 0), (1920, 0)];
+        assert_eq!(primary_index(&positions, Some((1920, 0))), 2);
+        // RandR primary not among the monitors, or none set: whichever sits at (0,0)
+        assert_eq!(primary_index(&positions, Some((5000, 0))), 1);
+        assert_eq!(primary_index(&positions, None), 1);
+        assert_eq!(primary_index(&[(-1920, 0), (1920, 0)], None), 0);
+    }
+
+    // with_x11 connects through $DISPLAY, so Xvfb tests take turns
+    static XVFB_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+    // Killed (and reaped) on drop, including when an assertion fails
+    struct Xvfb(std::process::Child);
+
+    impl Drop for Xvfb {
+        fn drop(&mut self) {
+            let _ = self.0.kill();
+            let _ = self.0.wait();
+        }
+    }
+
+    // One 3840x1080 Xvfb screen on `display`, with $DISPLAY pointed at it
+    fn start_xvfb(display: &str, extra_args: &[&str]) -> (Xvfb, x11rb::rust_connection::RustConnection, u32) {
+        use x11rb::connection::Connection;
+
+        let child = std::process::Command::new("Xvfb")
+            .args([display, "-screen", "0", "3840x1080x24", "-nolisten", "tcp"])
+            .args(extra_args)
+            .spawn()
+            .expect("failed to start Xvfb");
+        let xvfb = Xvfb(child);
+        let connected = (0..50).find_map(|_| {
+            std::thread::sleep(std::time::Duration::from_millis(100));
+            x11rb::connect(Some(display)).ok()
+        });
+        let Some((conn, screen)) = connected else {
+            panic!("Xvfb did not come up on {} (args {:?})", display, extra_args);
+        };
+        let root = conn.setup().roots[screen].root;
+        std::env::set_var("DISPLAY", display);
+        (xvfb, conn, root)
+    }
+
+    // Two monitors side by side on one Xvfb screen, each with its own dock window.
+    // Needs Xvfb on PATH: cargo test xvfb -- --ignored
+    #[test]
+    #[ignore = "needs Xvfb"]
+    fn xvfb_struts_per_monitor() {
+        use x11rb::connection::Connection;
+        use x11rb::protocol::xproto::{AtomEnum, ConnectionExt, CreateWindowAux, PropMode, WindowClass};
+        use x11rb::wrapper::ConnectionExt as _;
+
+        let _lock = XVFB_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let (_xvfb, conn, root) = start_xvfb(":93", &[]);
+        let strut_partial = conn.intern_atom(false, b"_NET_WM_STRUT_PARTIAL").unwrap().reply().unwrap().atom;
+
+        let docks: [Strut; 2] = [
+            // Bottom panel on the left monitor
+            [0, 0, 0, 40, 0, 0, 0, 0, 0, 0, 0, 1919],
+            // Top bar on the right monitor
+            [0, 0, 30, 0, 0, 0, 0, 0, 1920, 3839, 0, 0],
+        ];
+        for strut in &docks {
+            let window = conn.generate_id().unwrap();
+            conn.create_window(0, window, root, 0, 0, 10, 10, 0, WindowClass::INPUT_OUTPUT, 0, &CreateWindowAux::new())
+                .unwrap();
+            conn.change_property32(PropMode::REPLACE, window, strut_partial, AtomEnum::CARDINAL, strut).unwrap();
+        }
+        conn.sync().unwrap();
+
+        assert_eq!(x11_work_area(0, 0, 1920, 1080), Some(bounds(0, 0, 1920, 1040)));
+        assert_eq!(x11_work_area(1920, 0, 1920, 1080), Some(bounds(1920, 30, 1920, 1050)));
+    }
+
+    // Two CRTCs (Xvfb -crtcs, xserver 21.1+) at 60 and 75 Hz with the right one primary,
+    // and no docks: work areas come from _NET_WORKAREA.
+    // Needs Xvfb on PATH: cargo test xvfb -- --ignored
+    #[test]
+    #[ignore = "needs Xvfb"]
+    fn xvfb_two_crtcs() {
+        use x11rb::protocol::randr::{ConnectionExt as _, Rotation, SetConfig};
+        use x11rb::protocol::xproto::{AtomEnum, ConnectionExt as _, PropMode};
+        use x11rb::wrapper::ConnectionExt as _;
+
+        let _lock = XVFB_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let (_xvfb, conn, root) = start_xvfb(":94", &["-crtcs", "2"]);
+
+        let resources = conn.randr_get_screen_resources_current(root).unwrap().reply().unwrap();
+        assert!(resources.crtcs.len() >= 2 && resources.outputs.len() >= 2, "Xvfb without -crtcs support");
+
+        // CEA 1080p60 timings, and the same with the dot clock raised to 75 Hz
+        let monitors = [(0, b"1920x1080_60".as_slice(), 148_500_000), (1920, b"1920x1080_75".as_slice(), 185_625_000)];
+        for (i, &(x, name, dot_clock)) in monitors.iter().enumerate() {
+            let info = ModeInfo {
+                width: 1920,
+                height: 1080,
+                dot_clock,
+                hsync_start: 2008,
+                hsync_end: 2052,
+                htotal: 2200,
+                vsync_start: 1084,
+                vsync_end: 1089,
+                vtotal: 1125,
+                name_len: name.len() as u16,
+                ..Default::default()
+            };
+            let mode = conn.randr_create_mode(root, info, name).unwrap().reply().unwrap().mode;
+            let output = resources.outputs[i];
+            conn.randr_add_output_mode(output, mode).unwrap();
+            let status = conn.randr_set_crtc_config(
+                resources.crtcs[i], 0, resources.config_timestamp, x, 0, mode, Rotation::ROTATE0, &[output],
+            ).unwrap().reply().unwrap().status;
+            assert_eq!(status, SetConfig::SUCCESS);
+        }
+        conn.randr_set_output_primary(root, resources.outputs[1]).unwrap();
+
+        // A 30px bottom bar the WM reports only through the desktop-wide work area
+        let workarea = conn.intern_atom(false, b"_NET_WORKAREA").unwrap().reply().unwrap().atom;
+        conn.change_property32(PropMode::REPLACE, root, workarea, AtomEnum::CARDINAL, &[0, 0, 3840, 1050]).unwrap();
+        conn.sync().unwrap();
+
+        assert_eq!(x11_work_area(0, 0, 1920, 1080), Some(bounds(0, 0, 1920, 1050)));
+        assert_eq!(x11_work_area(1920, 0, 1920, 1080), Some(bounds(1920, 0, 1920, 1050)));
+
+        assert_eq!(query_refresh_rate_randr(0, 0, 1920, 1080), Some(60));
+        assert_eq!(query_refresh_rate_randr(1920, 0, 1920, 1080), Some(75));
+        assert_eq!(get_monitor_refresh_rate(1920, 0, 1920, 1080), 75);
+
+        assert_eq!(query_primary_position_randr(), Some((1920, 0)));
+        assert_eq!(primary_index(&[(0, 0), (1920, 0)], query_primary_position_randr()), 1);
+
+        let descriptors = get_display_descriptors();
+        let positions: Vec<(i32, i32)> = descriptors.iter().map(|d| (d.x, d.y)).collect();
+        assert_eq!(positions, [(0, 0), (1920, 0)]);
+        assert!(descriptors.iter().all(|d| d.rotation == 0 && d.edid.is_none()));
+        let connectors: Vec<&str> = descriptors.iter().filter_map(|d| d.connector.as_deref()).collect();
+        assert_eq!(connectors.len(), 2);
+        assert_ne!(connectors[0], connectors[1]);
+
+        // No _NET_WORKAREA either: the whole monitor
+        conn.delete_property(root, workarea).unwrap();
+        conn.sync().unwrap();
+        assert_eq!(x11_work_area(1920, 0, 1920, 1080), Some(bounds(1920, 0, 1920, 1080)));
+    }
+}
@@ -0,0 +1,83 @@
+// Pointer tracker for the umbrella cursor. The overlay is click-through, so the backend
+// polls the pointer and streams it as `cursor-position` in virtual-desktop logical
+// coords. Runs only while enabled (rainscape system.umbrellaCursor); polls at the
+// fastest refresh rate while moving, backs off when idle and stops emitting.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use tauri::Emitter;
+
+use crate::platform::get_cursor_position;
+use crate::poll_scheduler;
+use crate::MONITOR_MAPPINGS;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static THREAD_RUNNING: AtomicBool = AtomicBool::new(false);
+
+// Idle back-off: (pointer still for at least, poll interval)
+const IDLE_BACKOFF: [(Duration, Duration); 2] = [
+    (Duration::from_millis(250), Duration::from_millis(50)),
+    (Duration::from_secs(3), Duration::from_millis(150)),
+];
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CursorPosition {
+    pub x: i32,
+    pub y: i32,
+    pub monitor: usize,
+}
+
+// Physical pointer -> logical via the monitor under it
+fn to_logical(x: i32, y: i32) -> Option<CursorPosition> {
+    let mappings = MONITOR_MAPPINGS.lock().ok()?;
+    let mapping = mappings.iter().find(|m| m.contains_physical(x, y))?;
+    let (lx, ly) = mapping.map_point(x, y);
+    Some(CursorPosition { x: lx, y: ly, monitor: mapping.index })
+}
+
+pub(crate) fn set_enabled(app: &tauri::AppHandle, enabled: bool) {
+    if ENABLED.swap(enabled, Ordering::Relaxed) == enabled {
+        return;
+    }
+    log::info!("[Pointer] Umbrella cursor tracking {}", if enabled { "enabled" } else { "disabled" });
+    if enabled && !THREAD_RUNNING.swap(true, Ordering::SeqCst) {
+        spawn_tracker(app.clone());
+    }
+}
+
+fn spawn_tracker(app: tauri::AppHandle) {
+    std::thread::spawn(move || {
+        let mut last: Option<(i32, i32)> = None;
+        let mut last_move = Instant::now();
+
+        while ENABLED.load(Ordering::Relaxed) {
+            let now = Instant::now();
+            let current = get_cursor_position();
+
+            if current.is_some() && current != last {
+                last = current;
+                last_move = now;
+                if let Some(position) = current.and_then(|(x, y)| to_logical(x, y)) {
+                    if let Err(e) = app.emit("cursor-position", &position) {
+                        log::error!("Failed to emit cursor-position: {}", e);
+                    }
+                }
+            }
+
+            let idle_for = now.duration_since(last_move);
+            let interval = IDLE_BACKOFF.iter()
+                .rev()
+                .find(|(after, _)| idle_for >= *after)
+                .map(|&(_, interval)| interval)
+                .unwrap_or_else(poll_scheduler::fast_interval);
+            std::thread::sleep(interval);
+        }
+
+        THREAD_RUNNING.store(false, Ordering::SeqCst);
+        // Re-enabled between the loop check and the flag reset: keep tracking
+        if ENABLED.load(Ordering::Relaxed) && !THREAD_RUNNING.swap(true, Ordering::SeqCst) {
+            spawn_tracker(app);
+        }
+    });
+}
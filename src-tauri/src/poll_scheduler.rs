@@ -0,0 +1,244 @@
+// Adaptive window-poll scheduler: polls at the fastest monitor's refresh rate while
+// windows move, backs off progressively on a static desktop, slows right down when
+// every monitor is fullscreen, and suspends entirely while rain is paused.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::RAIN_PAUSED;
+
+// Fastest monitor refresh rate, set whenever monitor mappings are rebuilt
+static FASTEST_REFRESH_HZ: AtomicU32 = AtomicU32::new(60);
+
+// Pause suspension: the poll thread parks on this until RAIN_PAUSED clears
+static WAKE_LOCK: Mutex<()> = Mutex::new(());
+static WAKE: Condvar = Condvar::new();
+
+static STATS: Mutex<PollStats> = Mutex::new(PollStats {
+    mode: PollMode::Active,
+    interval_ms: 16.7,
+    effective_hz: 0.0,
+    target_hz: 60,
+});
+
+// Back-off ladder: (desktop static for at least, poll interval)
+const BACKOFF: [(Duration, Duration); 4] = [
+    (Duration::from_secs(1), Duration::from_millis(50)),
+    (Duration::from_secs(5), Duration::from_millis(100)),
+    (Duration::from_secs(30), Duration::from_millis(250)),
+    (Duration::from_secs(120), Duration::from_millis(500)),
+];
+
+// Every monitor fullscreen: nothing to rain on, just watch for the app leaving
+const FULLSCREEN_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum PollMode {
+    Active,
+    Idle,
+    Fullscreen,
+    Suspended,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PollStats {
+    pub mode: PollMode,
+    pub interval_ms: f64,
+    /// Polls actually completed over the last second
+    pub effective_hz: f64,
+    /// Rate used while windows are moving (fastest monitor refresh rate)
+    pub target_hz: u32,
+}
+
+pub(crate) fn set_fastest_refresh_rate(hz: u32) {
+    FASTEST_REFRESH_HZ.store(hz.clamp(15, 360), Ordering::Relaxed);
+}
+
+/// One frame at the fastest monitor's refresh rate.
+pub(crate) fn fast_interval() -> Duration {
+    Duration::from_secs_f64(1.0 / FASTEST_REFRESH_HZ.load(Ordering::Relaxed) as f64)
+}
+
+pub(crate) fn stats() -> PollStats {
+    STATS.lock().map(|s| s.clone()).unwrap_or(PollStats {
+        mode: PollMode::Active,
+        interval_ms: 0.0,
+        effective_hz: 0.0,
+        target_hz: FASTEST_REFRESH_HZ.load(Ordering::Relaxed),
+    })
+}
+
+/// Call after changing RAIN_PAUSED so a suspended poll thread re-checks it.
+pub(crate) fn wake() {
+    let _guard = WAKE_LOCK.lock();
+    WAKE.notify_all();
+}
+
+/// Block the calling (poll) thread for as long as rain is paused.
+pub(crate) fn wait_while_paused() {
+    let Ok(mut guard) = WAKE_LOCK.lock() else { return };
+    if !RAIN_PAUSED.load(Ordering::Relaxed) {
+        return;
+    }
+
+    log::info!("[WindowPoll] Rain paused, suspending window detection");
+    if let Ok(mut stats) = STATS.lock() {
+        stats.mode = PollMode::Suspended;
+        stats.effective_hz = 0.0;
+    }
+
+    while RAIN_PAUSED.load(Ordering::Relaxed) {
+        guard = match WAKE.wait(guard) {
+            Ok(g) => g,
+            Err(_) => return,
+        };
+    }
+    log::info!("[WindowPoll] Rain resumed, window detection active");
+}
+
+pub(crate) struct PollScheduler {
+    last_change: Instant,
+    mode: PollMode,
+    rate_window_start: Instant,
+    polls_in_window: u32,
+    effective_hz: f64,
+}
+
+impl PollScheduler {
+    pub(crate) fn new(now: Instant) -> Self {
+        Self {
+            last_change: now,
+            mode: PollMode::Active,
+            rate_window_start: now,
+            polls_in_window: 0,
+            effective_hz: 0.0,
+        }
+    }
+
+    /// Treat the desktop as freshly changed (after a pause, so the first polls are fast).
+    pub(crate) fn reset(&mut self, now: Instant) {
+        self.last_change = now;
+        self.rate_window_start = now;
+        self.polls_in_window = 0;
+    }
+
+    /// Record one poll's outcome and return how long to sleep before the next.
+    pub(crate) fn record(&mut self, changed: bool, all_fullscreen: bool, now: Instant) -> Duration {
+        if changed {
+            self.last_change = now;
+        }
+
+        self.polls_in_window += 1;
+        let window = now.duration_since(self.rate_window_start);
+        if window >= Duration::from_secs(1) {
+            self.effective_hz = self.polls_in_window as f64 / window.as_secs_f64();
+            self.rate_window_start = now;
+            self.polls_in_window = 0;
+        }
+
+        let target_hz = FASTEST_REFRESH_HZ.load(Ordering::Relaxed);
+        let fast = fast_interval();
+        let static_for = now.duration_since(self.last_change);
+
+        let (mode, interval) = if static_for < BACKOFF[0].0 {
+            (PollMode::Active, fast)
+        } else if all_fullscreen {
+            (PollMode::Fullscreen, FULLSCREEN_INTERVAL)
+        } else {
+            let interval = BACKOFF.iter()
+                .rev()
+                .find(|(after, _)| static_for >= *after)
+                .map(|&(_, interval)| interval)
+                .unwrap_or(fast);
+            (PollMode::Idle, interval)
+        };
+
+        if mode != self.mode {
+            log::info!("[WindowPoll] {:?} -> {:?} ({} ms)", self.mode, mode, interval.as_millis());
+            self.mode = mode;
+        }
+
+        if let Ok(mut stats) = STATS.lock() {
+            *stats = PollStats {
+                mode,
+                interval_ms: (interval.as_secs_f64() * 10_000.0).round() / 10.0,
+                effective_hz: (self.effective_hz * 10.0).round() / 10.0,
+                target_hz,
+            };
+        }
+
+        interval
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    // Interval after `static_for` of an unchanged desktop, polled once at that point
+    fn after_static(static_for: Duration, all_fullscreen: bool) -> (PollMode, Duration) {
+        let start = Instant::now();
+        let mut scheduler = PollScheduler::new(start);
+        let interval = scheduler.record(false, all_fullscreen, start + static_for);
+        (scheduler.mode, interval)
+    }
+
+    #[test]
+    fn fast_while_recently_changed() {
+        assert_eq!(after_static(Duration::ZERO, false), (PollMode::Active, fast_interval()));
+        assert_eq!(after_static(ms(999), false), (PollMode::Active, fast_interval()));
+    }
+
+    #[test]
+    fn backoff_ladder() {
+        assert_eq!(after_static(ms(1_000), false), (PollMode::Idle, ms(50)));
+        assert_eq!(after_static(ms(4_999), false), (PollMode::Idle, ms(50)));
+        assert_eq!(after_static(ms(5_000), false), (PollMode::Idle, ms(100)));
+        assert_eq!(after_static(ms(30_000), false), (PollMode::Idle, ms(250)));
+        assert_eq!(after_static(ms(119_999), false), (PollMode::Idle, ms(250)));
+        assert_eq!(after_static(ms(120_000), false), (PollMode::Idle, ms(500)));
+        assert_eq!(after_static(Duration::from_secs(3_600), false), (PollMode::Idle, ms(500)));
+    }
+
+    #[test]
+    fn fullscreen_overrides_backoff_once_settled() {
+        // Still settling: fast, so leaving fullscreen is picked up right away
+        assert_eq!(after_static(ms(500), true), (PollMode::Active, fast_interval()));
+        assert_eq!(after_static(ms(1_000), true), (PollMode::Fullscreen, FULLSCREEN_INTERVAL));
+        assert_eq!(after_static(ms(5_000), true), (PollMode::Fullscreen, FULLSCREEN_INTERVAL));
+    }
+
+    #[test]
+    fn change_resets_to_fast_interval() {
+        let start = Instant::now();
+        let mut scheduler = PollScheduler::new(start);
+        assert_eq!(scheduler.record(false, false, start + ms(60_000)), ms(250));
+
+        assert_eq!(scheduler.record(true, false, start + ms(60_100)), fast_interval());
+        assert_eq!(scheduler.mode, PollMode::Active);
+        // The ladder restarts from the change, not from the start
+        assert_eq!(scheduler.record(false, false, start + ms(61_000)), fast_interval());
+        assert_eq!(scheduler.record(false, false, start + ms(61_100)), ms(50));
+
+        // reset() after a pause counts as a change too
+        scheduler.reset(start + ms(200_000));
+        assert_eq!(scheduler.record(false, false, start + ms(200_500)), fast_interval());
+    }
+
+    #[test]
+    fn effective_rate_over_one_second() {
+        let start = Instant::now();
+        let mut scheduler = PollScheduler::new(start);
+        for i in 1..=20 {
+            scheduler.record(true, false, start + ms(i * 50));
+        }
+        assert_eq!(scheduler.effective_hz, 20.0);
+    }
+}
@@ -0,0 +1,123 @@
+// Window title privacy: titles can be swapped for session-salted hashes or dropped
+// before they leave the detector (window-data payload, lifecycle events, logs).
+// The detector's title-based filters always run against the real title first.
+
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+use crate::settings::{load_settings, save_settings};
+use crate::PRIVACY_MENU_ITEMS;
+
+static MODE: AtomicU8 = AtomicU8::new(TitlePrivacy::Off as u8);
+
+// Fresh per launch, so hashes can't be correlated across sessions or bug reports
+static SALT: OnceLock<RandomState> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum TitlePrivacy {
+    #[default]
+    Off,
+    /// Replace titles with a salted hash (stable for the session, so changes still register)
+    Hash,
+    /// Send and log empty titles
+    Drop,
+}
+
+impl TitlePrivacy {
+    pub(crate) const ALL: [TitlePrivacy; 3] = [TitlePrivacy::Off, TitlePrivacy::Hash, TitlePrivacy::Drop];
+
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => TitlePrivacy::Hash,
+            2 => TitlePrivacy::Drop,
+            _ => TitlePrivacy::Off,
+        }
+    }
+
+    /// Tray menu item id
+    pub(crate) fn menu_id(self) -> &'static str {
+        match self {
+            TitlePrivacy::Off => "privacy_off",
+            TitlePrivacy::Hash => "privacy_hash",
+            TitlePrivacy::Drop => "privacy_drop",
+        }
+    }
+
+    pub(crate) fn from_menu_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|mode| mode.menu_id() == id)
+    }
+}
+
+pub(crate) fn mode() -> TitlePrivacy {
+    TitlePrivacy::from_u8(MODE.load(Ordering::Relaxed))
+}
+
+/// Switch modes and keep the tray check marks in sync (radio-style).
+pub(crate) fn set_mode(mode: TitlePrivacy) {
+    MODE.store(mode as u8, Ordering::Relaxed);
+    if let Ok(items) = PRIVACY_MENU_ITEMS.lock() {
+        for (item_mode, item) in items.iter() {
+            let _ = item.set_checked(*item_mode == mode);
+        }
+    }
+}
+
+/// Title as it may leave the detector under the current mode.
+// Only the Windows detector reports titles so far
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub(crate) fn redact(title: &str) -> String {
+    redact_as(mode(), title)
+}
+
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn redact_as(mode: TitlePrivacy, title: &str) -> String {
+    match mode {
+        TitlePrivacy::Off => title.to_string(),
+        TitlePrivacy::Hash => {
+            let salt = SALT.get_or_init(RandomState::new);
+            format!("#{:016x}", salt.hash_one(title))
+        }
+        TitlePrivacy::Drop => String::new(),
+    }
+}
+
+/// Change mode from the panel or tray: apply, persist, log.
+pub(crate) fn apply(app: &tauri::AppHandle, mode: TitlePrivacy) {
+    set_mode(mode);
+    let mut settings = load_settings(app);
+    settings.title_privacy = mode;
+    save_settings(app, &settings);
+    log::info!("Title privacy set to {:?}", mode);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TITLE: &str = "Quarterly report.xlsx - Excel";
+
+    #[test]
+    fn off_passes_titles_through() {
+        assert_eq!(redact_as(TitlePrivacy::Off, TITLE), TITLE);
+        assert_eq!(redact_as(TitlePrivacy::Off, ""), "");
+    }
+
+    #[test]
+    fn hash_is_stable_within_session_and_hides_title() {
+        let hashed = redact_as(TitlePrivacy::Hash, TITLE);
+        assert_eq!(hashed, redact_as(TitlePrivacy::Hash, TITLE));
+        assert_ne!(hashed, redact_as(TitlePrivacy::Hash, "Quarterly report.xlsx - Excel*"));
+        assert_ne!(hashed, redact_as(TitlePrivacy::Hash, ""));
+
+        assert!(!hashed.contains("Quarterly") && !hashed.contains("Excel"));
+        assert!(hashed.starts_with('#') && hashed.len() == 17);
+    }
+
+    #[test]
+    fn drop_sends_empty_titles() {
+        assert_eq!(redact_as(TitlePrivacy::Drop, TITLE), "");
+    }
+}
@@ -0,0 +1,240 @@
+// User-defined rain zones: named rects or polygons on one monitor, either rain-free (keep
+// rain off a video call or clock widget) or rain-only (confine rain on that monitor to the
+// zone). Shapes are stored in settings as fractions of the monitor's logical size and
+// attached to it by stable id, so they follow the monitor through any layout it appears
+// in. They are folded into the void mask (and so the spawn rows) by void_mask.rs, and
+// sent with the static geometry so renderers know when to reload it.
+
+use crate::obstacles::RelativeRect;
+use crate::settings::{load_settings, save_settings};
+use crate::types::{MonitorRegion, VirtualDesktop};
+use crate::window_detector::Bounds;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum ZoneKind {
+    /// No rain inside the zone
+    RainFree,
+    /// Rain on the zone's monitor only inside its rain-only zones
+    RainOnly,
+}
+
+/// Point in fractions (0.0–1.0) of the monitor's logical width/height
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RelativePoint {
+    pub x: f64,
+    pub y: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub(crate) enum ZoneShape {
+    Rect { rect: RelativeRect },
+    Polygon { points: Vec<RelativePoint> },
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RainZone {
+    pub id: u64,
+    pub name: String,
+    pub kind: ZoneKind,
+    /// Stable monitor id the shape is relative to
+    pub monitor_id: String,
+    /// Monitor index when listed (display only; `monitor_id` is authoritative)
+    pub monitor: usize,
+    pub shape: ZoneShape,
+}
+
+/// Zone fields a caller can set (id and monitor id are assigned from the current layout)
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RainZoneDraft {
+    pub name: String,
+    pub kind: ZoneKind,
+    pub monitor: usize,
+    pub shape: ZoneShape,
+}
+
+/// A zone in virtual-desktop logical coords, like window bounds
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ProjectedZone {
+    pub id: u64,
+    pub name: String,
+    pub kind: ZoneKind,
+    pub monitor: usize,
+    /// Closed outline (rects as their four corners)
+    pub points: Vec<(f64, f64)>,
+    pub bounds: Bounds,
+}
+
+impl ZoneShape {
+    fn outline(&self) -> Vec<RelativePoint> {
+        match self {
+            ZoneShape::Rect { rect: r } => vec![
+                RelativePoint { x: r.x, y: r.y },
+                RelativePoint { x: r.x + r.width, y: r.y },
+                RelativePoint { x: r.x + r.width, y: r.y + r.height },
+                RelativePoint { x: r.x, y: r.y + r.height },
+            ],
+            ZoneShape::Polygon { points } => points.clone(),
+        }
+    }
+}
+
+impl ProjectedZone {
+    /// Even-odd test against the outline (logical coords)
+    pub(crate) fn contains(&self, x: f64, y: f64) -> bool {
+        let mut inside = false;
+        let mut j = self.points.len().wrapping_sub(1);
+        for (i, &(xi, yi)) in self.points.iter().enumerate() {
+            let (xj, yj) = self.points[j];
+            if (yi > y) != (yj > y) && x < xi + (y - yi) * (xj - xi) / (yj - yi) {
+                inside = !inside;
+            }
+            j = i;
+        }
+        inside
+    }
+}
+
+fn project_zone(zone: &RainZone, region: &MonitorRegion, desktop: &VirtualDesktop) -> ProjectedZone {
+    let left = desktop.origin_x as f64 + region.x as f64;
+    let top = desktop.origin_y as f64 + region.y as f64;
+    let points: Vec<(f64, f64)> = zone.shape.outline().iter()
+        .map(|p| (left + p.x * region.width as f64, top + p.y * region.height as f64))
+        .collect();
+
+    let min_x = points.iter().map(|p| p.0).fold(f64::INFINITY, f64::min);
+    let min_y = points.iter().map(|p| p.1).fold(f64::INFINITY, f64::min);
+    let max_x = points.iter().map(|p| p.0).fold(f64::NEG_INFINITY, f64::max);
+    let max_y = points.iter().map(|p| p.1).fold(f64::NEG_INFINITY, f64::max);
+
+    ProjectedZone {
+        id: zone.id,
+        name: zone.name.clone(),
+        kind: zone.kind,
+        monitor: region.index,
+        points,
+        bounds: Bounds {
+            x: min_x.floor() as i32,
+            y: min_y.floor() as i32,
+            width: (max_x.ceil() - min_x.floor()).max(1.0) as u32,
+            height: (max_y.ceil() - min_y.floor()).max(1.0) as u32,
+        },
+    }
+}
+
+/// Project zones into logical virtual-desktop coords. Zones on monitors the layout
+/// doesn't have are skipped (and come back with the monitor).
+pub(crate) fn project(zones: &[RainZone], desktop: &VirtualDesktop) -> Vec<ProjectedZone> {
+    zones.iter()
+        .filter_map(|zone| {
+            let region = desktop.monitors.iter().find(|m| m.identity.id == zone.monitor_id)?;
+            Some(project_zone(zone, region, desktop))
+        })
+        .collect()
+}
+
+fn validate(draft: &RainZoneDraft, desktop: &VirtualDesktop) -> Result<String, String> {
+    let region = desktop.monitors.iter()
+        .find(|m| m.index == draft.monitor)
+        .ok_or_else(|| format!("Monitor {} not in current layout", draft.monitor))?;
+
+    let outline = draft.shape.outline();
+    let in_range = |v: f64| (0.0..=1.0).contains(&v);
+    if !outline.iter().all(|p| in_range(p.x) && in_range(p.y)) {
+        return Err("Zone must lie within its monitor (fractions 0.0–1.0)".to_string());
+    }
+    match &draft.shape {
+        ZoneShape::Rect { rect } if rect.width <= 0.0 || rect.height <= 0.0 => {
+            Err("Zone width and height must be positive".to_string())
+        }
+        ZoneShape::Polygon { points } if points.len() < 3 => {
+            Err("Polygon zones need at least 3 points".to_string())
+        }
+        _ => Ok(region.identity.id.clone()),
+    }
+}
+
+/// All saved zones, with today's index for monitors in the current layout
+pub(crate) fn list(app: &tauri::AppHandle, desktop: &VirtualDesktop) -> Vec<RainZone> {
+    let mut zones = load_settings(app).rain_zones;
+    for zone in &mut zones {
+        if let Some(region) = desktop.monitors.iter().find(|m| m.identity.id == zone.monitor_id) {
+            zone.monitor = region.index;
+        }
+    }
+    zones
+}
+
+/// Current layout's zones in logical coords (for the void mask and static geometry)
+pub(crate) fn projected(app: &tauri::AppHandle, desktop: &VirtualDesktop) -> Vec<ProjectedZone> {
+    project(&load_settings(app).rain_zones, desktop)
+}
+
+// Settings written before the counter continue past every id in use
+fn allocate_id(zones: &[RainZone], next_id: &mut u64) -> u64 {
+    let highest = zones.iter().map(|z| z.id).max().unwrap_or(0);
+    let id = (*next_id).max(highest + 1);
+    *next_id = id + 1;
+    id
+}
+
+// Load settings, edit the zone list, save, re-publish the static geometry
+fn edit<T>(
+    app: &tauri::AppHandle,
+    desktop: &VirtualDesktop,
+    change: impl FnOnce(&mut Vec<RainZone>, &mut u64) -> Result<T, String>,
+) -> Result<T, String> {
+    let mut settings = load_settings(app);
+    let result = change(&mut settings.rain_zones, &mut settings.rain_zone_next_id)?;
+    save_settings(app, &settings);
+    crate::obstacles::publish(app, desktop);
+    Ok(result)
+}
+
+pub(crate) fn add(app: &tauri::AppHandle, desktop: &VirtualDesktop, draft: RainZoneDraft) -> Result<RainZone, String> {
+    let monitor_id = validate(&draft, desktop)?;
+    edit(app, desktop, |zones, next_id| {
+        let zone = RainZone {
+            id: allocate_id(zones, next_id),
+            name: draft.name,
+            kind: draft.kind,
+            monitor_id,
+            monitor: draft.monitor,
+            shape: draft.shape,
+        };
+        log::info!("[RainZones] Added {:?} zone {} \"{}\" on monitor {}", zone.kind, zone.id, zone.name, zone.monitor);
+        zones.push(zone.clone());
+        Ok(zone)
+    })
+}
+
+pub(crate) fn update(app: &tauri::AppHandle, desktop: &VirtualDesktop, id: u64, draft: RainZoneDraft) -> Result<RainZone, String> {
+    let monitor_id = validate(&draft, desktop)?;
+    edit(app, desktop, |zones, _| {
+        let zone = zones.iter_mut()
+            .find(|z| z.id == id)
+            .ok_or_else(|| format!("Rain zone {} not found", id))?;
+        zone.name = draft.name;
+        zone.kind = draft.kind;
+        zone.monitor_id = monitor_id;
+        zone.monitor = draft.monitor;
+        zone.shape = draft.shape;
+        Ok(zone.clone())
+    })
+}
+
+pub(crate) fn delete(app: &tauri::AppHandle, desktop: &VirtualDesktop, id: u64) -> Result<(), String> {
+    edit(app, desktop, |zones, _| {
+        let before = zones.len();
+        zones.retain(|z| z.id != id);
+        if zones.len() == before {
+            return Err(format!("Rain zone {} not found", id));
+        }
+        Ok(())
+    })
+}
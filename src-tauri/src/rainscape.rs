@@ -180,6 +180,7 @@ pub(crate) fn create_default_rainscape() -> serde_json::Value {
             "fullscreenDetection": true,
             "audioMuffling": true,
             "windowCollision": true,
+            "umbrellaCursor": false,
             "audioChannels": 3
         }
     })
@@ -0,0 +1,561 @@
+// Window-detection recording + replay for reproducing collision bugs.
+// A recording is JSON Lines: one header (VirtualDesktop layout), then one frame per
+// changed poll (ms since start + projected, privacy-redacted windows). Replay feeds
+// those frames to the webviews in place of live detection, with play/pause/seek.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use chrono::Local;
+use tauri::Emitter;
+
+use crate::docks::DockSurface;
+use crate::logging::log_dir;
+use crate::types::VirtualDesktop;
+use crate::window_detector::{WindowData, WindowInfo};
+use crate::window_tracker::{self, WindowTracker};
+
+const FORMAT_VERSION: u32 = 1;
+
+// Stop recording past this size so a forgotten session can't fill the disk
+const MAX_RECORDING_BYTES: u64 = 50 * 1_048_576;
+
+// Replay frame clock (~60 fps, matches live polling while windows move)
+const REPLAY_TICK: Duration = Duration::from_millis(16);
+
+static RECORDER: Mutex<Option<Recorder>> = Mutex::new(None);
+static REPLAY: Mutex<Option<Replay>> = Mutex::new(None);
+static REPLAY_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RecordingHeader {
+    version: u32,
+    started_at: String,
+    desktop: VirtualDesktop,
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RecordedFrame {
+    /// Milliseconds since recording start
+    pub t: u64,
+    pub windows: Vec<WindowInfo>,
+    #[serde(default)]
+    pub docks: Vec<DockSurface>,
+}
+
+/// A parsed recording file; usable without a running app (headless regression replays).
+pub(crate) struct Recording {
+    pub desktop: VirtualDesktop,
+    pub frames: Vec<RecordedFrame>,
+}
+
+impl Recording {
+    pub(crate) fn duration_ms(&self) -> u64 {
+        self.frames.last().map(|f| f.t).unwrap_or(0)
+    }
+
+    /// Index of the frame on screen at `position_ms` (last frame at or before it).
+    pub(crate) fn frame_at(&self, position_ms: u64) -> Option<usize> {
+        self.frames.partition_point(|f| f.t <= position_ms).checked_sub(1)
+    }
+}
+
+fn recordings_dir() -> PathBuf {
+    log_dir().join("recordings")
+}
+
+/// Resolve a replay path inside `dir` (bare names are taken relative to it). Anything
+/// that resolves elsewhere, through `..` or a symlink, is refused.
+fn recording_in(dir: &Path, path: &Path) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let dir = dir.canonicalize()?;
+    let resolved = dir.join(path).canonicalize()?;
+    if !resolved.starts_with(&dir) {
+        return Err(format!("Recordings can only be replayed from {}", dir.display()).into());
+    }
+    Ok(resolved)
+}
+
+pub(crate) fn load_recording(path: &Path) -> Result<Recording, Box<dyn std::error::Error>> {
+    let mut lines = BufReader::new(File::open(path)?).lines();
+
+    let header: RecordingHeader = match lines.next() {
+        Some(line) => serde_json::from_str(&line?)?,
+        None => return Err("Recording is empty".into()),
+    };
+    if header.version > FORMAT_VERSION {
+        return Err(format!("Unsupported recording version {}", header.version).into());
+    }
+
+    let mut frames = Vec::new();
+    for line in lines {
+        let line = line?;
+        if line.trim().is_empty() { continue; }
+        frames.push(serde_json::from_str::<RecordedFrame>(&line)?);
+    }
+    // Tolerate hand-edited files
+    frames.sort_by_key(|f| f.t);
+
+    Ok(Recording { desktop: header.desktop, frames })
+}
+
+// Recording
+
+struct Recorder {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    started: Instant,
+    frames: u64,
+    bytes: u64,
+}
+
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RecordingSummary {
+    pub path: String,
+    pub frames: u64,
+    pub duration_ms: u64,
+    pub bytes: u64,
+}
+
+impl Recorder {
+    fn write_line<T: serde::Serialize>(&mut self, value: &T) -> std::io::Result<()> {
+        let line = serde_json::to_string(value)?;
+        self.writer.write_all(line.as_bytes())?;
+        self.writer.write_all(b"\n")?;
+        self.bytes += line.len() as u64 + 1;
+        Ok(())
+    }
+
+    fn summary(&self) -> RecordingSummary {
+        RecordingSummary {
+            path: self.path.display().to_string(),
+            frames: self.frames,
+            duration_ms: self.started.elapsed().as_millis() as u64,
+            bytes: self.bytes,
+        }
+    }
+}
+
+/// Start writing frames to a new file under logs/recordings; returns its path.
+pub(crate) fn start_recording(desktop: &VirtualDesktop) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let mut guard = RECORDER.lock().map_err(|_| "Recorder lock poisoned")?;
+    if let Some(active) = guard.as_ref() {
+        return Err(format!("Already recording to {}", active.path.display()).into());
+    }
+
+    let dir = recordings_dir();
+    std::fs::create_dir_all(&dir)?;
+    let timestamp = Local::now().format("%Y-%m-%d_%H-%M-%S");
+    let path = dir.join(format!("WindowRecording_{}.jsonl", timestamp));
+
+    let mut recorder = Recorder {
+        path: path.clone(),
+        writer: BufWriter::new(File::create(&path)?),
+        started: Instant::now(),
+        frames: 0,
+        bytes: 0,
+    };
+    recorder.write_line(&RecordingHeader {
+        version: FORMAT_VERSION,
+        started_at: Local::now().to_rfc3339(),
+        desktop: desktop.clone(),
+    })?;
+
+    log::info!("[Recording] Started: {}", path.display());
+    *guard = Some(recorder);
+    Ok(path)
+}
+
+pub(crate) fn stop_recording() -> Option<RecordingSummary> {
+    let mut recorder = RECORDER.lock().ok()?.take()?;
+    let _ = recorder.writer.flush();
+    let summary = recorder.summary();
+    log::info!("[Recording] Stopped: {} frames, {} ms, {} bytes",
+        summary.frames, summary.duration_ms, summary.bytes);
+    Some(summary)
+}
+
+/// Called by the poll loop each cycle; only changed polls (and the first) are stored.
+pub(crate) fn record_frame(data: &WindowData, changed: bool) {
+    let Ok(mut guard) = RECORDER.lock() else { return };
+    let Some(recorder) = guard.as_mut() else { return };
+    if !changed && recorder.frames > 0 {
+        return;
+    }
+
+    let frame = RecordedFrame {
+        t: recorder.started.elapsed().as_millis() as u64,
+        windows: data.windows.clone(),
+        docks: data.docks.clone(),
+    };
+    let result = recorder.write_line(&frame);
+    recorder.frames += 1;
+
+    if let Err(e) = result {
+        log::error!("[Recording] Write failed, stopping: {}", e);
+    } else if recorder.bytes >= MAX_RECORDING_BYTES {
+        log::warn!("[Recording] Size limit reached ({} bytes), stopping", recorder.bytes);
+    } else {
+        return;
+    }
+    if let Some(mut recorder) = guard.take() {
+        let _ = recorder.writer.flush();
+    }
+}
+
+// Replay
+
+struct Replay {
+    path: PathBuf,
+    recording: Recording,
+    playing: bool,
+    /// Position at `anchor`; while playing, position at `now` = this + (now - anchor)
+    position_ms: u64,
+    anchor: Instant,
+    /// Bumped per replay so a superseded replay thread knows to exit
+    generation: u64,
+}
+
+impl Replay {
+    fn position(&self, now: Instant) -> u64 {
+        let pos = if self.playing {
+            self.position_ms + now.saturating_duration_since(self.anchor).as_millis() as u64
+        } else {
+            self.position_ms
+        };
+        pos.min(self.recording.duration_ms())
+    }
+
+    fn set_playing(&mut self, playing: bool, now: Instant) {
+        self.position_ms = self.position(now);
+        // Play at the end restarts from the beginning
+        if playing && self.position_ms >= self.recording.duration_ms() {
+            self.position_ms = 0;
+        }
+        self.anchor = now;
+        self.playing = playing;
+    }
+
+    fn seek(&mut self, position_ms: u64, now: Instant) {
+        self.position_ms = position_ms.min(self.recording.duration_ms());
+        self.anchor = now;
+    }
+
+    /// One replay-thread tick: the frame to emit when the clock has moved onto a different
+    /// one than `shown`, and whether playback just reached the end.
+    // Outer None in `shown` = nothing emitted yet (so a recording starting after t=0 still
+    // clears live data)
+    fn tick(&mut self, shown: &mut Option<Option<usize>>, now: Instant) -> (Option<WindowData>, bool) {
+        let position = self.position(now);
+        let finished = self.playing && position >= self.recording.duration_ms();
+        if finished {
+            self.position_ms = position;
+            self.playing = false;
+        }
+
+        let index = self.recording.frame_at(position);
+        if *shown == Some(index) {
+            return (None, finished);
+        }
+        *shown = Some(index);
+        let (windows, docks) = index
+            .map(|i| &self.recording.frames[i])
+            .map(|f| (f.windows.clone(), f.docks.clone()))
+            .unwrap_or_default();
+        (Some(WindowData { windows, docks, native_docks: Vec::new() }), finished)
+    }
+
+    fn status(&self, now: Instant) -> ReplayStatus {
+        ReplayStatus {
+            path: self.path.display().to_string(),
+            playing: self.playing,
+            position_ms: self.position(now),
+            duration_ms: self.recording.duration_ms(),
+            frames: self.recording.frames.len(),
+            desktop: self.recording.desktop.clone(),
+        }
+    }
+}
+
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ReplayStatus {
+    pub path: String,
+    pub playing: bool,
+    pub position_ms: u64,
+    pub duration_ms: u64,
+    pub frames: usize,
+    /// Layout the recording was made on (may differ from the current one)
+    pub desktop: VirtualDesktop,
+}
+
+/// Live detection stays quiet while this is true.
+pub(crate) fn is_replaying() -> bool {
+    REPLAY.lock().map(|r| r.is_some()).unwrap_or(false)
+}
+
+pub(crate) fn replay_status() -> Option<ReplayStatus> {
+    REPLAY.lock().ok()?.as_ref().map(|r| r.status(Instant::now()))
+}
+
+fn emit_replay_state(app: &tauri::AppHandle, status: Option<ReplayStatus>) {
+    if let Err(e) = app.emit("window-replay-state", status) {
+        log::error!("Failed to emit window-replay-state: {}", e);
+    }
+}
+
+/// Load a recording from logs/recordings and start playing it from the beginning.
+pub(crate) fn start_replay(app: &tauri::AppHandle, path: &Path) -> Result<ReplayStatus, Box<dyn std::error::Error>> {
+    let path = &recording_in(&recordings_dir(), path)?;
+    let recording = load_recording(path)?;
+    log::info!("[Replay] Loaded {}: {} frames, {} ms",
+        path.display(), recording.frames.len(), recording.duration_ms());
+
+    let status = {
+        let mut guard = REPLAY.lock().map_err(|_| "Replay lock poisoned")?;
+        let generation = REPLAY_GENERATION.fetch_add(1, Ordering::Relaxed) + 1;
+        let replay = Replay {
+            path: path.to_path_buf(),
+            recording,
+            playing: true,
+            position_ms: 0,
+            anchor: Instant::now(),
+            generation,
+        };
+        let status = replay.status(Instant::now());
+        *guard = Some(replay);
+        spawn_replay_thread(app.clone(), generation);
+        status
+    };
+
+    emit_replay_state(app, Some(status.clone()));
+    Ok(status)
+}
+
+pub(crate) fn set_replay_playing(app: &tauri::AppHandle, playing: bool) -> Result<ReplayStatus, String> {
+    update_replay(app, |replay, now| replay.set_playing(playing, now))
+}
+
+pub(crate) fn seek_replay(app: &tauri::AppHandle, position_ms: u64) -> Result<ReplayStatus, String> {
+    update_replay(app, |replay, now| replay.seek(position_ms, now))
+}
+
+fn update_replay(app: &tauri::AppHandle, change: impl FnOnce(&mut Replay, Instant)) -> Result<ReplayStatus, String> {
+    let status = {
+        let mut guard = REPLAY.lock().map_err(|e| format!("Replay lock poisoned: {}", e))?;
+        let replay = guard.as_mut().ok_or("No replay loaded")?;
+        let now = Instant::now();
+        change(replay, now);
+        replay.status(now)
+    };
+    emit_replay_state(app, Some(status.clone()));
+    Ok(status)
+}
+
+/// End the replay; live detection resumes on its next poll.
+pub(crate) fn stop_replay(app: &tauri::AppHandle) {
+    let stopped = REPLAY.lock().ok().and_then(|mut r| r.take()).is_some();
+    if stopped {
+        log::info!("[Replay] Stopped, resuming live detection");
+        emit_replay_state(app, None);
+    }
+}
+
+// Emits frames as the replay clock passes them, through a WindowTracker so replayed
+// sessions produce the same lifecycle events live detection would
+fn spawn_replay_thread(app: tauri::AppHandle, generation: u64) {
+    std::thread::spawn(move || {
+        let mut tracker = WindowTracker::new();
+        let mut shown = None;
+        loop {
+            std::thread::sleep(REPLAY_TICK);
+
+            let (data, finished) = {
+                let Ok(mut guard) = REPLAY.lock() else { return };
+                let Some(replay) = guard.as_mut().filter(|r| r.generation == generation) else { return };
+
+                let now = Instant::now();
+                let (data, finished) = replay.tick(&mut shown, now);
+                (data, finished.then(|| replay.status(now)))
+            };
+
+            if let Some(data) = data {
+                let update = tracker.update(&data, Instant::now());
+                window_tracker::emit_update(&app, &update, &data);
+            }
+            if let Some(status) = finished {
+                log::info!("[Replay] Reached end ({} ms)", status.duration_ms);
+                emit_replay_state(&app, Some(status));
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::window_detector::Bounds;
+
+    fn window(id: u64, x: i32) -> WindowInfo {
+        WindowInfo {
+            id,
+            bounds: Bounds { x, y: 100, width: 400, height: 300 },
+            title: String::new(),
+            is_maximized: false,
+            z_order: 0,
+            process_name: String::new(),
+            class_name: String::new(),
+            pid: 0,
+            is_focused: false,
+            is_topmost: false,
+            is_fullscreen: false,
+            pieces: Vec::new(),
+            visible_rects: Vec::new(),
+            exposed_top: Vec::new(),
+        }
+    }
+
+    // Fresh directory under the system temp dir, unique per test
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("rainydesk-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    // Header plus frames at t = 0 (one window), 500 (moved), 1000 (second window), 1500 (empty)
+    fn write_recording(path: &Path) {
+        let header = serde_json::json!({
+            "version": FORMAT_VERSION,
+            "startedAt": "2026-01-01T00:00:00+00:00",
+            "desktop": {
+                "originX": 0, "originY": 0, "width": 1920, "height": 1080,
+                "monitors": [], "primaryIndex": 0, "primaryScaleFactor": 1.0,
+            },
+        });
+        let frames = [
+            RecordedFrame { t: 1000, windows: vec![window(1, 50), window(2, 600)], docks: Vec::new() },
+            RecordedFrame { t: 0, windows: vec![window(1, 0)], docks: Vec::new() },
+            RecordedFrame { t: 500, windows: vec![window(1, 50)], docks: Vec::new() },
+            RecordedFrame { t: 1500, windows: Vec::new(), docks: Vec::new() },
+        ];
+        let mut out = header.to_string() + "\n";
+        for frame in &frames {
+            out += &serde_json::to_string(frame).unwrap();
+            out += "\n\n";
+        }
+        std::fs::write(path, out).unwrap();
+    }
+
+    fn replay(recording: Recording, start: Instant) -> Replay {
+        Replay {
+            path: PathBuf::new(),
+            recording,
+            playing: false,
+            position_ms: 0,
+            anchor: start,
+            generation: 0,
+        }
+    }
+
+    // The replay clock `ms` after `start`
+    fn at(start: Instant, ms: u64) -> Instant {
+        start + Duration::from_millis(ms)
+    }
+
+    // Windows (id, x) of the frame a tick emits, None when it emits nothing
+    fn emitted(replay: &mut Replay, shown: &mut Option<Option<usize>>, now: Instant) -> Option<Vec<(u64, i32)>> {
+        let (data, _) = replay.tick(shown, now);
+        data.map(|d| d.windows.iter().map(|w| (w.id, w.bounds.x)).collect())
+    }
+
+    #[test]
+    fn loads_sorted_frames() {
+        let dir = scratch_dir("load");
+        let path = dir.join("session.jsonl");
+        write_recording(&path);
+
+        let recording = load_recording(&path).unwrap();
+        assert_eq!(recording.desktop.width, 1920);
+        assert_eq!(recording.frames.iter().map(|f| f.t).collect::<Vec<_>>(), [0, 500, 1000, 1500]);
+        assert_eq!(recording.duration_ms(), 1500);
+        assert_eq!(recording.frame_at(0), Some(0));
+        assert_eq!(recording.frame_at(499), Some(0));
+        assert_eq!(recording.frame_at(500), Some(1));
+        assert_eq!(recording.frame_at(99_999), Some(3));
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn play_pause_and_seek_pick_frames() {
+        let dir = scratch_dir("replay");
+        let path = dir.join("session.jsonl");
+        write_recording(&path);
+        let t0 = Instant::now();
+        let mut replay = replay(load_recording(&path).unwrap(), t0);
+        let mut shown = None;
+
+        // Paused at the start: first frame once, then the clock doesn't move
+        assert_eq!(emitted(&mut replay, &mut shown, t0), Some(vec![(1, 0)]));
+        assert_eq!(emitted(&mut replay, &mut shown, at(t0, 800)), None);
+        assert_eq!(replay.position(at(t0, 800)), 0);
+
+        replay.seek(750, at(t0, 800));
+        assert_eq!(emitted(&mut replay, &mut shown, at(t0, 800)), Some(vec![(1, 50)]));
+        replay.seek(1000, at(t0, 900));
+        assert_eq!(emitted(&mut replay, &mut shown, at(t0, 900)), Some(vec![(1, 50), (2, 600)]));
+
+        // Playing advances from the seek point; pausing freezes it
+        replay.set_playing(true, at(t0, 1000));
+        assert_eq!(emitted(&mut replay, &mut shown, at(t0, 1200)), None);
+        replay.set_playing(false, at(t0, 1300));
+        assert_eq!(replay.position(at(t0, 1300)), 1300);
+        assert_eq!(replay.position(at(t0, 5000)), 1300);
+        assert_eq!(emitted(&mut replay, &mut shown, at(t0, 5000)), None);
+
+        // Resumed: the empty last frame comes out as the clock passes 1500, and playback stops
+        replay.set_playing(true, at(t0, 6000));
+        let (data, finished) = replay.tick(&mut shown, at(t0, 6250));
+        assert!(data.unwrap().windows.is_empty());
+        assert!(finished && !replay.playing);
+        // Reported once, not on every later tick
+        let (data, finished) = replay.tick(&mut shown, at(t0, 7000));
+        assert!(data.is_none() && !finished);
+
+        // Seeking past the end clamps; play there restarts from the beginning
+        replay.seek(60_000, at(t0, 8000));
+        assert_eq!(replay.position(at(t0, 8000)), 1500);
+        replay.set_playing(true, at(t0, 8000));
+        assert_eq!(replay.position(at(t0, 8100)), 100);
+        assert_eq!(emitted(&mut replay, &mut shown, at(t0, 8100)), Some(vec![(1, 0)]));
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn replays_only_from_recordings_dir() {
+        let dir = scratch_dir("paths");
+        let recordings = dir.join("recordings");
+        std::fs::create_dir_all(&recordings).unwrap();
+        let inside = recordings.join("session.jsonl");
+        let outside = dir.join("elsewhere.jsonl");
+        write_recording(&inside);
+        write_recording(&outside);
+
+        let resolved = recording_in(&recordings, &inside).unwrap();
+        assert_eq!(resolved, inside.canonicalize().unwrap());
+        assert_eq!(recording_in(&recordings, Path::new("session.jsonl")).unwrap(), resolved);
+
+        assert!(recording_in(&recordings, &outside).is_err());
+        assert!(recording_in(&recordings, Path::new("../elsewhere.jsonl")).is_err());
+        assert!(recording_in(&recordings, Path::new("missing.jsonl")).is_err());
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,28 @@
+// App-wide settings persistence (settings.json in app data). Panel position and
+// UI scale live separately in panel-config.json (window_mgmt.rs).
+
+use std::path::PathBuf;
+use tauri::Manager;
+
+use crate::types::AppSettings;
+
+pub(crate) fn get_settings_path(app: &tauri::AppHandle) -> Option<PathBuf> {
+    app.path().app_data_dir().ok().map(|d| d.join("settings.json"))
+}
+
+pub(crate) fn load_settings(app: &tauri::AppHandle) -> AppSettings {
+    get_settings_path(app)
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+pub(crate) fn save_settings(app: &tauri::AppHandle, settings: &AppSettings) {
+    let Some(path) = get_settings_path(app) else { return };
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Ok(json) = serde_json::to_string_pretty(settings) {
+        let _ = std::fs::write(&path, json);
+    }
+}
@@ -0,0 +1,343 @@
+// Overlay/background surfaces. Mega mode (default): one "overlay" and one "background"
+// window spanning the whole virtual desktop. Per-monitor mode: "overlay-N"/"background-N"
+// sized to monitor N, so void space gets no surface, each window renders at its own DPI and
+// refresh rate, and compositors that cap window size are happy. In per-monitor mode each
+// overlay runs its own grid; airborne drops leaving one are routed here to the overlay of
+// the monitor they fall into.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use tauri::{Emitter, Manager};
+
+use crate::geometry::MonitorMapping;
+use crate::settings::{load_settings, save_settings};
+use crate::types::{MonitorRegion, VirtualDesktop};
+use crate::window_mgmt::{create_background_window, create_overlay_window, fit_surface_window, SurfaceArea};
+use crate::{MONITOR_MAPPINGS, SURFACES_MENU_ITEM};
+
+static PER_MONITOR: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum WindowMode {
+    #[default]
+    Mega,
+    PerMonitor,
+}
+
+pub(crate) fn mode() -> WindowMode {
+    if PER_MONITOR.load(Ordering::Relaxed) { WindowMode::PerMonitor } else { WindowMode::Mega }
+}
+
+/// Apply without persisting or rebuilding (startup), keeping the tray check mark in sync.
+pub(crate) fn set_mode(mode: WindowMode) {
+    PER_MONITOR.store(mode == WindowMode::PerMonitor, Ordering::Relaxed);
+    if let Ok(guard) = SURFACES_MENU_ITEM.lock() {
+        if let Some(item) = guard.as_ref() {
+            let _ = item.set_checked(mode == WindowMode::PerMonitor);
+        }
+    }
+}
+
+/// Change from the panel or tray: apply, persist, and rebuild the windows off the main
+/// thread (closing a window has to finish there before its label can be reused).
+pub(crate) fn apply(app: &tauri::AppHandle, mode: WindowMode) {
+    set_mode(mode);
+    let mut settings = load_settings(app);
+    settings.window_mode = mode;
+    save_settings(app, &settings);
+    log::info!("[Surfaces] Window mode set to {:?}", mode);
+
+    let app = app.clone();
+    std::thread::spawn(move || match crate::commands::get_virtual_desktop(app.clone()) {
+        Ok(desktop) => sync(&app, &desktop),
+        Err(e) => log::error!("[Surfaces] Failed to get virtual desktop: {}", e),
+    });
+}
+
+/// Monitor index of a per-monitor surface ("overlay-2" -> 2)
+pub(crate) fn monitor_index(label: &str) -> Option<usize> {
+    label.strip_prefix("overlay-")
+        .or_else(|| label.strip_prefix("background-"))
+        .and_then(|s| s.parse().ok())
+}
+
+pub(crate) fn is_overlay(label: &str) -> bool {
+    label == "overlay" || (label.starts_with("overlay-") && monitor_index(label).is_some())
+}
+
+fn is_surface(label: &str) -> bool {
+    is_overlay(label) || label == "background" || (label.starts_with("background-") && monitor_index(label).is_some())
+}
+
+pub(crate) fn overlay_windows(app: &tauri::AppHandle) -> Vec<tauri::WebviewWindow> {
+    app.webview_windows().into_iter()
+        .filter(|(label, _)| is_overlay(label))
+        .map(|(_, window)| window)
+        .collect()
+}
+
+fn wanted_labels(mode: WindowMode, desktop: &VirtualDesktop) -> Vec<String> {
+    match mode {
+        WindowMode::Mega => vec!["background".to_string(), "overlay".to_string()],
+        WindowMode::PerMonitor => desktop.monitors.iter()
+            .flat_map(|m| [format!("background-{}", m.index), format!("overlay-{}", m.index)])
+            .collect(),
+    }
+}
+
+// Surface windows that don't belong to the wanted set (other windows are left alone)
+fn surplus(existing: impl IntoIterator<Item = String>, wanted: &[String]) -> Vec<String> {
+    existing.into_iter()
+        .filter(|label| is_surface(label) && !wanted.contains(label))
+        .collect()
+}
+
+fn area_for(label: &str, desktop: &VirtualDesktop) -> Option<SurfaceArea> {
+    match monitor_index(label) {
+        Some(index) => desktop.monitors.get(index).map(|region| SurfaceArea::monitor(desktop, region)),
+        None => Some(SurfaceArea::desktop(desktop)),
+    }
+}
+
+/// The part of the virtual desktop a window renders: its own monitor for per-monitor
+/// surfaces (rebased so the region sits at 0,0), None for mega windows and the panel.
+pub(crate) fn surface_desktop(label: &str, desktop: &VirtualDesktop) -> Option<VirtualDesktop> {
+    let region = desktop.monitors.get(monitor_index(label)?)?;
+    Some(VirtualDesktop {
+        origin_x: desktop.origin_x + region.x as i32,
+        origin_y: desktop.origin_y + region.y as i32,
+        width: region.width,
+        height: region.height,
+        monitors: vec![MonitorRegion {
+            x: 0,
+            y: 0,
+            work_x: region.work_x - region.x,
+            work_y: region.work_y - region.y,
+            ..region.clone()
+        }],
+        primary_index: 0,
+        primary_scale_factor: region.scale_factor,
+    })
+}
+
+/// Create a surface window (health tracking starts with it).
+pub(crate) fn create(app: &tauri::AppHandle, label: &str, desktop: &VirtualDesktop) -> Result<(), Box<dyn std::error::Error>> {
+    let area = area_for(label, desktop).ok_or_else(|| format!("No monitor for {}", label))?;
+    crate::init_health(label);
+    if is_overlay(label) {
+        create_overlay_window(app, label, &area)?;
+        if let Some(window) = app.get_webview_window(label) {
+            crate::interaction::apply_to_new_overlay(&window);
+        }
+        Ok(())
+    } else {
+        create_background_window(app, label, &area)
+    }
+}
+
+// Close a window and wait for the label to be released; closing completes on the main thread
+fn close_and_wait(app: &tauri::AppHandle, label: &str) {
+    let Some(window) = app.get_webview_window(label) else { return };
+    window.close().ok();
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while app.get_webview_window(label).is_some() && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(50));
+    }
+}
+
+/// Make the surfaces match the window mode and `desktop`: surplus windows close, existing
+/// ones move onto their area (rebuilt if they won't), missing ones are created. Blocks on
+/// window closes, so never call it from the main thread.
+/// Recovery may be mid-backoff for one of these windows; its later create then fails harmlessly.
+pub(crate) fn sync(app: &tauri::AppHandle, desktop: &VirtualDesktop) {
+    let wanted = wanted_labels(mode(), desktop);
+
+    for label in surplus(app.webview_windows().into_keys(), &wanted) {
+        log::info!("[Surfaces] Closing {}", label);
+        close_and_wait(app, &label);
+        crate::forget_health(&label);
+    }
+
+    for label in &wanted {
+        let Some(area) = area_for(label, desktop) else { continue };
+        if app.get_webview_window(label).is_some() {
+            match fit_surface_window(app, label, &area) {
+                Ok(()) => continue,
+                Err(e) => {
+                    log::warn!("[Surfaces] {}, recreating", e);
+                    close_and_wait(app, label);
+                }
+            }
+        }
+        if let Err(e) = create(app, label, desktop) {
+            log::error!("[Surfaces] Failed to create {}: {}", label, e);
+        }
+    }
+}
+
+/// An airborne drop leaving a per-monitor overlay (virtual-desktop logical coords, screen px/s)
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HandoffDrop {
+    pub x: f64,
+    pub y: f64,
+    pub vel_x: f64,
+    pub vel_y: f64,
+    pub radius: f64,
+}
+
+/// Forward drops to the overlay of the monitor each one is now over. Drops over void
+/// space (or back over the sender) are gone, as they would be in a mega window.
+pub(crate) fn hand_off(app: &tauri::AppHandle, from: &str, drops: Vec<HandoffDrop>) {
+    let mappings = MONITOR_MAPPINGS.lock().map(|m| m.clone()).unwrap_or_default();
+
+    for (index, drops) in route(from, drops, &mappings) {
+        if let Err(e) = app.emit_to(format!("overlay-{}", index).as_str(), "drops-handoff", drops) {
+            log::warn!("[Surfaces] Failed to hand drops to overlay-{}: {}", index, e);
+        }
+    }
+}
+
+// Drops grouped by the monitor index they now fall into
+fn route(from: &str, drops: Vec<HandoffDrop>, mappings: &[MonitorMapping]) -> BTreeMap<usize, Vec<HandoffDrop>> {
+    let source = monitor_index(from);
+    let mut routed: BTreeMap<usize, Vec<HandoffDrop>> = BTreeMap::new();
+    for drop in drops {
+        let (x, y) = (drop.x.floor() as i32, drop.y.floor() as i32);
+        let target = mappings.iter().find(|m| {
+            m.logical.left <= x && x < m.logical.right && m.logical.top <= y && y < m.logical.bottom
+        });
+        match target {
+            Some(m) if Some(m.index) != source => routed.entry(m.index).or_default().push(drop),
+            _ => {}
+        }
+    }
+    routed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Rect;
+    use crate::types::Bounds;
+
+    // 1080p primary, and a 1440p monitor at 200% to its right (1280x720 logical, so the
+    // space under it is void)
+    fn desktop() -> VirtualDesktop {
+        let region = |index: usize, x: u32, width: u32, height: u32, scale_factor: f64, physical: Bounds| MonitorRegion {
+            index,
+            x,
+            y: 0,
+            width,
+            height,
+            work_x: x,
+            work_y: 0,
+            work_width: width,
+            work_height: height - 40,
+            scale_factor,
+            refresh_rate: 60,
+            physical,
+            identity: Default::default(),
+            transform: Default::default(),
+        };
+        VirtualDesktop {
+            origin_x: 0,
+            origin_y: 0,
+            width: 3200,
+            height: 1080,
+            monitors: vec![
+                region(0, 0, 1920, 1080, 1.0, Bounds { x: 0, y: 0, width: 1920, height: 1080 }),
+                region(1, 1920, 1280, 720, 2.0, Bounds { x: 1920, y: 0, width: 2560, height: 1440 }),
+            ],
+            primary_index: 0,
+            primary_scale_factor: 1.0,
+        }
+    }
+
+    fn mappings() -> Vec<MonitorMapping> {
+        desktop().monitors.iter().map(|region| MonitorMapping::from_region(region, &desktop())).collect()
+    }
+
+    fn labels(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn drop_at(x: f64, y: f64) -> HandoffDrop {
+        HandoffDrop { x, y, vel_x: 0.0, vel_y: 400.0, radius: 2.0 }
+    }
+
+    fn routed_positions(routed: &BTreeMap<usize, Vec<HandoffDrop>>) -> Vec<(usize, Vec<(f64, f64)>)> {
+        routed.iter().map(|(&index, drops)| (index, drops.iter().map(|d| (d.x, d.y)).collect())).collect()
+    }
+
+    #[test]
+    fn switching_to_per_monitor_replaces_mega_windows() {
+        let wanted = wanted_labels(WindowMode::PerMonitor, &desktop());
+        assert_eq!(wanted, labels(&["background-0", "overlay-0", "background-1", "overlay-1"]));
+
+        // The panel and help window are left alone
+        let existing = labels(&["background", "overlay", "panel", "help"]);
+        assert_eq!(surplus(existing, &wanted), labels(&["background", "overlay"]));
+    }
+
+    #[test]
+    fn switching_back_to_mega_closes_per_monitor_windows() {
+        let wanted = wanted_labels(WindowMode::Mega, &desktop());
+        assert_eq!(wanted, labels(&["background", "overlay"]));
+
+        let existing = labels(&["background-0", "overlay-0", "background-1", "overlay-1", "panel"]);
+        assert_eq!(surplus(existing, &wanted), labels(&["background-0", "overlay-0", "background-1", "overlay-1"]));
+    }
+
+    #[test]
+    fn unplugged_monitor_closes_its_surfaces() {
+        let wanted = wanted_labels(WindowMode::PerMonitor, &desktop());
+        let existing = labels(&["overlay-0", "overlay-1", "overlay-2", "background-2", "overlay-extra"]);
+        assert_eq!(surplus(existing, &wanted), labels(&["overlay-2", "background-2"]));
+    }
+
+    #[test]
+    fn surface_areas_per_mode() {
+        let desktop = desktop();
+        let mega = area_for("overlay", &desktop).unwrap();
+        assert_eq!((mega.x, mega.y, mega.width, mega.height), (0.0, 0.0, 3200.0, 1080.0));
+        assert!(mega.physical.is_none());
+
+        let right = area_for("background-1", &desktop).unwrap();
+        assert_eq!((right.x, right.y, right.width, right.height), (1920.0, 0.0, 1280.0, 720.0));
+        assert_eq!(right.physical, Some(Bounds { x: 1920, y: 0, width: 2560, height: 1440 }));
+        assert!(area_for("overlay-5", &desktop).is_none());
+
+        // Rebased onto its own monitor
+        let surface = surface_desktop("overlay-1", &desktop).unwrap();
+        assert_eq!((surface.origin_x, surface.width, surface.height), (1920, 1280, 720));
+        assert_eq!((surface.monitors[0].x, surface.monitors[0].work_x), (0, 0));
+        assert_eq!(surface.primary_scale_factor, 2.0);
+        assert!(surface_desktop("overlay", &desktop).is_none());
+    }
+
+    #[test]
+    fn hand_off_routes_by_monitor_under_drop() {
+        let mappings = mappings();
+        assert_eq!(mappings[1].logical, Rect::from_xywh(1920, 0, 1280, 720));
+
+        let drops = vec![
+            drop_at(1950.5, 100.0), // onto the right monitor
+            drop_at(1919.9, 200.0), // still over the sender
+            drop_at(2500.0, 900.0), // void under the right monitor
+            drop_at(3000.0, 719.0),
+        ];
+        let routed = route("overlay-0", drops, &mappings);
+        assert_eq!(routed_positions(&routed), [(1, vec![(1950.5, 100.0), (3000.0, 719.0)])]);
+
+        let routed = route("overlay-1", vec![drop_at(1919.0, 10.0), drop_at(-5.0, 10.0)], &mappings);
+        assert_eq!(routed_positions(&routed), [(0, vec![(1919.0, 10.0)])]);
+    }
+
+    #[test]
+    fn hand_off_without_layout_drops_everything() {
+        assert!(route("overlay-0", vec![drop_at(2000.0, 10.0)], &[]).is_empty());
+    }
+}
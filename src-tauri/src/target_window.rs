@@ -0,0 +1,291 @@
+// Target-window mode: rain spawns only above one chosen window and follows it as it
+// moves and resizes. The choice is persisted by process + class name so it re-attaches
+// after a restart; when the attached window closes, the mode stops for the session.
+
+use std::sync::Mutex;
+use tauri::Emitter;
+
+use crate::settings::{load_settings, save_settings};
+use crate::window_detector::{Bounds, WindowData, WindowInfo};
+
+static TARGET: Mutex<TargetState> = Mutex::new(TargetState {
+    rule: None,
+    armed: false,
+    window: None,
+    candidates: Vec::new(),
+});
+
+/// Persisted identity of the chosen app (window ids don't survive restarts)
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TargetWindowRule {
+    pub process_name: String,
+    pub class_name: String,
+}
+
+impl TargetWindowRule {
+    fn matches(&self, win: &WindowInfo) -> bool {
+        win.process_name.eq_ignore_ascii_case(&self.process_name) && win.class_name == self.class_name
+    }
+}
+
+struct TargetState {
+    rule: Option<TargetWindowRule>,
+    /// Waiting to attach to the first window matching `rule`
+    armed: bool,
+    window: Option<WindowInfo>,
+    /// Latest detector output, for the picker
+    candidates: Vec<WindowInfo>,
+}
+
+impl TargetState {
+    fn status(&self) -> TargetWindowStatus {
+        TargetWindowStatus {
+            rule: self.rule.clone(),
+            window: self.window.clone(),
+            armed: self.armed,
+        }
+    }
+
+    /// Advance with one poll's windows; Some(status) when it should be emitted.
+    fn observe(&mut self, windows: &[WindowInfo]) -> Option<TargetWindowStatus> {
+        self.candidates = windows.to_vec();
+
+        let attached_id = self.window.as_ref().map(|w| w.id);
+        match attached_id {
+            Some(id) => match windows.iter().find(|w| w.id == id) {
+                Some(win) if self.window.as_ref().is_some_and(|w| w.bounds == win.bounds) => None,
+                Some(win) => {
+                    self.window = Some(win.clone());
+                    Some(self.status())
+                }
+                None => {
+                    // The rule stays persisted for the next launch, but another window of
+                    // the same app doesn't take over this session
+                    log::info!("[TargetWindow] Window closed, rain back to full desktop");
+                    self.window = None;
+                    self.armed = false;
+                    Some(self.status())
+                }
+            },
+            None if self.armed => {
+                let found = self.rule.as_ref()
+                    .and_then(|rule| windows.iter().find(|w| rule.matches(w)))
+                    .cloned();
+                found.map(|win| {
+                    log::info!("[TargetWindow] Attached to {} [{}]", win.process_name, win.class_name);
+                    self.window = Some(win);
+                    self.armed = false;
+                    self.status()
+                })
+            }
+            None => None,
+        }
+    }
+}
+
+/// `window` is null while no window is attached (rain covers the whole desktop).
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TargetWindowStatus {
+    pub rule: Option<TargetWindowRule>,
+    pub window: Option<WindowInfo>,
+    /// Waiting for a window matching `rule`; false once this session's window has closed
+    pub armed: bool,
+}
+
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TargetCandidate {
+    pub id: u64,
+    pub title: String,
+    pub process_name: String,
+    pub class_name: String,
+    pub bounds: Bounds,
+}
+
+fn emit_status(app: &tauri::AppHandle, status: &TargetWindowStatus) {
+    if let Err(e) = app.emit("target-window", status) {
+        log::error!("Failed to emit target-window: {}", e);
+    }
+}
+
+/// Startup: arm the persisted rule; attaches on the first poll that finds a match.
+pub(crate) fn init(rule: Option<TargetWindowRule>) {
+    let Ok(mut state) = TARGET.lock() else { return };
+    state.armed = rule.is_some();
+    state.rule = rule;
+}
+
+pub(crate) fn status() -> Option<TargetWindowStatus> {
+    TARGET.lock().ok().map(|s| s.status())
+}
+
+/// Windows the user can pick from (latest poll, topmost first).
+pub(crate) fn candidates() -> Vec<TargetCandidate> {
+    let Ok(state) = TARGET.lock() else { return Vec::new() };
+    state.candidates.iter()
+        .map(|w| TargetCandidate {
+            id: w.id,
+            title: w.title.clone(),
+            process_name: w.process_name.clone(),
+            class_name: w.class_name.clone(),
+            bounds: w.bounds.clone(),
+        })
+        .collect()
+}
+
+/// Called by the poll loop with projected windows; emits `target-window` when the
+/// attached window moves/resizes, closes, or a matching window first appears.
+pub(crate) fn track(app: &tauri::AppHandle, data: &WindowData) {
+    let status = {
+        let Ok(mut state) = TARGET.lock() else { return };
+        state.observe(&data.windows)
+    };
+
+    if let Some(status) = status {
+        emit_status(app, &status);
+    }
+}
+
+/// Attach to a window from the latest poll and persist its process/class.
+pub(crate) fn select(app: &tauri::AppHandle, id: u64) -> Result<TargetWindowStatus, String> {
+    let status = {
+        let mut state = TARGET.lock().map_err(|e| format!("Target lock poisoned: {}", e))?;
+        let win = state.candidates.iter()
+            .find(|w| w.id == id)
+            .cloned()
+            .ok_or_else(|| format!("Window {} not found", id))?;
+
+        let rule = TargetWindowRule {
+            process_name: win.process_name.clone(),
+            class_name: win.class_name.clone(),
+        };
+        log::info!("[TargetWindow] Selected {} [{}]", rule.process_name, rule.class_name);
+        state.rule = Some(rule);
+        state.window = Some(win);
+        state.armed = false;
+        state.status()
+    };
+
+    let mut settings = load_settings(app);
+    settings.target_window = status.rule.clone();
+    save_settings(app, &settings);
+
+    emit_status(app, &status);
+    Ok(status)
+}
+
+/// Back to whole-desktop rain and forget the persisted choice.
+pub(crate) fn clear(app: &tauri::AppHandle) {
+    let status = {
+        let Ok(mut state) = TARGET.lock() else { return };
+        state.rule = None;
+        state.window = None;
+        state.armed = false;
+        state.status()
+    };
+
+    let mut settings = load_settings(app);
+    settings.target_window = None;
+    save_settings(app, &settings);
+
+    log::info!("[TargetWindow] Cleared");
+    emit_status(app, &status);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(id: u64, process: &str, x: i32) -> WindowInfo {
+        WindowInfo {
+            id,
+            bounds: Bounds { x, y: 100, width: 800, height: 600 },
+            title: String::new(),
+            is_maximized: false,
+            z_order: 0,
+            process_name: process.to_string(),
+            class_name: "Main".to_string(),
+            pid: 0,
+            is_focused: false,
+            is_topmost: false,
+            is_fullscreen: false,
+            pieces: Vec::new(),
+            visible_rects: Vec::new(),
+            exposed_top: Vec::new(),
+        }
+    }
+
+    // Fresh start with a persisted rule, as init() leaves it
+    fn armed_for(process: &str) -> TargetState {
+        TargetState {
+            rule: Some(TargetWindowRule { process_name: process.to_string(), class_name: "Main".to_string() }),
+            armed: true,
+            window: None,
+            candidates: Vec::new(),
+        }
+    }
+
+    fn attached_id(status: Option<TargetWindowStatus>) -> Option<u64> {
+        status.expect("status should be emitted").window.map(|w| w.id)
+    }
+
+    #[test]
+    fn attaches_to_first_match() {
+        let mut state = armed_for("editor.exe");
+        assert!(state.observe(&[window(1, "browser.exe", 0)]).is_none());
+        assert_eq!(attached_id(state.observe(&[window(1, "browser.exe", 0), window(2, "EDITOR.EXE", 50)])), Some(2));
+        assert!(!state.armed);
+        assert_eq!(state.candidates.len(), 2);
+    }
+
+    #[test]
+    fn follows_moves_and_resizes_only() {
+        let mut state = armed_for("editor.exe");
+        state.observe(&[window(2, "editor.exe", 50)]);
+
+        // Unchanged bounds: nothing to emit
+        assert!(state.observe(&[window(2, "editor.exe", 50)]).is_none());
+
+        let status = state.observe(&[window(2, "editor.exe", 300)]).unwrap();
+        assert_eq!(status.window.unwrap().bounds.x, 300);
+
+        let mut resized = window(2, "editor.exe", 300);
+        resized.bounds.width = 1024;
+        let status = state.observe(&[resized]).unwrap();
+        assert_eq!(status.window.unwrap().bounds.width, 1024);
+    }
+
+    #[test]
+    fn close_stops_for_the_session() {
+        let mut state = armed_for("editor.exe");
+        state.observe(&[window(2, "editor.exe", 50)]);
+
+        let status = state.observe(&[]).unwrap();
+        assert!(status.window.is_none() && !status.armed);
+        // Rule kept for the next launch
+        assert_eq!(status.rule.unwrap().process_name, "editor.exe");
+
+        // Another window of the same app doesn't take over
+        assert!(state.observe(&[window(3, "editor.exe", 50)]).is_none());
+        assert!(state.window.is_none());
+    }
+
+    #[test]
+    fn rearms_after_restart_or_fresh_selection() {
+        let mut state = armed_for("editor.exe");
+        state.observe(&[window(2, "editor.exe", 50)]);
+        state.observe(&[]);
+
+        // Restart: init() arms the persisted rule again
+        let mut restarted = armed_for("editor.exe");
+        assert_eq!(attached_id(restarted.observe(&[window(3, "editor.exe", 50)])), Some(3));
+
+        // Fresh selection in this session: select() attaches directly
+        state.observe(&[window(4, "editor.exe", 50)]);
+        state.window = state.candidates.iter().find(|w| w.id == 4).cloned();
+        assert!(state.observe(&[window(4, "editor.exe", 50)]).is_none());
+        assert_eq!(attached_id(state.observe(&[window(4, "editor.exe", 90)])), Some(4));
+    }
+}
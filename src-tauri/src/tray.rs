@@ -3,14 +3,19 @@
 use std::sync::atomic::Ordering;
 use tauri::{
     Emitter,
-    menu::{Menu, MenuItem, Submenu},
+    menu::{CheckMenuItem, Menu, MenuItem, Submenu},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
 };
 
 use crate::commands::{hide_rainscaper, show_rainscaper};
 use crate::platform::load_theme_icon;
+use crate::docks;
+use crate::interaction;
+use crate::poll_scheduler;
+use crate::privacy::{self, TitlePrivacy};
+use crate::surfaces::{self, WindowMode};
 use crate::window_mgmt::reset_panel_position;
-use crate::{RAIN_PAUSED, PAUSE_MENU_ITEM, RAINSCAPER_MENU_ITEM, RAINSCAPER_VISIBLE, LAST_TRAY_POSITION};
+use crate::{RAIN_PAUSED, PAUSE_MENU_ITEM, DOCKS_MENU_ITEM, PRIVACY_MENU_ITEMS, SURFACES_MENU_ITEM, RAINSCAPER_MENU_ITEM, RAINSCAPER_VISIBLE, LAST_TRAY_POSITION};
 
 fn handle_menu_event(app: &tauri::AppHandle, id: &str, pause_item: &MenuItem<tauri::Wry>) {
     match id {
@@ -21,6 +26,7 @@ fn handle_menu_event(app: &tauri::AppHandle, id: &str, pause_item: &MenuItem<tau
         "pause" => {
             let paused = !RAIN_PAUSED.load(Ordering::Relaxed);
             RAIN_PAUSED.store(paused, Ordering::Relaxed);
+            poll_scheduler::wake();
             let _ = pause_item.set_text(if paused { "Resume" } else { "Pause" });
             let _ = app.emit("update-rainscape-param", serde_json::json!({
                 "path": "system.paused", "value": paused
@@ -42,7 +48,27 @@ fn handle_menu_event(app: &tauri::AppHandle, id: &str, pause_item: &MenuItem<tau
         "reset_position" => {
             reset_panel_position(app);
         }
+        "rain_on_docks" => {
+            docks::apply(app, !docks::rain_on_docks());
+        }
+        "per_monitor_windows" => {
+            let mode = match surfaces::mode() {
+                WindowMode::Mega => WindowMode::PerMonitor,
+                WindowMode::PerMonitor => WindowMode::Mega,
+            };
+            surfaces::apply(app, mode);
+        }
+        "interact" => {
+            if let Err(e) = interaction::start_timed(app, interaction::DEFAULT_TIMED_SECS) {
+                log::error!("[Tray] Failed to start interaction mode: {}", e);
+            }
+        }
         _ => {
+            if let Some(mode) = TitlePrivacy::from_menu_id(id) {
+                // Check items toggle themselves on click; apply re-syncs all three
+                privacy::apply(app, mode);
+                return;
+            }
             if let Some(vol_str) = id.strip_prefix("vol_") {
                 let volume = match vol_str {
                     "mute" => 0,
@@ -95,11 +121,51 @@ pub(crate) fn setup_tray(app: &tauri::App) -> Result<(), Box<dyn std::error::Err
         &MenuItem::with_id(app, "vol_100", "100%", true, None::<&str>)?,
     ])?;
 
+    let interact_item = MenuItem::with_id(
+        app, "interact", format!("Interact with Rain ({}s)", interaction::DEFAULT_TIMED_SECS), true, None::<&str>,
+    )?;
+
+    let docks_item = CheckMenuItem::with_id(app, "rain_on_docks", "Rain on Taskbar", true, docks::rain_on_docks(), None::<&str>)?;
+    if let Ok(mut guard) = DOCKS_MENU_ITEM.lock() {
+        *guard = Some(docks_item.clone());
+    }
+
+    let surfaces_item = CheckMenuItem::with_id(
+        app, "per_monitor_windows", "Per-Monitor Windows", true, surfaces::mode() == WindowMode::PerMonitor, None::<&str>,
+    )?;
+    if let Ok(mut guard) = SURFACES_MENU_ITEM.lock() {
+        *guard = Some(surfaces_item.clone());
+    }
+
+    let current_privacy = privacy::mode();
+    let privacy_items = [
+        (TitlePrivacy::Off, "Show Window Titles"),
+        (TitlePrivacy::Hash, "Hash Window Titles"),
+        (TitlePrivacy::Drop, "Hide Window Titles"),
+    ].into_iter()
+        .map(|(mode, label)| {
+            CheckMenuItem::with_id(app, mode.menu_id(), label, true, mode == current_privacy, None::<&str>)
+                .map(|item| (mode, item))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let privacy_submenu = Submenu::with_id_and_items(app, "privacy", "Privacy", true, &[
+        &privacy_items[0].1,
+        &privacy_items[1].1,
+        &privacy_items[2].1,
+    ])?;
+    if let Ok(mut guard) = PRIVACY_MENU_ITEMS.lock() {
+        *guard = privacy_items;
+    }
+
     let menu = Menu::with_items(app, &[
         &pause_item,
         &rainscaper_item,
         &reset_pos_item,
         &volume_submenu,
+        &interact_item,
+        &docks_item,
+        &surfaces_item,
+        &privacy_submenu,
         &quit_item
     ])?;
 
@@ -1,8 +1,16 @@
 // Shared data types for IPC serialization and app state.
 
+use std::collections::BTreeMap;
 use std::sync::Mutex;
 use std::time::Instant;
 
+use crate::interaction::InteractionModifier;
+use crate::monitor_identity::MonitorIdentity;
+use crate::privacy::TitlePrivacy;
+use crate::rain_zones::RainZone;
+use crate::surfaces::WindowMode;
+use crate::target_window::TargetWindowRule;
+
 // App state for configuration and cached hardware info
 pub(crate) struct AppState {
     pub config: Mutex<serde_json::Value>,
@@ -12,23 +20,74 @@ pub(crate) struct AppState {
 // Panel position and UI config persistence
 #[derive(serde::Serialize, serde::Deserialize, Default)]
 pub(crate) struct PanelConfig {
+    /// Last panel position on any layout (fallback when no layout memory applies)
     pub x: Option<i32>,
     pub y: Option<i32>,
     pub ui_scale: Option<f32>,
     pub detached: Option<bool>,
+    /// Panel/help positions per display layout fingerprint (obstacles::layout_fingerprint)
+    #[serde(default)]
+    pub layouts: BTreeMap<String, LayoutPositions>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Default, Clone)]
+pub(crate) struct LayoutPositions {
+    pub panel: Option<WindowAnchor>,
+    pub help: Option<WindowAnchor>,
+}
+
+/// A window's top-left as an offset from a monitor's work-area corner (window-positioning
+/// logical px), so it survives that monitor moving within the layout
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WindowAnchor {
+    pub monitor_id: String,
+    pub dx: i32,
+    pub dy: i32,
+    /// Unix seconds; the newest anchor wins when falling back across layouts
+    pub saved_at: i64,
+}
+
+// App-wide settings persistence (see settings.rs); missing fields fall back to defaults
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub(crate) struct AppSettings {
+    pub title_privacy: TitlePrivacy,
+    pub rain_on_docks: bool,
+    pub target_window: Option<TargetWindowRule>,
+    pub interaction_modifier: InteractionModifier,
+    pub window_mode: WindowMode,
+    pub rain_zones: Vec<RainZone>,
+    /// Next rain zone id; only grows so deleted ids aren't reused
+    pub rain_zone_next_id: u64,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            title_privacy: TitlePrivacy::Off,
+            rain_on_docks: false,
+            target_window: None,
+            interaction_modifier: InteractionModifier::default(),
+            window_mode: WindowMode::default(),
+            rain_zones: Vec::new(),
+            rain_zone_next_id: 0,
+        }
+    }
 }
 
 #[derive(Clone, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct DisplayInfo {
     pub index: usize,
+    pub identity: MonitorIdentity,
     pub bounds: Bounds,
     pub work_area: Bounds,
     pub scale_factor: f64,
     pub refresh_rate: u32,
 }
 
-#[derive(Clone, serde::Serialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct Bounds {
     pub x: i32,
@@ -47,7 +106,7 @@ pub(crate) struct SystemSpecs {
 }
 
 // Virtual desktop info: bounding box of all monitors + individual regions
-#[derive(Clone, serde::Serialize)]
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct VirtualDesktop {
     pub origin_x: i32,
@@ -68,7 +127,7 @@ pub(crate) struct WindowHealth {
 }
 
 // Single monitor region within the virtual desktop
-#[derive(Clone, serde::Serialize)]
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct MonitorRegion {
     pub index: usize,
@@ -82,4 +141,26 @@ pub(crate) struct MonitorRegion {
     pub work_height: u32,
     pub scale_factor: f64,
     pub refresh_rate: u32,
+    /// Monitor bounds in physical screen pixels (source of the logical rect above)
+    pub physical: Bounds,
+    /// Stable id, name, connector and rotation (the index above follows enumeration order)
+    #[serde(default)]
+    pub identity: MonitorIdentity,
+    /// This monitor's own physical <-> logical mapping
+    #[serde(default)]
+    pub transform: RegionTransform,
+}
+
+// Per-monitor mapping between physical screen pixels and virtual-desktop logical coords
+// (absolute, like VirtualDesktop::origin_x/origin_y):
+//   logical = logical_origin + (physical - physical_origin) / scale
+//   physical = physical_origin + (logical - logical_origin) * scale
+#[derive(Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RegionTransform {
+    pub scale: f64,
+    pub physical_x: i32,
+    pub physical_y: i32,
+    pub logical_x: i32,
+    pub logical_y: i32,
 }
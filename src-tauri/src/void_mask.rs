@@ -0,0 +1,293 @@
+// Void mask + spawn rows, rasterised once from the same VirtualDesktop regions that
+// get_virtual_desktop returns, so every webview works from identical cells instead of
+// re-deriving them in JS. Void = grid cell outside every monitor (gaps, staggered edges),
+// inside a rain-free zone, or outside the rain-only zones of its monitor (rain_zones.rs).
+
+use crate::rain_zones::{ProjectedZone, ZoneKind};
+use crate::types::VirtualDesktop;
+
+/// Sent to the renderer; `runs` is the row-major mask as alternating run lengths,
+/// starting with void (the first run may be 0).
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct VoidMaskData {
+    pub grid_scale: f64,
+    pub grid_width: u32,
+    pub grid_height: u32,
+    pub runs: Vec<u32>,
+    /// Per column: topmost non-void row, or -1 when the whole column is void
+    pub spawn_rows: Vec<i32>,
+    pub void_cells: u64,
+}
+
+/// Monitor rect in grid cells: origin floored, size ceiled (matches window rasterisation).
+fn monitor_cells(x: u32, y: u32, width: u32, height: u32, scale: f64) -> (u32, u32, u32, u32) {
+    (
+        (x as f64 * scale).floor() as u32,
+        (y as f64 * scale).floor() as u32,
+        (width as f64 * scale).ceil() as u32,
+        (height as f64 * scale).ceil() as u32,
+    )
+}
+
+/// Full mask, one byte per cell (1 = void), row-major. Zone tests use cell centres.
+pub(crate) fn build_mask(desktop: &VirtualDesktop, zones: &[ProjectedZone], scale: f64) -> (u32, u32, Vec<u8>) {
+    let grid_width = (desktop.width as f64 * scale).ceil() as u32;
+    let grid_height = (desktop.height as f64 * scale).ceil() as u32;
+    let mut cells = vec![1u8; grid_width as usize * grid_height as usize];
+
+    for m in &desktop.monitors {
+        let (mx, my, mw, mh) = monitor_cells(m.x, m.y, m.width, m.height, scale);
+        let x_end = (mx + mw).min(grid_width);
+        let y_end = (my + mh).min(grid_height);
+
+        let of_kind = |kind| zones.iter().filter(|z| z.monitor == m.index && z.kind == kind).collect::<Vec<_>>();
+        let rain_only = of_kind(ZoneKind::RainOnly);
+        let rain_free = of_kind(ZoneKind::RainFree);
+
+        for y in my.min(y_end)..y_end {
+            let row = y as usize * grid_width as usize;
+            if rain_only.is_empty() && rain_free.is_empty() {
+                cells[row + mx.min(x_end) as usize..row + x_end as usize].fill(0);
+                continue;
+            }
+            let ly = desktop.origin_y as f64 + (y as f64 + 0.5) / scale;
+            for x in mx.min(x_end)..x_end {
+                let lx = desktop.origin_x as f64 + (x as f64 + 0.5) / scale;
+                let open = (rain_only.is_empty() || rain_only.iter().any(|z| z.contains(lx, ly)))
+                    && !rain_free.iter().any(|z| z.contains(lx, ly));
+                if open {
+                    cells[row + x as usize] = 0;
+                }
+            }
+        }
+    }
+
+    (grid_width, grid_height, cells)
+}
+
+fn encode_runs(cells: &[u8]) -> Vec<u32> {
+    let mut runs = Vec::new();
+    let mut current = 1u8;
+    let mut length = 0u32;
+    for &cell in cells {
+        if cell == current {
+            length += 1;
+        } else {
+            runs.push(length);
+            current = cell;
+            length = 1;
+        }
+    }
+    runs.push(length);
+    runs
+}
+
+fn spawn_rows(cells: &[u8], grid_width: u32, grid_height: u32) -> Vec<i32> {
+    (0..grid_width as usize)
+        .map(|x| {
+            (0..grid_height as usize)
+                .find(|&y| cells[y * grid_width as usize + x] == 0)
+                .map_or(-1, |y| y as i32)
+        })
+        .collect()
+}
+
+// Column ranges that are void top to bottom (horizontal gaps between monitors)
+fn void_column_ranges(spawn_rows: &[i32]) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    let mut start = None;
+    for (x, &row) in spawn_rows.iter().enumerate() {
+        match (row < 0, start) {
+            (true, None) => start = Some(x),
+            (false, Some(s)) => {
+                ranges.push((s, x - 1));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        ranges.push((s, spawn_rows.len() - 1));
+    }
+    ranges
+}
+
+pub(crate) fn rasterize(desktop: &VirtualDesktop, zones: &[ProjectedZone], scale: f64) -> VoidMaskData {
+    let (grid_width, grid_height, cells) = build_mask(desktop, zones, scale);
+    let void_cells = cells.iter().map(|&c| c as u64).sum();
+    let spawn_rows = spawn_rows(&cells, grid_width, grid_height);
+
+    log::info!("[VoidMask] Grid {}x{} @ {}, void={}, usable={}, rain zones={}",
+        grid_width, grid_height, scale, void_cells, cells.len() as u64 - void_cells, zones.len());
+    let gaps = void_column_ranges(&spawn_rows);
+    if !gaps.is_empty() {
+        let list: Vec<String> = gaps.iter().map(|(a, b)| format!("{}-{}", a, b)).collect();
+        log::info!("[VoidMask] Void column ranges (gaps): {}", list.join(", "));
+    }
+
+    VoidMaskData {
+        grid_scale: scale,
+        grid_width,
+        grid_height,
+        runs: encode_runs(&cells),
+        spawn_rows,
+        void_cells,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::MonitorRegion;
+    use crate::window_detector::Bounds;
+
+    const SCALE: f64 = 0.25;
+
+    // Desktop from logical monitor rects (absolute); origin is their top-left
+    fn desktop(monitors: &[(i32, i32, u32, u32)]) -> VirtualDesktop {
+        let origin_x = monitors.iter().map(|m| m.0).min().unwrap();
+        let origin_y = monitors.iter().map(|m| m.1).min().unwrap();
+        let right = monitors.iter().map(|m| m.0 + m.2 as i32).max().unwrap();
+        let bottom = monitors.iter().map(|m| m.1 + m.3 as i32).max().unwrap();
+        let regions = monitors.iter().enumerate()
+            .map(|(index, &(x, y, width, height))| MonitorRegion {
+                index,
+                x: (x - origin_x) as u32,
+                y: (y - origin_y) as u32,
+                width,
+                height,
+                work_x: (x - origin_x) as u32,
+                work_y: (y - origin_y) as u32,
+                work_width: width,
+                work_height: height,
+                scale_factor: 1.0,
+                refresh_rate: 60,
+                physical: crate::types::Bounds { x, y, width, height },
+                identity: Default::default(),
+                transform: Default::default(),
+            })
+            .collect();
+        VirtualDesktop {
+            origin_x,
+            origin_y,
+            width: (right - origin_x) as u32,
+            height: (bottom - origin_y) as u32,
+            monitors: regions,
+            primary_index: 0,
+            primary_scale_factor: 1.0,
+        }
+    }
+
+    fn zone(kind: ZoneKind, monitor: usize, x: f64, y: f64, width: f64, height: f64) -> ProjectedZone {
+        ProjectedZone {
+            id: 1,
+            name: String::new(),
+            kind,
+            monitor,
+            points: vec![(x, y), (x + width, y), (x + width, y + height), (x, y + height)],
+            bounds: Bounds { x: x as i32, y: y as i32, width: width as u32, height: height as u32 },
+        }
+    }
+
+    // Same decoding as loadVoidMask in renderer.js
+    fn decode_runs(runs: &[u32]) -> Vec<u8> {
+        let mut cells = Vec::new();
+        let mut value = 1u8;
+        for &run in runs {
+            cells.resize(cells.len() + run as usize, value);
+            value ^= 1;
+        }
+        cells
+    }
+
+    fn void_rect(cells: &[u8], grid_width: u32, x: std::ops::Range<u32>, y: std::ops::Range<u32>) -> bool {
+        y.clone().all(|row| x.clone().all(|col| cells[(row * grid_width + col) as usize] == 1))
+    }
+
+    #[test]
+    fn stacked_monitors_leave_no_void() {
+        let data = rasterize(&desktop(&[(0, 0, 1920, 1080), (0, 1080, 1920, 1080)]), &[], SCALE);
+        assert_eq!((data.grid_width, data.grid_height), (480, 540));
+        assert_eq!(data.void_cells, 0);
+        assert_eq!(data.runs, [0, 480 * 540]);
+        assert!(data.spawn_rows.iter().all(|&row| row == 0));
+    }
+
+    #[test]
+    fn staggered_monitors_void_the_corners() {
+        let desktop = desktop(&[(0, 0, 1920, 1080), (1920, 200, 1920, 1080)]);
+        let (grid_width, grid_height, cells) = build_mask(&desktop, &[], SCALE);
+        assert_eq!((grid_width, grid_height), (960, 320));
+        assert!(void_rect(&cells, grid_width, 0..480, 270..320));
+        assert!(void_rect(&cells, grid_width, 480..960, 0..50));
+
+        let data = rasterize(&desktop, &[], SCALE);
+        assert_eq!(data.void_cells, 2 * 480 * 50);
+        assert!(data.spawn_rows[..480].iter().all(|&row| row == 0));
+        assert!(data.spawn_rows[480..].iter().all(|&row| row == 50));
+    }
+
+    #[test]
+    fn rotated_monitor_beside_landscape() {
+        // 1440p landscape next to a portrait 1080p
+        let desktop = desktop(&[(0, 0, 2560, 1440), (2560, 0, 1080, 1920)]);
+        let (grid_width, grid_height, cells) = build_mask(&desktop, &[], SCALE);
+        assert_eq!((grid_width, grid_height), (910, 480));
+        assert!(void_rect(&cells, grid_width, 0..640, 360..480));
+
+        let data = rasterize(&desktop, &[], SCALE);
+        assert_eq!(data.void_cells, 640 * 120);
+        assert!(data.spawn_rows.iter().all(|&row| row == 0));
+    }
+
+    #[test]
+    fn gap_between_monitors_is_void_top_to_bottom() {
+        let data = rasterize(&desktop(&[(0, 0, 1920, 1080), (2020, 0, 1920, 1080)]), &[], SCALE);
+        assert_eq!((data.grid_width, data.grid_height), (985, 270));
+        assert_eq!(data.void_cells, 25 * 270);
+        assert!(data.spawn_rows[480..505].iter().all(|&row| row == -1));
+        assert_eq!(data.spawn_rows[479], 0);
+        assert_eq!(data.spawn_rows[505], 0);
+        assert_eq!(void_column_ranges(&data.spawn_rows), [(480, 504)]);
+    }
+
+    #[test]
+    fn rain_free_zone_on_negative_origin_monitor() {
+        // Secondary left of the primary
+        let desktop = desktop(&[(-400, 0, 400, 400), (0, 0, 400, 400)]);
+        let zones = [zone(ZoneKind::RainFree, 0, -400.0, 0.0, 200.0, 100.0)];
+        let data = rasterize(&desktop, &zones, SCALE);
+        assert_eq!(data.void_cells, 50 * 25);
+        assert!(data.spawn_rows[..50].iter().all(|&row| row == 25));
+        assert!(data.spawn_rows[50..].iter().all(|&row| row == 0));
+    }
+
+    #[test]
+    fn rain_only_zone_limits_its_monitor() {
+        let desktop = desktop(&[(0, 0, 400, 400), (400, 0, 400, 400)]);
+        let zones = [zone(ZoneKind::RainOnly, 1, 600.0, 200.0, 200.0, 200.0)];
+        let data = rasterize(&desktop, &zones, SCALE);
+        // Monitor 1 only rains in its bottom-right quarter; monitor 0 is untouched
+        assert_eq!(data.void_cells, 100 * 100 - 50 * 50);
+        assert!(data.spawn_rows[..100].iter().all(|&row| row == 0));
+        assert!(data.spawn_rows[100..150].iter().all(|&row| row == -1));
+        assert!(data.spawn_rows[150..].iter().all(|&row| row == 50));
+    }
+
+    #[test]
+    fn runs_round_trip() {
+        let desktop = desktop(&[(0, 0, 1920, 1080), (1920, 200, 1920, 1080), (4000, -300, 1080, 1920)]);
+        let zones = [zone(ZoneKind::RainFree, 1, 2000.0, 400.0, 300.0, 300.0)];
+        let (_, _, cells) = build_mask(&desktop, &zones, SCALE);
+        let data = rasterize(&desktop, &zones, SCALE);
+        assert_eq!(decode_runs(&data.runs), cells);
+        assert_eq!(data.void_cells, cells.iter().filter(|&&c| c == 1).count() as u64);
+
+        // Starting open gives a leading empty void run
+        assert_eq!(encode_runs(&[0, 0, 1, 0]), [0, 2, 1, 1]);
+        assert_eq!(decode_runs(&encode_runs(&[0, 0, 1, 0])), [0, 0, 1, 0]);
+        assert_eq!(encode_runs(&[1, 1, 1]), [3]);
+        assert_eq!(encode_runs(&[]), [0]);
+    }
+}
@@ -1,21 +1,43 @@
-// Window collision detection — Win32 API bounds, clipped to monitor coords.
+// Window collision detection — Win32 API bounds in physical screen pixels.
+// geometry::project_windows then resolves occlusion and converts everything to
+// virtual-desktop logical coords (per-monitor scale, split at monitor edges).
 // Skips: invisible, minimized, cloaked (UWP phantoms), other virtual desktops,
 //        tiny (<50px), untitled, RainyDesk/DevTools, system class names, system overlays
+// Taskbars are collected separately as native dock rects (see docks.rs).
 // UWP/WinUI3 apps are NOT skipped — cloaked check handles suspended instances.
+// Titles pass through privacy::redact after filtering, before they're logged or sent.
+// get_windows_for_diagnostics also returns the skipped windows that have an on-screen
+// rect, tagged with the rule that dropped them (diagnostics.rs draws them).
 
+#[cfg(target_os = "windows")]
+use std::cell::RefCell;
+#[cfg(target_os = "windows")]
+use std::collections::HashMap;
 #[cfg(target_os = "windows")]
 use std::sync::atomic::{AtomicU32, Ordering};
 
+use crate::docks::DockSurface;
+#[cfg(target_os = "windows")]
+use crate::privacy;
+
 #[cfg(target_os = "windows")]
 use windows::{
     core::{BOOL, GUID},
-    Win32::Foundation::{HWND, LPARAM, RECT},
+    core::PWSTR,
+    Win32::Foundation::{CloseHandle, HWND, LPARAM, RECT},
     Win32::Graphics::Dwm::{DwmGetWindowAttribute, DWMWA_CLOAKED, DWMWA_EXTENDED_FRAME_BOUNDS},
     Win32::System::Com::{CoCreateInstance, CoInitializeEx, CLSCTX_ALL, COINIT_MULTITHREADED},
-    Win32::UI::Shell::IVirtualDesktopManager,
+    Win32::System::Threading::{
+        OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32, PROCESS_QUERY_LIMITED_INFORMATION,
+    },
+    Win32::UI::Shell::{
+        IVirtualDesktopManager, SHQueryUserNotificationState, QUNS_BUSY, QUNS_PRESENTATION_MODE,
+        QUNS_RUNNING_D3D_FULL_SCREEN,
+    },
     Win32::UI::WindowsAndMessaging::{
-        EnumWindows, GetClassNameW, GetWindowPlacement, GetWindowRect, GetWindowTextW,
-        IsIconic, IsWindowVisible, IsZoomed, SW_SHOWMINIMIZED, WINDOWPLACEMENT,
+        EnumWindows, GetClassNameW, GetForegroundWindow, GetWindowLongW, GetWindowPlacement,
+        GetWindowRect, GetWindowTextW, GetWindowThreadProcessId, IsIconic, IsWindowVisible, IsZoomed,
+        GWL_EXSTYLE, SW_SHOWMINIMIZED, WINDOWPLACEMENT, WS_EX_TOPMOST,
     },
 };
 
@@ -32,15 +54,41 @@ const CLSID_VIRTUAL_DESKTOP_MANAGER: GUID = GUID {
 #[cfg(target_os = "windows")]
 static POLL_COUNT: AtomicU32 = AtomicU32::new(0);
 
-#[derive(Debug, Clone, serde::Serialize)]
+// Process name lookups are cached per PID; pruned to live PIDs after every poll
+#[cfg(target_os = "windows")]
+thread_local! {
+    static PROCESS_NAMES: RefCell<HashMap<u32, String>> = RefCell::new(HashMap::new());
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct WindowInfo {
+    /// Stable for the window's lifetime within this session (native handle value)
+    pub id: u64,
+    /// Physical pixels from the detector; logical virtual-desktop coords once projected
     pub bounds: Bounds,
     pub title: String,
-    #[serde(rename = "isMaximized")]
     pub is_maximized: bool,
+    /// Stacking rank among reported windows: 0 = topmost
+    pub z_order: usize,
+    pub process_name: String,
+    /// Native window class (Windows: GetClassNameW); with process_name, identifies an app across restarts
+    #[serde(default)]
+    pub class_name: String,
+    pub pid: u32,
+    pub is_focused: bool,
+    pub is_topmost: bool,
+    /// Native fullscreen flag (Windows: shell reports a fullscreen app in the foreground)
+    pub is_fullscreen: bool,
+    /// The window cut at monitor edges, one piece per monitor it overlaps
+    pub pieces: Vec<WindowPiece>,
+    /// Parts of the window not covered by anything above it, clipped to monitors
+    pub visible_rects: Vec<Bounds>,
+    /// Uncovered stretches of the top edge (where rain can actually land)
+    pub exposed_top: Vec<EdgeSpan>,
 }
 
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct Bounds {
     pub x: i32,
     pub y: i32,
@@ -48,9 +96,67 @@ pub struct Bounds {
     pub height: u32,
 }
 
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct WindowPiece {
+    pub monitor: usize,
+    pub bounds: Bounds,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct EdgeSpan {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+}
+
+/// Detector rule that left a window out (invisible and minimized windows aren't recorded)
+// Only the Windows detector filters so far
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub enum SkipRule {
+    Cloaked,
+    OtherVirtualDesktop,
+    Tiny,
+    SystemClass,
+    PhantomAtOrigin,
+    Untitled,
+    OwnWindow,
+    DevTools,
+    SystemOverlay,
+}
+
+impl SkipRule {
+    pub fn label(self) -> &'static str {
+        match self {
+            SkipRule::Cloaked => "cloaked",
+            SkipRule::OtherVirtualDesktop => "other desktop",
+            SkipRule::Tiny => "tiny",
+            SkipRule::SystemClass => "system class",
+            SkipRule::PhantomAtOrigin => "phantom at origin",
+            SkipRule::Untitled => "untitled",
+            SkipRule::OwnWindow => "rainydesk",
+            SkipRule::DevTools => "devtools",
+            SkipRule::SystemOverlay => "system overlay",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SkippedWindow {
+    /// Bounds, id, class and (redacted) title only
+    pub window: WindowInfo,
+    pub rule: SkipRule,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct WindowData {
     pub windows: Vec<WindowInfo>,
+    /// Taskbars/docks/panels as collision surfaces (empty when rain on docks is off)
+    #[serde(default)]
+    pub docks: Vec<DockSurface>,
+    /// Native dock window rects from the detector (physical), input to docks::project_docks
+    #[serde(skip)]
+    pub native_docks: Vec<Bounds>,
 }
 
 /// Context passed through LPARAM to the EnumWindows callback.
@@ -59,10 +165,27 @@ pub struct WindowData {
 struct EnumContext {
     windows: Vec<WindowInfo>,
     vdm: Option<IVirtualDesktopManager>,
+    foreground: HWND,
+    foreground_fullscreen: bool,
+    process_names: HashMap<u32, String>,
+    native_docks: Vec<Bounds>,
+    /// Filtered-out windows, only collected for diagnostics
+    skipped: Option<Vec<SkippedWindow>>,
 }
 
 #[cfg(target_os = "windows")]
 pub fn get_visible_windows() -> Result<WindowData, Box<dyn std::error::Error>> {
+    enumerate(false).map(|(data, _)| data)
+}
+
+/// One detection pass that also returns what the filters dropped (slower; diagnostics only).
+#[cfg(target_os = "windows")]
+pub fn get_windows_for_diagnostics() -> Result<(WindowData, Vec<SkippedWindow>), Box<dyn std::error::Error>> {
+    enumerate(true)
+}
+
+#[cfg(target_os = "windows")]
+fn enumerate(collect_skipped: bool) -> Result<(WindowData, Vec<SkippedWindow>), Box<dyn std::error::Error>> {
     // Init COM once per thread (redundant calls are tolerated but leak refcounts)
     thread_local! {
         static COM_INIT: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
@@ -79,9 +202,20 @@ pub fn get_visible_windows() -> Result<WindowData, Box<dyn std::error::Error>> {
         CoCreateInstance(&CLSID_VIRTUAL_DESKTOP_MANAGER, None, CLSCTX_ALL).ok()
     };
 
+    // Shell's own "a fullscreen app is in front" signal; applies to the foreground window only
+    let foreground_fullscreen = matches!(
+        unsafe { SHQueryUserNotificationState() },
+        Ok(state) if state == QUNS_BUSY || state == QUNS_RUNNING_D3D_FULL_SCREEN || state == QUNS_PRESENTATION_MODE
+    );
+
     let mut ctx = EnumContext {
         windows: Vec::new(),
         vdm,
+        foreground: unsafe { GetForegroundWindow() },
+        foreground_fullscreen,
+        process_names: PROCESS_NAMES.with(|names| std::mem::take(&mut *names.borrow_mut())),
+        native_docks: Vec::new(),
+        skipped: collect_skipped.then(Vec::new),
     };
 
     unsafe {
@@ -91,13 +225,119 @@ pub fn get_visible_windows() -> Result<WindowData, Box<dyn std::error::Error>> {
         )?;
     }
 
+    // Hand the cache back, dropping PIDs that no longer own a reported window
+    let mut names = std::mem::take(&mut ctx.process_names);
+    names.retain(|pid, _| ctx.windows.iter().any(|w| w.pid == *pid));
+    PROCESS_NAMES.with(|cache| *cache.borrow_mut() = names);
+
     // DEBUG: Log window count periodically (every 600 calls = ~30 seconds at 50ms)
     let poll_num = POLL_COUNT.fetch_add(1, Ordering::Relaxed);
     if poll_num % 600 == 0 {
         log::info!("[WindowDetector] Poll #{}: found {} windows (raw)", poll_num + 1, ctx.windows.len());
     }
 
-    Ok(WindowData { windows: ctx.windows })
+    let data = WindowData {
+        windows: ctx.windows,
+        docks: Vec::new(),
+        native_docks: ctx.native_docks,
+    };
+    Ok((data, ctx.skipped.unwrap_or_default()))
+}
+
+// Executable file name for a PID (e.g. "Discord.exe"); empty if the process is protected
+#[cfg(target_os = "windows")]
+unsafe fn query_process_name(pid: u32) -> String {
+    let Ok(process) = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) else {
+        return String::new();
+    };
+
+    let mut buf = [0u16; 1024];
+    let mut len = buf.len() as u32;
+    let result = QueryFullProcessImageNameW(process, PROCESS_NAME_WIN32, PWSTR(buf.as_mut_ptr()), &mut len);
+    let _ = CloseHandle(process);
+
+    if result.is_err() {
+        return String::new();
+    }
+
+    let path = String::from_utf16_lossy(&buf[..len as usize]);
+    path.rsplit(['\\', '/']).next().unwrap_or_default().to_string()
+}
+
+// Visible frame via DWM extended frame bounds (GetWindowRect includes the invisible
+// resize border, so it's only the fallback); None for empty or inverted rects
+#[cfg(target_os = "windows")]
+unsafe fn window_rect(hwnd: HWND) -> Option<RECT> {
+    let mut rect = RECT::default();
+    let dwm_result = DwmGetWindowAttribute(
+        hwnd,
+        DWMWA_EXTENDED_FRAME_BOUNDS,
+        &mut rect as *mut _ as *mut std::ffi::c_void,
+        std::mem::size_of::<RECT>() as u32,
+    );
+    if dwm_result.is_err() && GetWindowRect(hwnd, &mut rect).is_err() {
+        return None;
+    }
+    // Guard against malformed windows with negative dimensions
+    (rect.right > rect.left && rect.bottom > rect.top).then_some(rect)
+}
+
+// Window class name (locale-independent, structural identity)
+#[cfg(target_os = "windows")]
+unsafe fn window_class(hwnd: HWND) -> String {
+    let mut class_buf = [0u16; 256];
+    let class_len = GetClassNameW(hwnd, &mut class_buf);
+    if class_len > 0 {
+        String::from_utf16_lossy(&class_buf[..class_len as usize])
+    } else {
+        String::new()
+    }
+}
+
+#[cfg(target_os = "windows")]
+unsafe fn window_title(hwnd: HWND) -> String {
+    let mut title_buf = [0u16; 512];
+    let title_len = GetWindowTextW(hwnd, &mut title_buf);
+    if title_len > 0 {
+        String::from_utf16_lossy(&title_buf[..title_len as usize])
+    } else {
+        String::new()
+    }
+}
+
+// Drop a window from the results; in a diagnostics pass, remember it and which rule fired
+#[cfg(target_os = "windows")]
+unsafe fn skip(ctx: &mut EnumContext, hwnd: HWND, rule: SkipRule) -> BOOL {
+    let Some(skipped) = ctx.skipped.as_mut() else {
+        return BOOL(1);
+    };
+    if let Some(rect) = window_rect(hwnd) {
+        skipped.push(SkippedWindow {
+            window: WindowInfo {
+                id: hwnd.0 as usize as u64,
+                bounds: Bounds {
+                    x: rect.left,
+                    y: rect.top,
+                    width: (rect.right - rect.left) as u32,
+                    height: (rect.bottom - rect.top) as u32,
+                },
+                title: privacy::redact(&window_title(hwnd)),
+                is_maximized: false,
+                z_order: skipped.len(),
+                process_name: String::new(),
+                class_name: window_class(hwnd),
+                pid: 0,
+                is_focused: false,
+                is_topmost: false,
+                is_fullscreen: false,
+                pieces: Vec::new(),
+                visible_rects: Vec::new(),
+                exposed_top: Vec::new(),
+            },
+            rule,
+        });
+    }
+    BOOL(1)
 }
 
 #[cfg(target_os = "windows")]
@@ -134,13 +374,13 @@ unsafe extern "system" fn enum_window_callback(hwnd: HWND, lparam: LPARAM) -> BO
         &mut cloaked as *mut _ as *mut std::ffi::c_void,
         std::mem::size_of::<u32>() as u32,
     ).is_ok() && cloaked != 0 {
-        return BOOL(1);
+        return skip(ctx, hwnd, SkipRule::Cloaked);
     }
 
     // Skip windows on other virtual desktops
     if let Some(ref vdm) = ctx.vdm {
         match vdm.IsWindowOnCurrentVirtualDesktop(hwnd) {
-            Ok(is_current) if !is_current.as_bool() => return BOOL(1),
+            Ok(is_current) if !is_current.as_bool() => return skip(ctx, hwnd, SkipRule::OtherVirtualDesktop),
             Err(_) => {} // COM error — don't filter (safer to show than hide)
             _ => {}
         }
@@ -149,95 +389,66 @@ unsafe extern "system" fn enum_window_callback(hwnd: HWND, lparam: LPARAM) -> BO
     // Check if window is maximized
     let is_maximized = IsZoomed(hwnd).as_bool();
 
-    // Get window bounds using DWM extended frame bounds (accurate visible area)
-    // Falls back to GetWindowRect if DWM fails
-    let mut rect = RECT::default();
-    let dwm_result = DwmGetWindowAttribute(
-        hwnd,
-        DWMWA_EXTENDED_FRAME_BOUNDS,
-        &mut rect as *mut _ as *mut std::ffi::c_void,
-        std::mem::size_of::<RECT>() as u32,
-    );
-
-    if dwm_result.is_err() {
-        // Fallback to GetWindowRect (includes invisible frame)
-        if GetWindowRect(hwnd, &mut rect).is_err() {
-            return BOOL(1);
-        }
-    }
+    // Window bounds (accurate visible area)
+    let Some(rect) = window_rect(hwnd) else {
+        return BOOL(1);
+    };
+    let width = (rect.right - rect.left) as u32;
+    let height = (rect.bottom - rect.top) as u32;
 
-    let width_raw = rect.right - rect.left;
-    let height_raw = rect.bottom - rect.top;
+    let class_name = window_class(hwnd);
 
-    // Guard against malformed windows with negative dimensions
-    if width_raw <= 0 || height_raw <= 0 {
+    // Taskbars aren't windows to rain on like the rest, but they are dock surfaces.
+    // Checked before the size filter: a 100%-scale taskbar is only 48px tall.
+    if class_name == "Shell_TrayWnd" || class_name == "Shell_SecondaryTrayWnd" {
+        ctx.native_docks.push(Bounds { x: rect.left, y: rect.top, width, height });
         return BOOL(1);
     }
 
-    let width = width_raw as u32;
-    let height = height_raw as u32;
-
     // Filter out tiny windows (likely system UI elements)
     if width < 50 || height < 50 {
-        return BOOL(1);
+        return skip(ctx, hwnd, SkipRule::Tiny);
     }
 
-    // Get window class name (locale-independent, structural identity)
-    let mut class_buf = [0u16; 256];
-    let class_len = GetClassNameW(hwnd, &mut class_buf);
-    let class_name = if class_len > 0 {
-        String::from_utf16_lossy(&class_buf[..class_len as usize])
-    } else {
-        String::new()
-    };
-
     // Skip system windows by class name (locale-independent)
     // UWP/WinUI3 NOT skipped — cloaked check catches suspended instances instead.
     // CoreWindow skipped to avoid double-counting inside ApplicationFrameWindow.
     if class_name == "CEF-OSC-WIDGET" ||    // NVIDIA GeForce Overlay (transparent, not a real window)
        class_name == "Progman" ||           // Desktop (Program Manager)
        class_name == "WorkerW" ||           // Desktop worker windows
-       class_name == "Shell_TrayWnd" ||     // Taskbar
-       class_name == "Shell_SecondaryTrayWnd" ||  // Secondary taskbar (multi-monitor)
        class_name == "NotifyIconOverflowWindow" ||  // System tray overflow
        class_name == "Windows.UI.Core.CoreWindow" ||  // UWP content (covered by ApplicationFrameWindow)
        class_name == "XamlExplorerHostIslandWindow" ||  // XAML hosting islands inside other windows
        class_name == "ForegroundStaging" ||  // Compositor staging
        class_name == "MultitaskingViewFrame" ||  // Task View (Win+Tab)
        class_name == "XamlWindow" {          // Various XAML overlays
-        return BOOL(1);
+        return skip(ctx, hwnd, SkipRule::SystemClass);
     }
 
     // Get window title for additional filtering and logging
-    let mut title_buf = [0u16; 512];
-    let title_len = GetWindowTextW(hwnd, &mut title_buf);
-    let title = if title_len > 0 {
-        String::from_utf16_lossy(&title_buf[..title_len as usize])
-    } else {
-        String::new()
-    };
+    let title = window_title(hwnd);
 
     // Skip phantom windows at origin with portrait dimensions (often minimized apps)
     let is_near_origin = rect.left.abs() < 50 && rect.top.abs() < 50;
     let is_portrait_size = height > width && (width >= 1000 || height >= 1800);
     if is_near_origin && is_portrait_size {
-        return BOOL(1);
+        return skip(ctx, hwnd, SkipRule::PhantomAtOrigin);
     }
 
     // Skip windows without titles (system windows)
     if title.is_empty() {
-        return BOOL(1);
+        return skip(ctx, hwnd, SkipRule::Untitled);
     }
 
     // Skip our own overlay windows (starts_with avoids false positives on
     // terminals whose title includes a "RainyDesk" directory path)
     if title.starts_with("RainyDesk") {
-        return BOOL(1);
+        return skip(ctx, hwnd, SkipRule::OwnWindow);
     }
 
     // Skip DevTools windows (Tauri dev mode)
     if title.contains("DevTools") {
-        return BOOL(1);
+        return skip(ctx, hwnd, SkipRule::DevTools);
     }
 
     // Skip known system overlays by title (fallback for edge cases)
@@ -246,9 +457,12 @@ unsafe extern "system" fn enum_window_callback(hwnd: HWND, lparam: LPARAM) -> BO
        title == "Microsoft Text Input Application" ||
        title == "Task Switching" ||   // Alt-Tab overlay
        title == "Task View" {         // Win+Tab overlay
-        return BOOL(1);
+        return skip(ctx, hwnd, SkipRule::SystemOverlay);
     }
 
+    // Filters above need the real title; everything past this point gets the redacted one
+    let title = privacy::redact(&title);
+
     // DEBUG: Log windows periodically (every ~30 sec only, not at startup)
     let poll_num = POLL_COUNT.load(Ordering::Relaxed);
     if poll_num % 600 == 0 {
@@ -256,7 +470,21 @@ unsafe extern "system" fn enum_window_callback(hwnd: HWND, lparam: LPARAM) -> BO
             title, class_name, rect.left, rect.top, width, height);
     }
 
+    let mut pid: u32 = 0;
+    GetWindowThreadProcessId(hwnd, Some(&mut pid));
+    let process_name = ctx.process_names
+        .entry(pid)
+        .or_insert_with(|| query_process_name(pid))
+        .clone();
+
+    let ex_style = GetWindowLongW(hwnd, GWL_EXSTYLE) as u32;
+    let is_topmost = ex_style & WS_EX_TOPMOST.0 != 0;
+
+    // EnumWindows walks top-level windows top-to-bottom, so push order is z-order
+    let z_order = ctx.windows.len();
+
     ctx.windows.push(WindowInfo {
+        id: hwnd.0 as usize as u64,
         bounds: Bounds {
             x: rect.left,
             y: rect.top,
@@ -265,15 +493,33 @@ unsafe extern "system" fn enum_window_callback(hwnd: HWND, lparam: LPARAM) -> BO
         },
         title,
         is_maximized,
+        z_order,
+        process_name,
+        class_name,
+        pid,
+        is_focused: hwnd == ctx.foreground,
+        is_topmost,
+        is_fullscreen: hwnd == ctx.foreground && ctx.foreground_fullscreen,
+        pieces: Vec::new(),
+        visible_rects: Vec::new(),
+        exposed_top: Vec::new(),
     });
 
     BOOL(1) // Continue enumeration
 }
 
+#[cfg(not(target_os = "windows"))]
+pub fn get_windows_for_diagnostics() -> Result<(WindowData, Vec<SkippedWindow>), Box<dyn std::error::Error>> {
+    Ok((get_visible_windows()?, Vec::new()))
+}
+
 #[cfg(not(target_os = "windows"))]
 pub fn get_visible_windows() -> Result<WindowData, Box<dyn std::error::Error>> {
     // TODO: Linux/macOS implementation
+    // No native dock windows here; docks come from the work-area difference alone
     Ok(WindowData {
         windows: Vec::new(),
+        docks: Vec::new(),
+        native_docks: Vec::new(),
     })
 }
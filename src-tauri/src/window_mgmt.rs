@@ -2,8 +2,10 @@
 
 use std::path::PathBuf;
 use std::sync::atomic::Ordering;
+use chrono::Local;
 use tauri::{Emitter, Manager, WebviewUrl, WebviewWindowBuilder};
 
+use crate::layout::{self, Displays, Remembered};
 use crate::platform::*;
 use crate::types::*;
 use crate::{RAINSCAPER_MENU_ITEM, RAINSCAPER_VISIBLE};
@@ -30,6 +32,107 @@ pub(crate) fn save_panel_config(app: &tauri::AppHandle, config: &PanelConfig) {
     }
 }
 
+// Panel UI scale (persisted as PanelConfig::ui_scale)
+
+/// Saved UI scale; None until one has been set (the panel then auto-fits on first launch)
+pub(crate) fn saved_ui_scale(app: &tauri::AppHandle) -> Option<f64> {
+    load_panel_config(app)
+        .and_then(|c| c.ui_scale)
+        .map(|s| (s as f64).clamp(layout::UI_SCALE_RANGE.0, layout::UI_SCALE_RANGE.1))
+}
+
+/// Panel size (window logical) at the saved UI scale
+pub(crate) fn scaled_panel_size(app: &tauri::AppHandle) -> (i32, i32) {
+    layout::panel_size(saved_ui_scale(app).unwrap_or(1.0))
+}
+
+/// Resize the panel window (window logical) and pull it back inside its work area.
+pub(crate) fn resize_panel(app: &tauri::AppHandle, window: &tauri::WebviewWindow, width: f64, height: f64) -> Result<(), String> {
+    window.set_resizable(true).ok();
+    window.set_size(tauri::LogicalSize::new(width, height))
+        .map_err(|e| format!("Failed to resize: {}", e))?;
+    window.set_resizable(false).ok();
+
+    let Some((x, y, _, _)) = logical_frame(window) else { return Ok(()) };
+    let (cx, cy) = clamp_panel_to_work_area(app, x, y, width.round() as i32, height.round() as i32);
+    if (cx, cy) != (x, y) {
+        window.set_position(tauri::Position::Logical(tauri::LogicalPosition::new(cx as f64, cy as f64))).ok();
+        log::info!("[Rainscaper] Repositioned to ({}, {}) to stay in work area", cx, cy);
+    }
+    Ok(())
+}
+
+/// Change the UI scale: persist it and resize the panel to match. Returns the scale applied.
+pub(crate) fn apply_ui_scale(app: &tauri::AppHandle, scale: f64) -> Result<f64, String> {
+    if !scale.is_finite() {
+        return Err(format!("Invalid UI scale {}", scale));
+    }
+    let scale = scale.clamp(layout::UI_SCALE_RANGE.0, layout::UI_SCALE_RANGE.1);
+
+    let mut config = load_panel_config(app).unwrap_or_default();
+    config.ui_scale = Some(scale as f32);
+    save_panel_config(app, &config);
+
+    let (panel_w, panel_h) = layout::panel_size(scale);
+    if let Some(window) = app.get_webview_window("rainscaper") {
+        resize_panel(app, &window, panel_w as f64, panel_h as f64)?;
+    }
+    log::info!("[Rainscaper] UI scale {:.0}%: panel {}x{}", scale * 100.0, panel_w, panel_h);
+    Ok(scale)
+}
+
+// Position memory: panel/help positions per display layout, anchored to a monitor id
+
+fn layout_key(displays: &Displays) -> Option<String> {
+    layout::virtual_desktop(displays).map(|desktop| crate::obstacles::layout_fingerprint(&desktop))
+}
+
+/// Remember where a window sits (window logical) on the current layout. The panel's
+/// position is also kept as the layout-independent fallback.
+pub(crate) fn remember_position(app: &tauri::AppHandle, window: Remembered, x: i32, y: i32, width: i32, height: i32) {
+    let displays = describe_monitors(app);
+    let mut config = load_panel_config(app).unwrap_or_default();
+    if window == Remembered::Panel {
+        config.x = Some(x);
+        config.y = Some(y);
+    }
+    let anchor = layout::anchor_window(&displays, x, y, width, height, Local::now().timestamp());
+    if let (Some(key), Some(anchor)) = (layout_key(&displays), anchor) {
+        log::info!("[Layout] Remembered {:?} at +({}, {}) on monitor {}", window, anchor.dx, anchor.dy, anchor.monitor_id);
+        window.set(config.layouts.entry(key).or_default(), anchor);
+    }
+    save_panel_config(app, &config);
+}
+
+/// Remembered position of a window for the current layout (see layout::recall).
+pub(crate) fn recall_position(app: &tauri::AppHandle, window: Remembered, width: i32, height: i32) -> Option<(i32, i32)> {
+    let displays = describe_monitors(app);
+    let config = load_panel_config(app)?;
+    layout::recall(&displays, &config.layouts, &layout_key(&displays)?, window, width, height)
+}
+
+/// Saved panel position: remembered for this layout, else the last position anywhere
+/// clamped to a current work area.
+pub(crate) fn saved_panel_position(app: &tauri::AppHandle, panel_w: i32, panel_h: i32) -> Option<(i32, i32)> {
+    recall_position(app, Remembered::Panel, panel_w, panel_h).or_else(|| {
+        let config = load_panel_config(app)?;
+        config.x.zip(config.y).map(|(x, y)| clamp_panel_to_work_area(app, x, y, panel_w, panel_h))
+    })
+}
+
+/// A window's outer rect in window logical px: (x, y, width, height)
+pub(crate) fn logical_frame(window: &tauri::WebviewWindow) -> Option<(i32, i32, i32, i32)> {
+    let (Ok(pos), Ok(size), Ok(scale)) = (window.outer_position(), window.outer_size(), window.scale_factor()) else {
+        return None;
+    };
+    Some((
+        (pos.x as f64 / scale) as i32,
+        (pos.y as f64 / scale) as i32,
+        (size.width as f64 / scale) as i32,
+        (size.height as f64 / scale) as i32,
+    ))
+}
+
 // Shared helper: update the tray menu item text for Rainscaper Open/Close
 pub(crate) fn update_rainscaper_menu_text(text: &str) {
     if let Ok(guard) = RAINSCAPER_MENU_ITEM.lock() {
@@ -45,135 +148,48 @@ pub(crate) fn update_rainscaper_menu_text(text: &str) {
 /// Input tray_x/tray_y are physical (from tray click events).
 /// panel_w/panel_h are actual logical dimensions (accounts for UI scale).
 pub(crate) fn calculate_rainscaper_position(app: &tauri::AppHandle, tray_x: i32, tray_y: i32, panel_w: i32, panel_h: i32) -> (i32, i32) {
-    const MARGIN: i32 = 8;
-
-    let monitors: Vec<tauri::Monitor> = app
-        .available_monitors()
-        .unwrap_or_default()
-        .into_iter()
-        .collect();
-
-    let mut scale = 1.0_f64;
-    let mut work_x = 0i32;
-    let mut work_y = 0i32;
-    let mut work_w = 1920i32;
-    let mut work_h = 1080i32;
-    let mut taskbar_at_top = false;
-
-    for monitor in &monitors {
-        let pos = monitor.position();
-        let size = monitor.size();
-
-        if tray_x >= pos.x && tray_x < pos.x + size.width as i32 &&
-           tray_y >= pos.y && tray_y < pos.y + size.height as i32 {
-            scale = monitor.scale_factor();
-            let work = get_monitor_work_area(pos.x, pos.y, size.width, size.height);
-            work_x = (work.x as f64 / scale) as i32;
-            work_y = (work.y as f64 / scale) as i32;
-            work_w = (work.width as f64 / scale) as i32;
-            work_h = (work.height as f64 / scale) as i32;
-            taskbar_at_top = tray_y < pos.y + 100;
-            break;
-        }
-    }
-
-    let tray_lx = (tray_x as f64 / scale) as i32;
-    let tray_ly = (tray_y as f64 / scale) as i32;
-
-    let mut x = tray_lx - (panel_w / 2);
-    let mut y = if taskbar_at_top {
-        tray_ly + (40.0 / scale) as i32 + MARGIN
-    } else {
-        // Align panel bottom edge with work area bottom
-        work_y + work_h - panel_h - MARGIN
-    };
-
-    let x_min = work_x + MARGIN;
-    let y_min = work_y + MARGIN;
-    x = x.max(x_min).min((work_x + work_w - panel_w - MARGIN).max(x_min));
-    y = y.max(y_min).min((work_y + work_h - panel_h - MARGIN).max(y_min));
-
-    (x, y)
+    layout::panel_near_tray(&describe_monitors(app), tray_x, tray_y, panel_w, panel_h)
 }
 
 /// Clamp a saved panel position to the current work area so it doesn't overlap the taskbar.
+/// A position on no current monitor (e.g. one that was unplugged) lands on the primary.
 pub(crate) fn clamp_panel_to_work_area(app: &tauri::AppHandle, x: i32, y: i32, panel_w: i32, panel_h: i32) -> (i32, i32) {
-    const MARGIN: i32 = 8;
-
-    let monitors: Vec<tauri::Monitor> = app
-        .available_monitors()
-        .unwrap_or_default()
-        .into_iter()
-        .collect();
-
-    for monitor in &monitors {
-        let pos = monitor.position();
-        let size = monitor.size();
-        let scale = monitor.scale_factor();
-        let mon_lx = (pos.x as f64 / scale) as i32;
-        let mon_ly = (pos.y as f64 / scale) as i32;
-        let mon_lw = (size.width as f64 / scale) as i32;
-        let mon_lh = (size.height as f64 / scale) as i32;
-
-        if x >= mon_lx && x < mon_lx + mon_lw &&
-           y >= mon_ly && y < mon_ly + mon_lh {
-            let work = get_monitor_work_area(pos.x, pos.y, size.width, size.height);
-            let work_x = (work.x as f64 / scale) as i32;
-            let work_y = (work.y as f64 / scale) as i32;
-            let work_w = (work.width as f64 / scale) as i32;
-            let work_h = (work.height as f64 / scale) as i32;
-
-            let x_min = work_x + MARGIN;
-            let y_min = work_y + MARGIN;
-            let x_max = (work_x + work_w - panel_w - MARGIN).max(x_min);
-            let y_max = (work_y + work_h - panel_h - MARGIN).max(y_min);
-            let cx = x.max(x_min).min(x_max);
-            let cy = y.max(y_min).min(y_max);
-            return (cx, cy);
-        }
-    }
+    layout::clamp_panel(&describe_monitors(app), x, y, panel_w, panel_h)
+}
 
-    (x, y)
+/// Display layout changed: a detached panel moves to where it was last left on this
+/// layout; otherwise it's pulled back into a work area if its monitor went away or shrank.
+/// Saved positions are left alone so they come back with the old layout.
+pub(crate) fn reclamp_panel(app: &tauri::AppHandle) {
+    let Some(window) = app.get_webview_window("rainscaper") else { return };
+    let Some((x, y, w, h)) = logical_frame(&window) else { return };
+
+    let detached = load_panel_config(app).and_then(|c| c.detached).unwrap_or(false);
+    let remembered = if detached { recall_position(app, Remembered::Panel, w, h) } else { None };
+    let (cx, cy) = remembered.unwrap_or_else(|| clamp_panel_to_work_area(app, x, y, w, h));
+    if (cx, cy) != (x, y) {
+        window.set_position(tauri::Position::Logical(
+            tauri::LogicalPosition::new(cx as f64, cy as f64)
+        )).ok();
+        log::info!("[Rainscaper] Panel {} after display change: ({}, {}) -> ({}, {})",
+            if remembered.is_some() { "restored" } else { "re-clamped" }, x, y, cx, cy);
+    }
 }
 
-/// Reset panel position to bottom-right of the taskbar monitor's work area.
+/// Reset panel position to bottom-right of the taskbar monitor's work area, at the
+/// panel's size for the saved UI scale.
 pub(crate) fn reset_panel_position(app: &tauri::AppHandle) {
-    const PANEL_WIDTH: i32 = 400;
-    const PANEL_HEIGHT: i32 = 500;
-    const MARGIN: i32 = 12;
-
-    let monitors: Vec<tauri::Monitor> = app
-        .available_monitors()
-        .unwrap_or_default()
-        .into_iter()
-        .collect();
-
-    if monitors.is_empty() { return; }
-
-    let idx = get_primary_monitor_index(&monitors);
-    let mon = &monitors[idx];
-    let pos = mon.position();
-    let size = mon.size();
-    let scale = mon.scale_factor();
-    let work = get_monitor_work_area(pos.x, pos.y, size.width, size.height);
-
-    let work_x = (work.x as f64 / scale) as i32;
-    let work_y = (work.y as f64 / scale) as i32;
-    let work_w = (work.width as f64 / scale) as i32;
-    let work_h = (work.height as f64 / scale) as i32;
-
-    let x = work_x + work_w - PANEL_WIDTH - MARGIN;
-    let y = work_y + work_h - PANEL_HEIGHT - MARGIN;
+    let (panel_w, panel_h) = scaled_panel_size(app);
+    let Some((x, y)) = layout::panel_home(&describe_monitors(app), panel_w, panel_h) else { return };
 
     let mut config = load_panel_config(app).unwrap_or_default();
-    config.x = Some(x);
-    config.y = Some(y);
     config.detached = Some(false);
     save_panel_config(app, &config);
+    remember_position(app, Remembered::Panel, x, y, panel_w, panel_h);
 
     if let Some(window) = app.get_webview_window("rainscaper") {
         window.set_resizable(true).ok();
-        window.set_size(tauri::LogicalSize::new(PANEL_WIDTH as f64, PANEL_HEIGHT as f64)).ok();
+        window.set_size(tauri::LogicalSize::new(panel_w as f64, panel_h as f64)).ok();
         window.set_resizable(false).ok();
         window.set_position(tauri::Position::Logical(
             tauri::LogicalPosition::new(x as f64, y as f64)
@@ -195,13 +211,15 @@ pub(crate) fn reset_panel_position(app: &tauri::AppHandle) {
         "value": true
     }));
 
-    log::info!("[Rainscaper] Panel reset: position ({}, {}), UI scale 100%", x, y);
+    log::info!("[Rainscaper] Panel reset: position ({}, {}), UI scale {:.0}%",
+        x, y, saved_ui_scale(app).unwrap_or(1.0) * 100.0);
 }
 
 // Window creation
 
 pub(crate) fn create_rainscaper_window_at(app: &tauri::AppHandle, x: i32, y: i32, visible: bool) -> Result<(), String> {
     log::info!("[Rainscaper] Creating window at ({}, {}), visible={}", x, y, visible);
+    let (panel_w, panel_h) = scaled_panel_size(app);
 
     let window = WebviewWindowBuilder::new(
         app,
@@ -210,7 +228,7 @@ pub(crate) fn create_rainscaper_window_at(app: &tauri::AppHandle, x: i32, y: i32
     )
         .title("RainyDesk Rainscaper")
         .position(x as f64, y as f64)
-        .inner_size(400.0, 500.0)
+        .inner_size(panel_w as f64, panel_h as f64)
         .transparent(true)
         .decorations(false)
         .always_on_top(true)
@@ -241,45 +259,14 @@ pub(crate) fn create_rainscaper_window_at(app: &tauri::AppHandle, x: i32, y: i32
     Ok(())
 }
 
-fn calculate_help_window_geometry(app: &tauri::AppHandle) -> (f64, f64, f64, f64) {
-    let monitors: Vec<tauri::Monitor> = app
-        .available_monitors()
-        .unwrap_or_default()
-        .into_iter()
-        .collect();
-
-    if monitors.is_empty() {
-        return (1120.0, 630.0, 100.0, 100.0);
-    }
-
-    let idx = get_primary_monitor_index(&monitors);
-    let mon = &monitors[idx];
-    let mon_pos = mon.position();
-    let mon_size = mon.size();
-    let work = get_monitor_work_area(
-        mon_pos.x, mon_pos.y, mon_size.width, mon_size.height,
-    );
-    let scale = mon.scale_factor();
-    let work_w = work.width as f64 / scale;
-    let work_h = work.height as f64 / scale;
-    let work_x = work.x as f64 / scale;
-    let work_y = work.y as f64 / scale;
-
-    // Try 75% of work width at 16:9; if too tall, constrain by height
-    let mut w = (work_w * 0.75).round();
-    let mut h = (w * 9.0 / 16.0).round();
-    if h > work_h * 0.85 {
-        h = (work_h * 0.85).round();
-        w = (h * 16.0 / 9.0).round();
-    }
-
-    (w, h, work_x + (work_w - w) / 2.0, work_y + (work_h - h) / 2.0)
-}
-
 pub(crate) fn create_help_window(app: &tauri::AppHandle, visible: bool) -> Result<(), String> {
     log::info!("[Help] Creating window, visible={}", visible);
 
-    let (help_w, help_h, pos_x, pos_y) = calculate_help_window_geometry(app);
+    let mut area = layout::help_window_area(&describe_monitors(app));
+    if let Some((x, y)) = recall_position(app, Remembered::Help, area.width as i32, area.height as i32) {
+        area.x = x as f64;
+        area.y = y as f64;
+    }
 
     let window = WebviewWindowBuilder::new(
         app,
@@ -287,8 +274,8 @@ pub(crate) fn create_help_window(app: &tauri::AppHandle, visible: bool) -> Resul
         WebviewUrl::App("help.html".into())
     )
         .title("RainyDesk Help")
-        .inner_size(help_w, help_h)
-        .position(pos_x, pos_y)
+        .inner_size(area.width, area.height)
+        .position(area.x, area.y)
         .transparent(true)
         .decorations(false)
         .always_on_top(true)
@@ -310,23 +297,66 @@ pub(crate) fn create_help_window(app: &tauri::AppHandle, visible: bool) -> Resul
     Ok(())
 }
 
+// Overlay/background surfaces. Either one "overlay"/"background" pair spanning the whole
+// virtual desktop, or (per-monitor mode, see surfaces.rs) "overlay-N"/"background-N" sized
+// to monitor N.
+
+/// Where a surface window goes: a logical rect for the builder, plus exact physical bounds
+/// for per-monitor windows (the builder converts logical with a single scale, which misses
+/// on mixed-DPI setups).
+pub(crate) struct SurfaceArea {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    pub physical: Option<Bounds>,
+}
+
+impl SurfaceArea {
+    pub(crate) fn desktop(desktop: &VirtualDesktop) -> Self {
+        Self {
+            x: desktop.origin_x as f64,
+            y: desktop.origin_y as f64,
+            width: desktop.width as f64,
+            height: desktop.height as f64,
+            physical: None,
+        }
+    }
+
+    pub(crate) fn monitor(desktop: &VirtualDesktop, region: &MonitorRegion) -> Self {
+        Self {
+            x: (desktop.origin_x + region.x as i32) as f64,
+            y: (desktop.origin_y + region.y as i32) as f64,
+            width: region.width as f64,
+            height: region.height as f64,
+            physical: Some(region.physical.clone()),
+        }
+    }
+}
+
+// Builder position/size are logical; per-monitor windows are then snapped to the exact
+// physical monitor bounds
+fn apply_physical_bounds(window: &tauri::WebviewWindow, area: &SurfaceArea) -> tauri::Result<()> {
+    let Some(p) = &area.physical else { return Ok(()) };
+    window.set_position(tauri::Position::Physical(tauri::PhysicalPosition::new(p.x, p.y)))?;
+    window.set_size(tauri::Size::Physical(tauri::PhysicalSize::new(p.width, p.height)))
+}
+
 #[cfg(target_os = "windows")]
-pub(crate) fn create_mega_background(
+pub(crate) fn create_background_window(
     app: &tauri::AppHandle,
-    desktop: &VirtualDesktop,
+    label: &str,
+    area: &SurfaceArea,
 ) -> Result<(), Box<dyn std::error::Error>> {
     use windows::Win32::Foundation::HWND;
     use windows::Win32::UI::WindowsAndMessaging::{SetWindowPos, HWND_BOTTOM, SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE};
 
-    log::info!(
-        "Creating mega-background: {}x{} at ({}, {})",
-        desktop.width, desktop.height, desktop.origin_x, desktop.origin_y
-    );
+    log::info!("Creating {}: {}x{} at ({}, {})", label, area.width, area.height, area.x, area.y);
 
-    let window = WebviewWindowBuilder::new(app, "background", WebviewUrl::App("background.html".into()))
+    let window = WebviewWindowBuilder::new(app, label, WebviewUrl::App("background.html".into()))
         .title("RainyDesk Background")
-        .position(desktop.origin_x as f64, desktop.origin_y as f64)
-        .inner_size(desktop.width as f64, desktop.height as f64)
+        .position(area.x, area.y)
+        .inner_size(area.width, area.height)
         .transparent(true)
         .decorations(false)
         .always_on_top(false)
@@ -336,6 +366,7 @@ pub(crate) fn create_mega_background(
         .shadow(false)
         .build()?;
 
+    apply_physical_bounds(&window, area)?;
     window.set_ignore_cursor_events(true)?;
 
     let hwnd = window.hwnd()?;
@@ -348,31 +379,122 @@ pub(crate) fn create_mega_background(
         );
     }
 
-    log::info!("Mega-background created successfully");
+    log::info!("{} created successfully", label);
+    Ok(())
+}
+
+// Linux: built hidden so the desktop type hint is set before the window is first mapped
+// (WMs only read it at map time), then kept below normal windows and out of pagers.
+#[cfg(target_os = "linux")]
+pub(crate) fn create_background_window(
+    app: &tauri::AppHandle,
+    label: &str,
+    area: &SurfaceArea,
+) -> Result<(), Box<dyn std::error::Error>> {
+    log::info!("Creating {}: {}x{} at ({}, {})", label, area.width, area.height, area.x, area.y);
+
+    let window = WebviewWindowBuilder::new(app, label, WebviewUrl::App("background.html".into()))
+        .title("RainyDesk Background")
+        .position(area.x, area.y)
+        .inner_size(area.width, area.height)
+        .transparent(true)
+        .decorations(false)
+        .always_on_top(false)
+        .skip_taskbar(true)
+        .resizable(false)
+        .focused(false)
+        .shadow(false)
+        .visible(false)
+        .build()?;
+
+    apply_physical_bounds(&window, area)?;
+
+    let gtk_handle = window.clone();
+    let log_label = label.to_string();
+    window.run_on_main_thread(move || {
+        use gtk::prelude::*;
+
+        let Ok(gtk_window) = gtk_handle.gtk_window() else {
+            log::error!("[Linux] No GTK window for {}", log_label);
+            return;
+        };
+        gtk_window.set_type_hint(gtk::gdk::WindowTypeHint::Desktop);
+        apply_passive_hints(&gtk_window);
+        gtk_window.set_keep_below(true);
+        gtk_window.show();
+        set_empty_input_shape(&gtk_window);
+        log::info!("[Linux] {} mapped as desktop-type, keep-below", log_label);
+    })?;
+
+    log::info!("{} created successfully", label);
     Ok(())
 }
 
-#[cfg(not(target_os = "windows"))]
-pub(crate) fn create_mega_background(
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+pub(crate) fn create_background_window(
     _app: &tauri::AppHandle,
-    _desktop: &VirtualDesktop,
+    _label: &str,
+    _area: &SurfaceArea,
 ) -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-pub(crate) fn create_mega_overlay(
+/// Move and resize an existing surface window onto `area`.
+/// Errors when the window is missing or refuses the new geometry; the caller recreates it.
+pub(crate) fn fit_surface_window(app: &tauri::AppHandle, label: &str, area: &SurfaceArea) -> Result<(), String> {
+    let window = app.get_webview_window(label).ok_or_else(|| format!("{} window not found", label))?;
+
+    // Non-resizable windows ignore set_size on some platforms
+    window.set_resizable(true).ok();
+    let result = match area.physical {
+        Some(_) => apply_physical_bounds(&window, area),
+        None => window
+            .set_position(tauri::Position::Logical(tauri::LogicalPosition::new(area.x, area.y)))
+            .and_then(|_| window.set_size(tauri::LogicalSize::new(area.width, area.height))),
+    };
+    window.set_resizable(false).ok();
+    result.map_err(|e| format!("Failed to fit {} window: {}", label, e))?;
+
+    log::info!("Fitted {} to {}x{} at ({}, {})", label, area.width, area.height, area.x, area.y);
+    Ok(())
+}
+
+// Never focused, never listed in taskbars/pagers (overlay and background windows).
+// Interaction sessions make the overlay focusable for their duration (interaction.rs).
+#[cfg(target_os = "linux")]
+fn apply_passive_hints(gtk_window: &gtk::ApplicationWindow) {
+    use gtk::prelude::*;
+
+    gtk_window.set_skip_taskbar_hint(true);
+    gtk_window.set_skip_pager_hint(true);
+    gtk_window.set_accept_focus(false);
+    gtk_window.set_focus_on_map(false);
+}
+
+// Click-through via an empty X input shape (the Linux counterpart of WS_EX_TRANSPARENT).
+// For the background, which is built hidden: set_ignore_cursor_events needs a realized
+// GdkWindow and never gets toggled there, so the shape is applied directly after show()
+#[cfg(target_os = "linux")]
+fn set_empty_input_shape(gtk_window: &gtk::ApplicationWindow) {
+    use gtk::prelude::*;
+
+    match gtk_window.window() {
+        Some(gdk_window) => gdk_window.input_shape_combine_region(&gtk::cairo::Region::create(), 0, 0),
+        None => log::warn!("[Linux] Window not realized, input shape not set"),
+    }
+}
+
+pub(crate) fn create_overlay_window(
     app: &tauri::AppHandle,
-    desktop: &VirtualDesktop,
+    label: &str,
+    area: &SurfaceArea,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    log::info!(
-        "Creating mega-overlay: {}x{} at ({}, {})",
-        desktop.width, desktop.height, desktop.origin_x, desktop.origin_y
-    );
+    log::info!("Creating {}: {}x{} at ({}, {})", label, area.width, area.height, area.x, area.y);
 
-    let window = WebviewWindowBuilder::new(app, "overlay", WebviewUrl::App("index.html".into()))
+    let window = WebviewWindowBuilder::new(app, label, WebviewUrl::App("index.html".into()))
         .title("RainyDesk")
-        .position(desktop.origin_x as f64, desktop.origin_y as f64)
-        .inner_size(desktop.width as f64, desktop.height as f64)
+        .position(area.x, area.y)
+        .inner_size(area.width, area.height)
         .transparent(true)
         .decorations(false)
         .always_on_top(true)
@@ -382,8 +504,17 @@ pub(crate) fn create_mega_overlay(
         .shadow(false)
         .build()?;
 
+    apply_physical_bounds(&window, area)?;
     window.set_ignore_cursor_events(true)?;
 
+    // Interaction mode must never outlive overlay focus
+    let blur_app = app.clone();
+    window.on_window_event(move |event| {
+        if let tauri::WindowEvent::Focused(false) = event {
+            crate::interaction::on_overlay_blur(&blur_app);
+        }
+    });
+
     #[cfg(target_os = "windows")]
     {
         use windows::Win32::Foundation::HWND;
@@ -395,7 +526,23 @@ pub(crate) fn create_mega_overlay(
             let style = GetWindowLongW(HWND(hwnd.0), GWL_EXSTYLE);
             SetWindowLongW(HWND(hwnd.0), GWL_EXSTYLE, style | WS_EX_NOACTIVATE.0 as i32);
         }
-        log::info!("Added WS_EX_NOACTIVATE to overlay window");
+        log::info!("Added WS_EX_NOACTIVATE to {}", label);
+    }
+
+    // Linux: no WS_EX_NOACTIVATE, so ask the WM not to focus or list it instead
+    #[cfg(target_os = "linux")]
+    {
+        let gtk_handle = window.clone();
+        let log_label = label.to_string();
+        window.run_on_main_thread(move || {
+            let Ok(gtk_window) = gtk_handle.gtk_window() else {
+                log::error!("[Linux] No GTK window for {}", log_label);
+                return;
+            };
+            // Click-through already came from set_ignore_cursor_events above
+            apply_passive_hints(&gtk_window);
+            log::info!("[Linux] {} hints set: no focus, skip taskbar/pager", log_label);
+        })?;
     }
 
     #[cfg(debug_assertions)]
@@ -403,6 +550,6 @@ pub(crate) fn create_mega_overlay(
         window.open_devtools();
     }
 
-    log::info!("Mega-overlay created successfully");
+    log::info!("{} created successfully", label);
     Ok(())
 }
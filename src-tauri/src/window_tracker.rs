@@ -0,0 +1,319 @@
+// Window snapshot diffing: lifecycle events + periodic keyframes.
+// Compares each poll against the previous one so the webviews only hear about
+// real changes instead of re-receiving the full window list 60 times a second.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tauri::Emitter;
+
+use crate::docks::DockSurface;
+use crate::window_detector::{Bounds, WindowData, WindowInfo};
+
+// Full window-data is re-sent at least this often so late listeners catch up
+const KEYFRAME_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WindowGeometryChange {
+    pub window: WindowInfo,
+    pub previous: Bounds,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WindowStateChange {
+    pub window: WindowInfo,
+    pub was_maximized: bool,
+    pub was_focused: bool,
+    pub was_topmost: bool,
+    pub was_fullscreen: bool,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(untagged)]
+pub(crate) enum WindowEvent {
+    Opened(WindowInfo),
+    Closed(WindowInfo),
+    Moved(WindowGeometryChange),
+    Resized(WindowGeometryChange),
+    StateChanged(WindowStateChange),
+}
+
+impl WindowEvent {
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            WindowEvent::Opened(_) => "window-opened",
+            WindowEvent::Closed(_) => "window-closed",
+            WindowEvent::Moved(_) => "window-moved",
+            WindowEvent::Resized(_) => "window-resized",
+            WindowEvent::StateChanged(_) => "window-state-changed",
+        }
+    }
+}
+
+/// Result of diffing one poll against the last: events to emit, whether anything
+/// changed at all, and whether the full snapshot should go out (change or keyframe).
+pub(crate) struct TrackerUpdate {
+    pub events: Vec<WindowEvent>,
+    pub changed: bool,
+    pub emit_snapshot: bool,
+}
+
+pub(crate) struct WindowTracker {
+    known: HashMap<u64, WindowInfo>,
+    order: Vec<u64>,
+    docks: Vec<DockSurface>,
+    last_snapshot: Option<Instant>,
+}
+
+impl WindowTracker {
+    pub(crate) fn new() -> Self {
+        Self {
+            known: HashMap::new(),
+            order: Vec::new(),
+            docks: Vec::new(),
+            last_snapshot: None,
+        }
+    }
+
+    pub(crate) fn update(&mut self, data: &WindowData, now: Instant) -> TrackerUpdate {
+        let mut events = Vec::new();
+        let mut changed = false;
+        let mut current: HashMap<u64, WindowInfo> = HashMap::with_capacity(data.windows.len());
+
+        for win in &data.windows {
+            match self.known.get(&win.id) {
+                None => events.push(WindowEvent::Opened(win.clone())),
+                Some(prev) => {
+                    let b = &win.bounds;
+                    let p = &prev.bounds;
+                    if b.x != p.x || b.y != p.y {
+                        events.push(WindowEvent::Moved(WindowGeometryChange {
+                            window: win.clone(),
+                            previous: p.clone(),
+                        }));
+                    }
+                    if b.width != p.width || b.height != p.height {
+                        events.push(WindowEvent::Resized(WindowGeometryChange {
+                            window: win.clone(),
+                            previous: p.clone(),
+                        }));
+                    }
+                    if win.is_maximized != prev.is_maximized
+                        || win.is_focused != prev.is_focused
+                        || win.is_topmost != prev.is_topmost
+                        || win.is_fullscreen != prev.is_fullscreen
+                    {
+                        events.push(WindowEvent::StateChanged(WindowStateChange {
+                            window: win.clone(),
+                            was_maximized: prev.is_maximized,
+                            was_focused: prev.is_focused,
+                            was_topmost: prev.is_topmost,
+                            was_fullscreen: prev.is_fullscreen,
+                        }));
+                    }
+                    // Title edits don't get their own event but still refresh the snapshot
+                    if win.title != prev.title {
+                        changed = true;
+                    }
+                }
+            }
+            current.insert(win.id, win.clone());
+        }
+
+        // Sorted so event logs and recordings come out the same every run
+        let mut closed: Vec<&WindowInfo> = self.known.values()
+            .filter(|prev| !current.contains_key(&prev.id))
+            .collect();
+        closed.sort_by_key(|prev| (prev.z_order, prev.id));
+        events.extend(closed.into_iter().map(|prev| WindowEvent::Closed(prev.clone())));
+
+        // Z-order shuffles (focus changes) alter overlap even when no bounds move
+        let order: Vec<u64> = data.windows.iter().map(|w| w.id).collect();
+        if order != self.order {
+            changed = true;
+        }
+
+        // Docks have no lifecycle events; taskbar moves/toggles just refresh the snapshot
+        if data.docks != self.docks {
+            changed = true;
+            self.docks = data.docks.clone();
+        }
+
+        self.known = current;
+        self.order = order;
+
+        let keyframe_due = self.last_snapshot
+            .map(|t| now.duration_since(t) >= KEYFRAME_INTERVAL)
+            .unwrap_or(true);
+        let changed = changed || !events.is_empty();
+        let emit_snapshot = changed || keyframe_due;
+        if emit_snapshot {
+            self.last_snapshot = Some(now);
+        }
+
+        TrackerUpdate { events, changed, emit_snapshot }
+    }
+}
+
+/// Emit lifecycle events first, then the snapshot they belong to.
+pub(crate) fn emit_update(app: &tauri::AppHandle, update: &TrackerUpdate, data: &WindowData) {
+    for event in &update.events {
+        if let Err(e) = app.emit(event.name(), event) {
+            log::error!("Failed to emit {}: {}", event.name(), e);
+        }
+    }
+
+    if update.emit_snapshot {
+        if let Err(e) = app.emit("window-data", data) {
+            log::error!("Failed to emit window-data: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(id: u64, x: i32, y: i32, width: u32, height: u32) -> WindowInfo {
+        WindowInfo {
+            id,
+            bounds: Bounds { x, y, width, height },
+            title: format!("Window {}", id),
+            is_maximized: false,
+            z_order: 0,
+            process_name: String::new(),
+            class_name: String::new(),
+            pid: 0,
+            is_focused: false,
+            is_topmost: false,
+            is_fullscreen: false,
+            pieces: Vec::new(),
+            visible_rects: Vec::new(),
+            exposed_top: Vec::new(),
+        }
+    }
+
+    fn data(mut windows: Vec<WindowInfo>) -> WindowData {
+        for (z, win) in windows.iter_mut().enumerate() {
+            win.z_order = z;
+        }
+        WindowData { windows, docks: Vec::new(), native_docks: Vec::new() }
+    }
+
+    fn names(update: &TrackerUpdate) -> Vec<&'static str> {
+        update.events.iter().map(|e| e.name()).collect()
+    }
+
+    #[test]
+    fn first_poll_opens_everything() {
+        let mut tracker = WindowTracker::new();
+        let update = tracker.update(&data(vec![window(1, 0, 0, 100, 100), window(2, 50, 50, 100, 100)]), Instant::now());
+        assert_eq!(names(&update), ["window-opened", "window-opened"]);
+        assert!(update.changed && update.emit_snapshot);
+    }
+
+    #[test]
+    fn closed_windows_come_out_in_z_order() {
+        let mut tracker = WindowTracker::new();
+        let t0 = Instant::now();
+        let ids = [40, 7, 93, 12, 61];
+        tracker.update(&data(ids.iter().map(|&id| window(id, 0, 0, 100, 100)).collect()), t0);
+
+        let update = tracker.update(&data(vec![window(7, 0, 0, 100, 100)]), t0);
+        let closed: Vec<u64> = update.events.iter()
+            .map(|e| match e {
+                WindowEvent::Closed(w) => w.id,
+                other => panic!("unexpected {}", other.name()),
+            })
+            .collect();
+        assert_eq!(closed, [40, 93, 12, 61]);
+    }
+
+    #[test]
+    fn move_and_resize_report_previous_bounds() {
+        let mut tracker = WindowTracker::new();
+        let t0 = Instant::now();
+        tracker.update(&data(vec![window(1, 0, 0, 100, 100), window(2, 500, 0, 100, 100)]), t0);
+
+        let update = tracker.update(&data(vec![window(1, 10, 20, 100, 100), window(2, 500, 0, 300, 100)]), t0);
+        assert_eq!(names(&update), ["window-moved", "window-resized"]);
+        match (&update.events[0], &update.events[1]) {
+            (WindowEvent::Moved(moved), WindowEvent::Resized(resized)) => {
+                assert_eq!(moved.previous, Bounds { x: 0, y: 0, width: 100, height: 100 });
+                assert_eq!(resized.previous.width, 100);
+                assert_eq!(resized.window.bounds.width, 300);
+            }
+            _ => unreachable!(),
+        }
+
+        // Moved and resized in one poll: both events
+        let update = tracker.update(&data(vec![window(1, 0, 0, 50, 50), window(2, 500, 0, 300, 100)]), t0);
+        assert_eq!(names(&update), ["window-moved", "window-resized"]);
+    }
+
+    #[test]
+    fn state_flags_change() {
+        let mut tracker = WindowTracker::new();
+        let t0 = Instant::now();
+        tracker.update(&data(vec![window(1, 0, 0, 100, 100)]), t0);
+
+        let mut maximized = window(1, 0, 0, 100, 100);
+        maximized.is_maximized = true;
+        maximized.is_focused = true;
+        let update = tracker.update(&data(vec![maximized]), t0);
+        assert_eq!(names(&update), ["window-state-changed"]);
+        match &update.events[0] {
+            WindowEvent::StateChanged(change) => {
+                assert!(!change.was_maximized && !change.was_focused);
+                assert!(change.window.is_maximized && change.window.is_focused);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn title_and_z_order_changes_refresh_without_events() {
+        let mut tracker = WindowTracker::new();
+        let t0 = Instant::now();
+        tracker.update(&data(vec![window(1, 0, 0, 100, 100), window(2, 0, 0, 100, 100)]), t0);
+
+        let update = tracker.update(&data(vec![window(2, 0, 0, 100, 100), window(1, 0, 0, 100, 100)]), t0);
+        assert!(update.events.is_empty());
+        assert!(update.changed && update.emit_snapshot);
+
+        let mut renamed = window(2, 0, 0, 100, 100);
+        renamed.title = "Renamed".to_string();
+        let update = tracker.update(&data(vec![renamed, window(1, 0, 0, 100, 100)]), t0);
+        assert!(update.events.is_empty());
+        assert!(update.changed);
+    }
+
+    #[test]
+    fn keyframes_go_out_on_the_interval() {
+        let mut tracker = WindowTracker::new();
+        let t0 = Instant::now();
+        let same = data(vec![window(1, 0, 0, 100, 100)]);
+        assert!(tracker.update(&same, t0).emit_snapshot);
+
+        let quiet = tracker.update(&same, t0 + KEYFRAME_INTERVAL / 2);
+        assert!(!quiet.changed && !quiet.emit_snapshot);
+
+        let keyframe = tracker.update(&same, t0 + KEYFRAME_INTERVAL);
+        assert!(!keyframe.changed && keyframe.emit_snapshot);
+
+        // The interval restarts from the last snapshot sent
+        assert!(!tracker.update(&same, t0 + KEYFRAME_INTERVAL + KEYFRAME_INTERVAL / 2).emit_snapshot);
+        assert!(tracker.update(&same, t0 + KEYFRAME_INTERVAL * 2).emit_snapshot);
+    }
+
+    #[test]
+    fn a_change_resets_the_keyframe_clock() {
+        let mut tracker = WindowTracker::new();
+        let t0 = Instant::now();
+        tracker.update(&data(vec![window(1, 0, 0, 100, 100)]), t0);
+        let moved = tracker.update(&data(vec![window(1, 5, 0, 100, 100)]), t0 + KEYFRAME_INTERVAL / 2);
+        assert!(moved.emit_snapshot);
+        assert!(!tracker.update(&data(vec![window(1, 5, 0, 100, 100)]), t0 + KEYFRAME_INTERVAL).emit_snapshot);
+    }
+}
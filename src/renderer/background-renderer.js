@@ -125,8 +125,10 @@ function renderLoop() {
 function registerEventListeners() {
   window.addEventListener('resize', resizeCanvas);
 
-  window.rainydesk.onVirtualDesktop?.((info) => {
-    virtualDesktop = info;
+  window.rainydesk.onVirtualDesktop?.(async (info) => {
+    // Per-monitor backgrounds only cover their own monitor
+    const surface = await window.rainydesk.getSurfaceDesktop().catch(() => null);
+    virtualDesktop = surface || info;
     resizeCanvas();
   });
 
@@ -428,7 +430,8 @@ async function init() {
   // PHASE 3: Get virtual desktop info (includes phantom DPI detection)
   try {
     const dpiResult = await window.rainydesk.detectPhantomDPI();
-    virtualDesktop = dpiResult.virtualDesktop || await window.rainydesk.getVirtualDesktop();
+    const surfaceDesktop = await window.rainydesk.getSurfaceDesktop();
+    virtualDesktop = surfaceDesktop || dpiResult.virtualDesktop || await window.rainydesk.getVirtualDesktop();
     window.rainydesk.log(`[Background] Virtual desktop: ${virtualDesktop.width}x${virtualDesktop.height}`);
 
     // Default FPS limit to primary monitor's native refresh rate (overridden by autosave if present)
@@ -2,7 +2,7 @@
 
 import { Slider, Toggle, ColorPicker, TriToggle, RotaryKnob, Dropdown, updateSliderValue, showTooltip, hideTooltip } from './components';
 import { applyTheme, applyCustomTheme, generateRandomTheme, getRandomThemeName, DEFAULT_THEME_NAMES, clearCustomFonts, deriveThemeColors } from './themes';
-import type { CustomTheme, UserThemesFile } from './types';
+import type { CustomTheme, InteractionModifier, MonitorInfo, UserThemesFile } from './types';
 import { enable as enableAutostart, disable as disableAutostart, isEnabled as isAutostartEnabled } from '@tauri-apps/plugin-autostart';
 
 // Tab definitions
@@ -118,6 +118,7 @@ interface PanelState {
   rainOverFullscreen: boolean;
   audioMuffling: boolean;
   windowCollision: boolean;
+  umbrellaCursor: boolean;
   // Spatial audio
   spatialAudio: boolean;
   // Audio channels tier (1=Lite, 2=Standard, 3=Full)
@@ -180,6 +181,7 @@ export class RainyDeskPanel {
   private detachBtn: HTMLButtonElement | null = null;
   private isDetached: boolean = false;
   private headerElement: HTMLElement | null = null;
+  private targetPickerElement: HTMLElement | null = null;
 
   // SVG factories — use DOM API + currentColor so icons follow theme colors
   private static createSVG(viewBox: string, pathD: string, fill = 'none', stroke = 'currentColor'): SVGSVGElement {
@@ -337,6 +339,7 @@ export class RainyDeskPanel {
       rainOverFullscreen: false,
       audioMuffling: true,
       windowCollision: true,
+      umbrellaCursor: false,
       // Spatial audio
       spatialAudio: false,
       // Audio channels tier
@@ -420,6 +423,18 @@ export class RainyDeskPanel {
       this.isDetached = await window.rainydesk.getPanelDetached();
     } catch { this.isDetached = false; }
 
+    // UI scale lives in the backend's panel config; localStorage only seeds the first paint.
+    // Nothing saved yet: hand over the auto-fit (or pre-existing localStorage) scale.
+    try {
+      const savedScale = await window.rainydesk.getUiScale();
+      if (savedScale === null) {
+        await window.rainydesk.setUiScale(this.state.uiScale);
+      } else {
+        this.state.uiScale = savedScale;
+        localStorage.setItem('rainscaper-ui-scale', String(savedScale));
+      }
+    } catch { /* keep the localStorage scale */ }
+
     // Hook param updates to flash the autosave indicator (skip non-saveable commands)
     const originalUpdateParam = window.rainydesk.updateRainscapeParam;
     window.rainydesk.updateRainscapeParam = (path: string, value: unknown) => {
@@ -501,20 +516,41 @@ export class RainyDeskPanel {
       };
     });
 
+    // Target window attached, lost or changed (from this panel or a restart)
+    window.rainydesk.onTargetWindow(() => {
+      void this.refreshTargetPicker();
+    });
+
     // Listen for reinit status from the overlay window
     window.rainydesk.onReinitStatus?.((status: 'stopped' | 'initializing' | 'raining') => {
       this.state.appStatus = status;
       this.updateFooterStatus();
     });
 
-    // Monitor hot-swap: show panel on System tab and flash the Reset button
-    window.rainydesk.onMonitorConfigChanged?.(() => {
-      window.rainydesk.log('[Panel] Monitor config changed, alerting user');
-      window.rainydesk.showRainscaper(0, 0);
-      this.state.activeTab = 'system';
-      this.switchTab('system');
-      // Button is created fresh by switchTab → apply glow after DOM settles
-      requestAnimationFrame(() => this.flashReinitButton());
+    // Monitor hot-swap: the backend has already refit the rain to the new layout.
+    // A monitor coming or going shows the panel on System and flashes the Reset button;
+    // a move or rescale only flashes it
+    window.rainydesk.onMonitorConfigChanged?.((change) => {
+      const names = (monitors: MonitorInfo[]) => monitors.map((m) => m.identity.name).join(', ');
+      const parts: string[] = [];
+      if (change.added.length) parts.push(`added ${names(change.added)}`);
+      if (change.removed.length) parts.push(`removed ${names(change.removed)}`);
+      if (change.moved.length) parts.push(`moved ${change.moved.map((m) => m.name).join(', ')}`);
+      for (const r of change.rescaled) {
+        parts.push(`${r.name} ${Math.round(r.from * 100)}% \u2192 ${Math.round(r.to * 100)}%`);
+      }
+      const summary = parts.join('; ') || 'layout refreshed';
+      window.rainydesk.log(`[Panel] Monitor config changed (${summary}), alerting user`);
+
+      if (change.added.length || change.removed.length) {
+        window.rainydesk.showRainscaper(0, 0);
+        this.state.activeTab = 'system';
+        this.switchTab('system');
+        // Button is created fresh by switchTab → apply glow after DOM settles
+        requestAnimationFrame(() => this.flashReinitButton(summary));
+      } else {
+        this.flashReinitButton(summary);
+      }
     });
   }
 
@@ -586,6 +622,7 @@ export class RainyDeskPanel {
         fullscreenDetection: system.fullscreenDetection,
         audioMuffling: system.audioMuffling,
         windowCollision: system.windowCollision,
+        umbrellaCursor: system.umbrellaCursor,
         spatialAudio: system.spatialAudio,
         audioChannels: system.audioChannels,
       },
@@ -746,6 +783,7 @@ export class RainyDeskPanel {
       }
       if (typeof sys.audioMuffling === 'boolean') this.state.audioMuffling = sys.audioMuffling;
       if (typeof sys.windowCollision === 'boolean') this.state.windowCollision = sys.windowCollision;
+      if (typeof sys.umbrellaCursor === 'boolean') this.state.umbrellaCursor = sys.umbrellaCursor;
       if (typeof sys.renderScale === 'number') {
         this.state.renderScale = sys.renderScale;
         this.state.renderScalePending = sys.renderScale;
@@ -811,12 +849,7 @@ export class RainyDeskPanel {
       const idx = fpsSteps.indexOf(value);
       updateSliderValue(this.root, 'fpsLimit', idx >= 0 ? idx : fpsSteps.length - 1);
     } else if (path === 'system.resetPanel') {
-      // Reset UI scale to 100% (triggered by tray "Reset Panel")
-      this.state.uiScale = 1.0;
-      localStorage.setItem('rainscaper-ui-scale', '1');
-      this.applyUIScale(1.0);
-      // Sync slider display (index 2 = 1.0x in scaleSteps)
-      updateSliderValue(this.root, 'uiScale', 2);
+      // Tray "Reset Panel": the backend re-homed the panel at its scaled size.
       // Reset detach state to snapped
       this.isDetached = false;
       this.updateDetachButton();
@@ -2168,8 +2201,8 @@ export class RainyDeskPanel {
     }
   }
 
-  private flashReinitButton(): void {
-    if (!this.reinitButton) return;
+  private flashReinitButton(reason: string): void {
+    if (!this.reinitButton?.isConnected) return;
     // Clear any active cooldown so the button is immediately usable
     this.reinitCooldownEnd = 0;
     if (this.reinitCooldownTimer) {
@@ -2181,10 +2214,15 @@ export class RainyDeskPanel {
     const svgA = 'width="16" height="16" viewBox="0 0 24 24" fill="none" xmlns="http://www.w3.org/2000/svg"';
     const resetIcon = `<svg ${svgA}><path d="M12 21V11M12 11L9 14M12 11L15 14M7 16.8184C4.69636 16.2074 3 14.1246 3 11.6493C3 9.20008 4.8 6.9375 7.5 6.5C8.34694 4.48637 10.3514 3 12.6893 3C15.684 3 18.1317 5.32251 18.3 8.25C19.8893 8.94488 21 10.6503 21 12.4969C21 14.8148 19.25 16.7236 17 16.9725" stroke="currentColor" stroke-width="2" stroke-linecap="round" stroke-linejoin="round"/></svg>`;
     this.reinitButton.innerHTML = `${resetIcon}Reset RainyDesk`;
+    this.reinitButton.title = `Displays changed: ${reason}`;
     this.reinitButton.classList.add('monitor-alert');
     this.reinitButton.scrollIntoView({ behavior: 'smooth', block: 'center' });
-    const cleanup = () => this.reinitButton?.classList.remove('monitor-alert');
-    this.reinitButton.addEventListener('click', cleanup, { once: true });
+    const button = this.reinitButton;
+    const cleanup = () => {
+      button.classList.remove('monitor-alert');
+      button.removeAttribute('title');
+    };
+    button.addEventListener('click', cleanup, { once: true });
   }
 
   private createAudioTab(): HTMLElement {
@@ -3127,6 +3165,52 @@ export class RainyDeskPanel {
       })
     );
 
+    // Umbrella Cursor (pointer shelters the rain beneath it)
+    behavior.content.appendChild(
+      Toggle({
+        label: 'Umbrella Cursor',
+        sublabel: 'Rain splashes off your mouse pointer',
+        checked: this.state.umbrellaCursor,
+        onChange: (v) => {
+          this.state.umbrellaCursor = v;
+          window.rainydesk.updateRainscapeParam('system.umbrellaCursor', v);
+        },
+      })
+    );
+
+    // Hold To Interact (off until the user picks a modifier; the tray's timed session always works)
+    const modifierRow = document.createElement('div');
+    behavior.content.appendChild(modifierRow);
+    window.rainydesk.getInteractionStatus().then((status) => {
+      modifierRow.replaceChildren(
+        Dropdown({
+          id: 'interactionModifier',
+          label: 'Hold To Interact',
+          options: [
+            { label: 'Off', value: 'off' },
+            { label: 'Ctrl', value: 'ctrl' },
+            { label: 'Alt', value: 'alt' },
+            { label: 'Shift', value: 'shift' },
+          ],
+          value: status?.modifier ?? 'off',
+          onChange: (v) => {
+            window.rainydesk.setInteractionModifier(v as InteractionModifier)
+              .catch((err) => window.rainydesk.log(`[Interaction] Failed to set modifier: ${err}`));
+          },
+        })
+      );
+    }).catch((err) => window.rainydesk.log(`[Interaction] Failed to load status: ${err}`));
+
+    // Rain On Window (target-window mode). Candidates come from the latest detection
+    // poll, so the list is refreshed whenever the pointer comes over it
+    const targetPicker = document.createElement('div');
+    targetPicker.addEventListener('pointerenter', () => {
+      if (!targetPicker.querySelector('.dropdown-trigger.open')) void this.refreshTargetPicker();
+    });
+    this.targetPickerElement = targetPicker;
+    behavior.content.appendChild(targetPicker);
+    void this.refreshTargetPicker();
+
     // Rain Over Fullscreen (inverted: ON = rain shows, OFF = rain suppressed)
     behavior.content.appendChild(
       Toggle({
@@ -3391,6 +3475,53 @@ export class RainyDeskPanel {
     return container;
   }
 
+  private async refreshTargetPicker(): Promise<void> {
+    const container = this.targetPickerElement;
+    if (!container) return;
+    try {
+      const [candidates, status] = await Promise.all([
+        window.rainydesk.listTargetWindows(),
+        window.rainydesk.getTargetWindow(),
+      ]);
+      const shorten = (text: string) => (text.length > 32 ? `${text.slice(0, 31)}\u2026` : text);
+      const options = [{ label: 'Whole Desktop', value: '' }];
+      for (const c of candidates) {
+        options.push({ label: shorten(c.title || c.processName), value: String(c.id) });
+      }
+
+      // Attached window, or a remembered app waiting for its window to appear (after its
+      // window closes, rain stays on the whole desktop until the next launch)
+      let value = '';
+      if (status?.window) {
+        value = String(status.window.id);
+        if (!options.some((o) => o.value === value) && status.rule) {
+          options.push({ label: shorten(status.rule.processName), value });
+        }
+      } else if (status?.rule && status.armed) {
+        value = 'waiting';
+        options.splice(1, 0, { label: shorten(`${status.rule.processName} (waiting)`), value });
+      }
+
+      container.replaceChildren(
+        Dropdown({
+          id: 'targetWindow',
+          label: 'Rain On Window',
+          options,
+          value,
+          onChange: (v) => {
+            if (v === 'waiting') return;
+            const change = v === ''
+              ? window.rainydesk.clearTargetWindow()
+              : window.rainydesk.setTargetWindow(Number(v));
+            change.catch((err) => window.rainydesk.log(`[TargetWindow] Failed to change target: ${err}`));
+          },
+        })
+      );
+    } catch (err) {
+      window.rainydesk.log(`[TargetWindow] Failed to list windows: ${err}`);
+    }
+  }
+
   private updateFullscreenMufflingVisibility(): void {
     const el = this.root.querySelector('[data-fs-muffle-toggle="true"]') as HTMLElement;
     if (el) el.style.display = this.state.rainOverFullscreen ? '' : 'none';
@@ -3614,14 +3745,13 @@ export class RainyDeskPanel {
     document.documentElement.style.setProperty('--ui-scale', String(scale));
   }
 
-  /* Full UI scale: resize Tauri window + apply CSS transform */
+  /* Full UI scale: backend persists it and resizes the Tauri window, plus CSS transform */
   private applyUIScale(scale: number): void {
-    const newWidth = Math.round(400 * scale);
-    const newHeight = Math.round(500 * scale);
-    window.rainydesk.resizeRainscaper(newWidth, newHeight);
     this.applyUIScaleCSS(scale);
-    // Re-snap after resize
-    if (!this.isDetached) window.rainydesk.snapPanelToTray();
+    void window.rainydesk.setUiScale(scale).then(() => {
+      // Re-snap after resize
+      if (!this.isDetached) window.rainydesk.snapPanelToTray();
+    }).catch((err) => window.rainydesk.log(`[UIScale] Failed to apply ${scale}: ${err}`));
   }
 
   /* Inject or update SVG radialGradient for trans mode logo cycling */
@@ -38,6 +38,19 @@ export interface UserThemesFile {
 
 // Named interfaces for IPC return types (reused across bridge + consumers)
 
+/** Stable across re-plugging and reordering, unlike `index` */
+export interface MonitorIdentity {
+  /** "edid:DEL-4090-ABC123", "connector:HDMI-1" or "hash:…" */
+  id: string;
+  name: string;
+  connector: string | null;
+  manufacturer: string | null;
+  model: string | null;
+  serial: string | null;
+  /** Clockwise degrees */
+  rotation: 0 | 90 | 180 | 270;
+}
+
 export interface MonitorInfo {
   index: number;
   x: number;
@@ -50,6 +63,21 @@ export interface MonitorInfo {
   workHeight: number;
   scaleFactor: number;
   refreshRate: number;
+  physical: { x: number; y: number; width: number; height: number };
+  identity: MonitorIdentity;
+  transform: RegionTransform;
+}
+
+/**
+ * A monitor's own physical <-> logical mapping (absolute virtual-desktop coords):
+ * logical = logical origin + (physical - physical origin) / scale
+ */
+export interface RegionTransform {
+  scale: number;
+  physicalX: number;
+  physicalY: number;
+  logicalX: number;
+  logicalY: number;
 }
 
 export interface VirtualDesktop {
@@ -61,20 +89,172 @@ export interface VirtualDesktop {
   monitors: MonitorInfo[];
 }
 
-export interface DisplayInfo {
-  monitors: Array<{
-    width: number;
-    height: number;
-    rel_x: number;
-    rel_y: number;
-    is_primary: boolean;
+/** Payload of `monitor-config-changed`; monitors are matched by identity id */
+export interface MonitorConfigChange {
+  added: MonitorInfo[];
+  removed: MonitorInfo[];
+  /** Physical bounds changed (position or resolution) */
+  moved: Array<{ id: string; name: string; index: number; from: MonitorInfo['physical']; to: MonitorInfo['physical'] }>;
+  rescaled: Array<{ id: string; name: string; index: number; from: number; to: number }>;
+  /** New layout, already covered by the overlay and background windows */
+  desktop: VirtualDesktop;
+}
+
+export interface MonitorState {
+  index: number;
+  fullscreenWindow: number | null;
+  maximizedWindow: number | null;
+  workAreaCoverage: number;
+  topmostWindow: number | null;
+}
+
+export interface WindowPollStats {
+  mode: 'active' | 'idle' | 'fullscreen' | 'suspended';
+  intervalMs: number;
+  effectiveHz: number;
+  targetHz: number;
+}
+
+export type TitlePrivacy = 'off' | 'hash' | 'drop';
+
+/** One overlay/background pair spanning the desktop, or one per monitor */
+export type WindowMode = 'mega' | 'perMonitor';
+
+/** Airborne drop handed between per-monitor overlays (global logical coords, screen px/s) */
+export interface HandoffDrop {
+  x: number;
+  y: number;
+  velX: number;
+  velY: number;
+  radius: number;
+}
+
+export interface TargetWindowRule {
+  processName: string;
+  className: string;
+}
+
+export interface TargetWindowCandidate {
+  id: number;
+  title: string;
+  processName: string;
+  className: string;
+  bounds: { x: number; y: number; width: number; height: number };
+}
+
+export interface TargetWindowStatus {
+  rule: TargetWindowRule | null;
+  window: { id: number; bounds: { x: number; y: number; width: number; height: number } } | null;
+  /** Waiting for a window matching `rule`; false once this session's window has closed */
+  armed: boolean;
+}
+
+export type InteractionModifier = 'off' | 'ctrl' | 'alt' | 'shift';
+
+export interface InteractionStatus {
+  active: boolean;
+  source: 'modifier' | 'timed' | null;
+  /** Time left in a timed session */
+  remainingMs: number | null;
+  modifier: InteractionModifier;
+}
+
+/** Pointer position in virtual-desktop logical coords */
+export interface CursorPosition {
+  x: number;
+  y: number;
+  monitor: number;
+}
+
+/** Fractions (0–1) of the obstacle's monitor logical size */
+export interface ObstacleDraft {
+  name: string;
+  monitor: number;
+  rect: { x: number; y: number; width: number; height: number };
+}
+
+export interface Obstacle extends ObstacleDraft {
+  id: number;
+  /** MonitorIdentity.id the obstacle belongs to */
+  monitorId: string;
+}
+
+export type RainZoneKind = 'rainFree' | 'rainOnly';
+
+/** Fractions (0–1) of the zone's monitor logical size */
+export type RainZoneShape =
+  | { type: 'rect'; rect: { x: number; y: number; width: number; height: number } }
+  | { type: 'polygon'; points: Array<{ x: number; y: number }> };
+
+export interface RainZoneDraft {
+  name: string;
+  kind: RainZoneKind;
+  monitor: number;
+  shape: RainZoneShape;
+}
+
+export interface RainZone extends RainZoneDraft {
+  id: number;
+  /** MonitorIdentity.id the zone belongs to */
+  monitorId: string;
+}
+
+/** Zone in virtual-desktop logical coords */
+export interface ProjectedRainZone {
+  id: number;
+  name: string;
+  kind: RainZoneKind;
+  monitor: number;
+  points: Array<[number, number]>;
+  bounds: { x: number; y: number; width: number; height: number };
+}
+
+export interface StaticGeometry {
+  layout: string;
+  obstacles: Array<{
+    id: number;
+    name: string;
+    monitor: number;
+    bounds: { x: number; y: number; width: number; height: number };
   }>;
-  virtual_desktop: {
-    width: number;
-    height: number;
-    x: number;
-    y: number;
-  };
+  rainZones: ProjectedRainZone[];
+}
+
+export interface VoidMaskData {
+  gridScale: number;
+  gridWidth: number;
+  gridHeight: number;
+  /** Row-major alternating run lengths, starting with void (first run may be 0) */
+  runs: number[];
+  /** Per column: topmost non-void row, -1 if the whole column is void */
+  spawnRows: number[];
+  voidCells: number;
+}
+
+export interface WindowRecordingSummary {
+  path: string;
+  frames: number;
+  durationMs: number;
+  bytes: number;
+}
+
+export interface WindowReplayStatus {
+  path: string;
+  playing: boolean;
+  positionMs: number;
+  durationMs: number;
+  frames: number;
+  desktop: VirtualDesktop;
+}
+
+/** Physical pixels, straight from the OS */
+export interface DisplayInfo {
+  index: number;
+  identity: MonitorIdentity;
+  bounds: { x: number; y: number; width: number; height: number };
+  workArea: { x: number; y: number; width: number; height: number };
+  scaleFactor: number;
+  refreshRate: number;
 }
 
 export interface PhantomDPIResult {
@@ -122,6 +302,10 @@ declare global {
       setPanelDetached: (detached: boolean) => Promise<void>;
       snapPanelToTray: () => Promise<void>;
       resizeRainscaper: (width: number, height: number) => Promise<void>;
+      /** Saved panel UI scale; null until one has been set */
+      getUiScale: () => Promise<number | null>;
+      /** Persist the UI scale and resize/re-clamp the panel; resolves to the scale applied */
+      setUiScale: (scale: number) => Promise<number>;
       saveRainscape: (name: string, data: unknown) => Promise<void>;
       readRainscape: (name: string) => Promise<Record<string, unknown>>;
       loadRainscapes: () => Promise<{ root: string[]; custom: string[] }>;
@@ -144,19 +328,70 @@ declare global {
       openLogsFolder: () => Promise<void>;
       getDisplayInfo: () => Promise<DisplayInfo>;
       getVirtualDesktop: () => Promise<VirtualDesktop>;
+      getMonitorStates: () => Promise<MonitorState[]>;
+      onMonitorState: (callback: (states: MonitorState[]) => void) => void;
+      getWindowPollStats: () => Promise<WindowPollStats>;
+      getTitlePrivacy: () => Promise<TitlePrivacy>;
+      setTitlePrivacy: (mode: TitlePrivacy) => Promise<void>;
+      getRainOnDocks: () => Promise<boolean>;
+      setRainOnDocks: (enabled: boolean) => Promise<void>;
+      getWindowMode: () => Promise<WindowMode>;
+      setWindowMode: (mode: WindowMode) => Promise<void>;
+      getSurfaceDesktop: () => Promise<VirtualDesktop | null>;
+      handoffDrops: (drops: HandoffDrop[]) => Promise<void>;
+      onDropsHandoff: (callback: (drops: HandoffDrop[]) => void) => void;
+      listTargetWindows: () => Promise<TargetWindowCandidate[]>;
+      getTargetWindow: () => Promise<TargetWindowStatus | null>;
+      setTargetWindow: (id: number) => Promise<TargetWindowStatus>;
+      clearTargetWindow: () => Promise<void>;
+      onTargetWindow: (callback: (status: TargetWindowStatus) => void) => void;
+      onCursorPosition: (callback: (pos: CursorPosition) => void) => void;
+      getInteractionStatus: () => Promise<InteractionStatus | null>;
+      startInteraction: (seconds?: number) => Promise<InteractionStatus>;
+      stopInteraction: () => Promise<void>;
+      setInteractionModifier: (modifier: InteractionModifier) => Promise<InteractionStatus>;
+      onInteractionMode: (callback: (status: InteractionStatus) => void) => void;
+      listObstacles: () => Promise<Obstacle[]>;
+      addObstacle: (obstacle: ObstacleDraft) => Promise<Obstacle>;
+      updateObstacle: (id: number, obstacle: ObstacleDraft) => Promise<Obstacle>;
+      deleteObstacle: (id: number) => Promise<void>;
+      listRainZones: () => Promise<RainZone[]>;
+      addRainZone: (zone: RainZoneDraft) => Promise<RainZone>;
+      updateRainZone: (id: number, zone: RainZoneDraft) => Promise<RainZone>;
+      deleteRainZone: (id: number) => Promise<void>;
+      getStaticGeometry: () => Promise<StaticGeometry>;
+      getVoidMask: (gridScale: number) => Promise<VoidMaskData>;
+      onStaticGeometry: (callback: (geometry: StaticGeometry) => void) => void;
+      /** Writes logs/diagnostics/Layout_*.png; resolves to its path */
+      saveDiagnosticsImage: () => Promise<string>;
+      /** Same image as raw PNG bytes */
+      getDiagnosticsImage: () => Promise<ArrayBuffer>;
+      startWindowRecording: () => Promise<string>;
+      stopWindowRecording: () => Promise<WindowRecordingSummary | null>;
+      startWindowReplay: (path: string) => Promise<WindowReplayStatus>;
+      setWindowReplayPlaying: (playing: boolean) => Promise<WindowReplayStatus>;
+      seekWindowReplay: (positionMs: number) => Promise<WindowReplayStatus>;
+      stopWindowReplay: () => Promise<void>;
+      getWindowReplayStatus: () => Promise<WindowReplayStatus | null>;
+      onWindowReplayState: (callback: (status: WindowReplayStatus | null) => void) => void;
       getSystemSpecs: () => Promise<{
         cpuModel: string;
         gpuModel: string;
         gpuVramGb: number | null;
         totalRamGb: number;
       }>;
-      getAllDisplays: () => Promise<DisplayInfo>;
+      getAllDisplays: () => Promise<DisplayInfo[]>;
       // Event listeners (from Rust backend or cross-window IPC)
       onDisplayInfo: (callback: (info: DisplayInfo) => void) => void;
       onVirtualDesktop: (callback: (info: VirtualDesktop) => void) => void;
       onToggleRain: (callback: (enabled: boolean) => void) => void;
       onToggleAudio: (callback: (enabled: boolean) => void) => void;
       onWindowData: (callback: (data: unknown) => void) => Promise<() => void>;
+      onWindowOpened: (callback: (data: unknown) => void) => Promise<() => void>;
+      onWindowClosed: (callback: (data: unknown) => void) => Promise<() => void>;
+      onWindowMoved: (callback: (data: unknown) => void) => Promise<() => void>;
+      onWindowResized: (callback: (data: unknown) => void) => Promise<() => void>;
+      onWindowStateChanged: (callback: (data: unknown) => void) => Promise<() => void>;
       onToggleRainscaper: (callback: () => void) => void;
       // Rainscape management
       setRainscape: (name: string) => Promise<void>;
@@ -178,7 +413,7 @@ declare global {
       // Help window hidden event
       onHelpWindowHidden: (callback: () => void) => void;
       // Monitor hot-swap detection
-      onMonitorConfigChanged: (callback: () => void) => void;
+      onMonitorConfigChanged: (callback: (change: MonitorConfigChange) => void) => void;
       // Phantom DPI scaling detection
       detectPhantomDPI: () => Promise<PhantomDPIResult>;
       // Custom themes I/O
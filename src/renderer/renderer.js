@@ -50,8 +50,10 @@ const GRID_SCALE_PRESETS = {
 };
 let GRID_SCALE = GRID_SCALE_PRESETS.normal; // Default to Normal (1:4)
 
-// Virtual desktop info (mega-window architecture)
+// Virtual desktop info (mega-window architecture); in per-monitor mode just this
+// window's monitor, with airborne drops handed to the neighbouring overlays
 let virtualDesktop = null;
+let perMonitorSurface = false;
 
 // Actual canvas dimensions (for particle bounds)
 let canvasWidth = 1920;
@@ -140,6 +142,13 @@ let textureIntensityLinked = true;   // audio.texture.intensityLinked
 let enableFullscreenDetection = true;
 let enableMaximizedDetection = true;
 let enableWindowCollision = true;
+let enableUmbrellaCursor = false;
+const UMBRELLA_RADIUS = 48;          // Canopy radius around the pointer (logical px)
+
+// Interaction mode: backend makes the overlay accept the mouse (modifier held or timed)
+let interactionActive = false;
+let lastInteractionPointer = null;   // { x, y, t } of the previous pointer sample
+const INTERACTION_RADIUS = 64;       // Disturbance radius around the pointer (logical px)
 let enableAudioMuffling = true;
 let enableMaximizedMuffling = true;
 
@@ -311,6 +320,12 @@ let windowUpdateDebounceTimer = null;
 let lastWindowData = null;
 let lastWindowZonesForReinit = null;
 
+// Target-window mode: rain spawns only above this window (logical bounds; null = whole desktop)
+let targetWindowBounds = null;
+
+// Custom static obstacles for the current monitor layout, as window-like zones
+let staticObstacleZones = [];
+
 /* Re-classify stored window data when a behavior toggle changes.
    Reclassifies from raw windowZones so toggle state affects void/normal split. */
 function reprocessWindowState() {
@@ -318,7 +333,7 @@ function reprocessWindowState() {
 
   // Re-classify from raw window data (respects current toggle state)
   const voidWins = [];
-  const normal = [];
+  let normal = [];
   const spawn = [];
   const detectedFullscreenMonitors = new Set();
   const detectedMaximizedMonitors = new Set();
@@ -354,6 +369,12 @@ function reprocessWindowState() {
     }
   }
 
+  // Occluded parts of windows don't collide
+  normal = collisionZones(normal);
+
+  // Custom obstacles always collide like normal windows
+  normal.push(...staticObstacleZones);
+
   // Update cached classification
   lastWindowZonesForReinit = { normal, void: voidWins, spawn };
 
@@ -366,6 +387,7 @@ function reprocessWindowState() {
     }
     suppressFullscreenColumns();
     suppressMaximizedColumns();
+    suppressOutsideTargetWindow();
   }
 
   if (matrixRenderer) {
@@ -554,6 +576,19 @@ function suppressFullscreenColumns() {
   }
 }
 
+/* Sets spawnMap columns outside the target window to -1 (target-window mode).
+   Must run AFTER updateWindowZones since that restores spawnMap from originalSpawnMap. */
+function suppressOutsideTargetWindow() {
+  if (!gridSimulation || !virtualDesktop || !targetWindowBounds) return;
+
+  const scale = GRID_SCALE;
+  const originX = virtualDesktop.originX || 0;
+  const startCol = Math.floor((targetWindowBounds.x - originX) * scale);
+  const endCol = Math.ceil((targetWindowBounds.x + targetWindowBounds.width - originX) * scale);
+  gridSimulation.suppressSpawnColumns(0, startCol);
+  gridSimulation.suppressSpawnColumns(endCol, Number.MAX_SAFE_INTEGER);
+}
+
 /* Mirrors updateFullscreenState() for maximized windows */
 function updateMaximizedState() {
   if (!enableMaximizedDetection) {
@@ -594,86 +629,32 @@ function suppressMaximizedColumns() {
   }
 }
 
-/* Build void mask: 1 = gap between monitors, 0 = usable */
-function buildVoidMask(desktop, scale) {
-  const gridWidth = Math.ceil(desktop.width * scale);
-  const gridHeight = Math.ceil(desktop.height * scale);
-
-  const voidMask = new Uint8Array(gridWidth * gridHeight);
-  voidMask.fill(1);
-
-  for (const monitor of desktop.monitors) {
-    const mx = Math.floor(monitor.x * scale);
-    const my = Math.floor(monitor.y * scale);
-    const mw = Math.ceil(monitor.width * scale);
-    const mh = Math.ceil(monitor.height * scale);
-
-    for (let y = my; y < my + mh && y < gridHeight; y++) {
-      for (let x = mx; x < mx + mw && x < gridWidth; x++) {
-        voidMask[y * gridWidth + x] = 0;
-      }
-    }
-  }
-
-  const voidCount = voidMask.reduce((sum, v) => sum + v, 0);
-  window.rainydesk.log(`[VoidMask] Grid ${gridWidth}x${gridHeight}, void=${voidCount}, usable=${voidMask.length - voidCount}`);
-
-  // Debug: Log each monitor's grid bounds
-  for (const monitor of desktop.monitors) {
-    const mx = Math.floor(monitor.x * scale);
-    const my = Math.floor(monitor.y * scale);
-    const mw = Math.ceil(monitor.width * scale);
-    const mh = Math.ceil(monitor.height * scale);
-    window.rainydesk.log(`[VoidMask] Monitor ${monitor.index}: grid x=${mx}-${mx+mw-1}, y=${my}-${my+mh-1}`);
+/* Void mask (1 = gap between/around monitors, 0 = usable) + spawn map (per-column
+   topmost non-void Y), rasterised once by the backend so every window gets identical cells */
+async function loadVoidMask(scale) {
+  let data;
+  try {
+    data = await window.rainydesk.getVoidMask(scale);
+  } catch (err) {
+    // Rain everywhere beats no rain at all; gaps between monitors just go unmasked
+    window.rainydesk.log(`[VoidMask] Failed to load, falling back to an all-open mask: ${err}`);
+    const gridWidth = Math.ceil(virtualDesktop.width * scale);
+    const gridHeight = Math.ceil(virtualDesktop.height * scale);
+    return { voidMask: new Uint8Array(gridWidth * gridHeight), spawnMap: new Int16Array(gridWidth), fallback: true };
   }
 
-  // Debug: Check for void columns (entire column is void = gap between monitors)
-  let voidColumnRanges = [];
-  let inVoidRange = false;
-  let rangeStart = 0;
-  for (let x = 0; x < gridWidth; x++) {
-    let columnIsVoid = true;
-    for (let y = 0; y < gridHeight; y++) {
-      if (voidMask[y * gridWidth + x] === 0) {
-        columnIsVoid = false;
-        break;
-      }
-    }
-    if (columnIsVoid && !inVoidRange) {
-      inVoidRange = true;
-      rangeStart = x;
-    } else if (!columnIsVoid && inVoidRange) {
-      inVoidRange = false;
-      voidColumnRanges.push(`${rangeStart}-${x-1}`);
-    }
-  }
-  if (inVoidRange) {
-    voidColumnRanges.push(`${rangeStart}-${gridWidth-1}`);
-  }
-  if (voidColumnRanges.length > 0) {
-    window.rainydesk.log(`[VoidMask] WARNING: Void column ranges (gaps): ${voidColumnRanges.join(', ')}`);
-  } else {
-    window.rainydesk.log(`[VoidMask] No void columns - monitors are edge-to-edge horizontally`);
+  const voidMask = new Uint8Array(data.gridWidth * data.gridHeight);
+  let offset = 0;
+  let value = 1;
+  for (const run of data.runs) {
+    if (value === 1) voidMask.fill(1, offset, offset + run);
+    offset += run;
+    value ^= 1;
   }
 
-  return voidMask;
-}
-
-/* Compute spawn map (per-column topmost non-void Y) */
-function computeSpawnMap(voidMask, gridWidth, gridHeight) {
-  const spawnMap = new Int16Array(gridWidth);
-  spawnMap.fill(-1);
-
-  for (let x = 0; x < gridWidth; x++) {
-    for (let y = 0; y < gridHeight; y++) {
-      if (voidMask[y * gridWidth + x] === 0) {
-        spawnMap[x] = y;
-        break;
-      }
-    }
-  }
-
-  return spawnMap;
+  const spawnMap = Int16Array.from(data.spawnRows);
+  window.rainydesk.log(`[VoidMask] Grid ${data.gridWidth}x${data.gridHeight}, void=${data.voidCells}, usable=${voidMask.length - data.voidCells}`);
+  return { voidMask, spawnMap, fallback: false };
 }
 
 /* Compute splash floor map (per-column bottom of work area) */
@@ -730,6 +711,33 @@ function computeDisplayFloorMap(desktop, scale, gridWidth, gridHeight) {
   return displayFloorMap;
 }
 
+/* Collision surfaces from the backend's occlusion pass: visible rects, else the exposed
+   top-edge spans as 1px ledges. Null when neither was sent, so full bounds are used. */
+function windowSurfaces(w) {
+  if (Array.isArray(w.visibleRects)) {
+    return w.visibleRects.map(r => ({ x: r.x, y: r.y, width: r.width, height: r.height }));
+  }
+  if (Array.isArray(w.exposedTop)) {
+    return w.exposedTop.map(span => ({ x: span.x, y: span.y, width: span.width, height: 1 }));
+  }
+  return null;
+}
+
+/* Expand normal windows into their visible surfaces; the whole-window bounds stay
+   on the zone for maximized/fullscreen classification only */
+function collisionZones(windows) {
+  return windows.flatMap(w => w.surfaces
+    ? w.surfaces.map(r => ({ ...r, title: w.title, isMaximized: false }))
+    : [w]);
+}
+
+function sameSurfaces(a, b) {
+  if (!a || !b) return a === b;
+  if (a.length !== b.length) return false;
+  return a.every((r, i) => r.x === b[i].x && r.y === b[i].y &&
+    r.width === b[i].width && r.height === b[i].height);
+}
+
 function windowsChanged(oldWindows, newWindows) {
   if (!oldWindows || !newWindows) return true;
   if (oldWindows.length !== newWindows.length) return true;
@@ -740,7 +748,8 @@ function windowsChanged(oldWindows, newWindows) {
     if (!a || !b) return true;
     if (a.x !== b.x || a.y !== b.y ||
         a.width !== b.width || a.height !== b.height ||
-        a.isMaximized !== b.isMaximized) {
+        a.isMaximized !== b.isMaximized ||
+        !sameSurfaces(a.surfaces, b.surfaces)) {
       return true;
     }
   }
@@ -963,6 +972,7 @@ function migrateToV2(data) {
       fullscreenDetection: data.system?.fullscreenDetection,
       audioMuffling: data.system?.audioMuffling,
       windowCollision: data.system?.windowCollision,
+      umbrellaCursor: data.system?.umbrellaCursor,
     },
   };
 }
@@ -1042,6 +1052,7 @@ function gatherPresetData() {
       fullscreenDetection: enableFullscreenDetection,
       audioMuffling: enableAudioMuffling,
       windowCollision: enableWindowCollision,
+      umbrellaCursor: enableUmbrellaCursor,
     },
   };
 }
@@ -1306,6 +1317,10 @@ function applyRainscapeData(rawData) {
       enableWindowCollision = data.system.windowCollision;
       window.rainydesk.updateRainscapeParam('system.windowCollision', data.system.windowCollision);
     }
+    if (typeof data.system.umbrellaCursor === 'boolean') {
+      enableUmbrellaCursor = data.system.umbrellaCursor;
+      window.rainydesk.updateRainscapeParam('system.umbrellaCursor', data.system.umbrellaCursor);
+    }
     if (typeof data.system.renderScale === 'number') {
       renderScale = Math.max(0.125, Math.min(1.0, data.system.renderScale));
       window.rainydesk.updateRainscapeParam('physics.renderScale', renderScale);
@@ -1347,6 +1362,7 @@ async function reinitializePhysics(newGridScale) {
     if (gridSimulation) {
       gridSimulation.onCollision = null;
       gridSimulation.onDebugLog = null;
+      gridSimulation.onDropExit = null;
       gridSimulation.dispose();
       gridSimulation = null;
     }
@@ -1607,26 +1623,137 @@ function registerEventListeners() {
     }
   });
 
+  // Target-window mode: follow the chosen window, back to full desktop when it closes
+  window.rainydesk.onTargetWindow((status) => {
+    const bounds = status && status.window ? status.window.bounds : null;
+    const wasActive = targetWindowBounds !== null;
+    targetWindowBounds = bounds;
+    if (bounds && !wasActive) {
+      window.rainydesk.log(`[TargetWindow] Attached to ${status.rule.processName} [${status.rule.className}]`);
+    } else if (!bounds && wasActive) {
+      window.rainydesk.log('[TargetWindow] Detached, raining on full desktop');
+    }
+    reprocessWindowState();
+  });
+
+  // Umbrella cursor: backend streams the pointer only while system.umbrellaCursor is on
+  window.rainydesk.onCursorPosition((pos) => {
+    if (!enableUmbrellaCursor || !gridSimulation) return;
+    gridSimulation.setUmbrella(pos.x, pos.y, UMBRELLA_RADIUS);
+  });
+
+  // Interaction mode: pointer drags flick drops and slosh puddles
+  window.rainydesk.onInteractionMode((status) => {
+    interactionActive = Boolean(status && status.active);
+    lastInteractionPointer = null;
+    document.body.style.cursor = interactionActive ? 'grab' : '';
+  });
+
+  const handleInteractionPointer = (e) => {
+    if (!interactionActive || !gridSimulation || !virtualDesktop) return;
+    const x = e.clientX + (virtualDesktop.originX || 0);
+    const y = e.clientY + (virtualDesktop.originY || 0);
+    const now = performance.now();
+    const prev = lastInteractionPointer;
+    lastInteractionPointer = { x, y, t: now };
+    if (e.type === 'pointermove' && e.buttons === 0) return;
+
+    // Click pokes straight down; drags carry the pointer's velocity
+    let velX = 0;
+    let velY = 600;
+    if (prev && e.type === 'pointermove') {
+      const dt = Math.max(1, now - prev.t) / 1000;
+      velX = (x - prev.x) / dt;
+      velY = (y - prev.y) / dt;
+    }
+    gridSimulation.disturb(x, y, velX, velY, INTERACTION_RADIUS);
+  };
+  window.addEventListener('pointerdown', handleInteractionPointer);
+  window.addEventListener('pointermove', handleInteractionPointer);
+  window.addEventListener('keydown', (e) => {
+    if (interactionActive && e.key === 'Escape') window.rainydesk.stopInteraction();
+  });
+
+  // Custom obstacles and rain zones: re-sent by the backend on edits and monitor layout changes.
+  // Zones live in the backend's void mask, so a zone change reloads it into the running grid.
+  let appliedRainZones = null;
+  const reloadRainZones = async () => {
+    const { voidMask, spawnMap, fallback } = await loadVoidMask(GRID_SCALE);
+    // Keep the running mask rather than swap in the fallback.
+    // Size mismatch means the layout changed too; its reinit picks the zones up
+    if (!fallback && gridSimulation?.setStaticMasks(voidMask, spawnMap)) reprocessWindowState();
+  };
+  const applyStaticGeometry = (geometry) => {
+    const rainZones = JSON.stringify(geometry?.rainZones || []);
+    if (appliedRainZones !== null && rainZones !== appliedRainZones && gridSimulation) {
+      reloadRainZones().catch(err => window.rainydesk.log(`[RainZones] Failed to reload void mask: ${err}`));
+    }
+    appliedRainZones = rainZones;
+
+    staticObstacleZones = (geometry?.obstacles || []).map(o => ({
+      x: o.bounds.x,
+      y: o.bounds.y,
+      width: o.bounds.width,
+      height: o.bounds.height,
+      title: `[obstacle ${o.name}]`,
+      isMaximized: false
+    }));
+    window.rainydesk.log(`[Obstacles] ${staticObstacleZones.length} obstacle(s) for layout ${geometry?.layout}`);
+    reprocessWindowState();
+  };
+  window.rainydesk.onStaticGeometry(applyStaticGeometry);
+  window.rainydesk.getStaticGeometry()
+    .then(applyStaticGeometry)
+    .catch(err => window.rainydesk.log(`[Obstacles] Failed to load: ${err}`));
+
+  // Display topology change: the backend has already moved this window onto the new
+  // desktop, so adopt its layout and rebuild the grid (void mask, floors) to match
+  window.rainydesk.onMonitorConfigChanged(async (change) => {
+    const surface = perMonitorSurface ? await window.rainydesk.getSurfaceDesktop().catch(() => null) : null;
+    virtualDesktop = surface || change.desktop;
+    const summary = `+${change.added.length} -${change.removed.length} moved ${change.moved.length} rescaled ${change.rescaled.length}`;
+    window.rainydesk.log(`[Monitors] Layout changed (${summary}), rebuilding for ${virtualDesktop.width}x${virtualDesktop.height}`);
+    resizeCanvas();
+    reinitializePhysics(GRID_SCALE);
+  });
+
+  // Drops that left a neighbouring per-monitor overlay over this monitor
+  window.rainydesk.onDropsHandoff((drops) => {
+    if (gridSimulation && !reinitInProgress) gridSimulation.injectDrops(drops);
+  });
+
   // Window data handler
   let windowDataLogged = false;
   window.rainydesk.onWindowData((data) => {
-    // Window detector sends physical pixel coordinates — convert to logical
-    // using the primary monitor's scale factor (no-op at 100% / scale=1)
-    const dpiScale = virtualDesktop?.primaryScaleFactor || window.devicePixelRatio || 1;
-
+    // Backend already sends virtual-desktop logical coordinates (each window mapped
+    // through its own monitor's scale), so no DPI conversion is needed here.
     // Filter out RainyDesk and DevTools windows
     const newWindowZones = data.windows
       .filter(w => !w.title || !w.title.startsWith('RainyDesk'))
       .filter(w => !w.title || !w.title.includes('DevTools'))
       .map(w => ({
-        x: w.bounds.x / dpiScale,
-        y: w.bounds.y / dpiScale,
-        width: w.bounds.width / dpiScale,
-        height: w.bounds.height / dpiScale,
+        x: w.bounds.x,
+        y: w.bounds.y,
+        width: w.bounds.width,
+        height: w.bounds.height,
         title: w.title,
-        isMaximized: w.isMaximized || false
+        isMaximized: w.isMaximized || false,
+        surfaces: windowSurfaces(w)
       }));
 
+    // Taskbars/docks/panels (backend sends none when rain on docks is off)
+    for (const dock of data.docks || []) {
+      newWindowZones.push({
+        x: dock.bounds.x,
+        y: dock.bounds.y,
+        width: dock.bounds.width,
+        height: dock.bounds.height,
+        title: `[dock ${dock.edge}]`,
+        isMaximized: false,
+        isDock: true
+      });
+    }
+
     // Skip update if windows haven't changed
     if (!windowsChanged(lastWindowData, newWindowZones)) {
       return;
@@ -1657,13 +1784,19 @@ function registerEventListeners() {
       // Detection toggles affect classification: when detection is OFF
       // (Rain Over X is ON), those windows become normal collision surfaces
       const voidWindows = [];
-      const normalWindows = [];
+      let normalWindows = [];
       const spawnBlockWindows = [];
       const detectedFullscreenMonitors = new Set();
       const detectedMaximizedMonitors = new Set();
 
       if (virtualDesktop && virtualDesktop.monitors) {
         for (const win of windowZones) {
+          // Docks always collide normally; they'd never match maximized/fullscreen anyway
+          if (win.isDock) {
+            normalWindows.push(win);
+            continue;
+          }
+
           let classified = false;
 
           for (const mon of virtualDesktop.monitors) {
@@ -1704,6 +1837,12 @@ function registerEventListeners() {
           }
         }
 
+        // Occluded parts of windows don't collide
+        normalWindows = collisionZones(normalWindows);
+
+        // Custom obstacles always collide like normal windows
+        normalWindows.push(...staticObstacleZones);
+
         // Fullscreen debounce (quick enter, slow exit with hysteresis)
         if (enableFullscreenDetection && virtualDesktop && virtualDesktop.monitors) {
           const now = Date.now();
@@ -1849,6 +1988,7 @@ function registerEventListeners() {
           gridSimulation.updateWindowZones([], [], []);
         }
         suppressFullscreenColumns();
+        suppressOutsideTargetWindow();
       }
 
       // Update Matrix renderer window zones (adjust for VD origin)
@@ -2249,6 +2389,9 @@ function registerEventListeners() {
     } else if (path === 'system.windowCollision') {
       enableWindowCollision = Boolean(value);
       reprocessWindowState();
+    } else if (path === 'system.umbrellaCursor') {
+      enableUmbrellaCursor = Boolean(value);
+      if (!enableUmbrellaCursor && gridSimulation) gridSimulation.clearUmbrella();
     } else if (path === 'system.renderScale') {
       renderScale = Math.max(0.125, Math.min(1.0, Number(value)));
     } else if (path === 'system.audioChannels') {
@@ -2299,11 +2442,10 @@ async function initPixiPhysics() {
   const logicWidth = Math.ceil(virtualDesktop.width * scale);
   const logicHeight = Math.ceil(virtualDesktop.height * scale);
 
-  // Build void mask, spawn map, floor maps (yield between heavy ops to avoid UI freeze)
-  const voidMask = buildVoidMask(virtualDesktop, scale);
+  // Void mask + spawn map from the backend, then floor maps (yield between heavy ops to avoid UI freeze)
+  const { voidMask, spawnMap } = await loadVoidMask(scale);
   await new Promise(r => setTimeout(r, 0));
 
-  const spawnMap = computeSpawnMap(voidMask, logicWidth, logicHeight);
   const floorMap = computeFloorMap(virtualDesktop, scale, logicWidth, logicHeight);
   const displayFloorMap = computeDisplayFloorMap(virtualDesktop, scale, logicWidth, logicHeight);
   await new Promise(r => setTimeout(r, 0));
@@ -2336,6 +2478,9 @@ async function initPixiPhysics() {
   await new Promise(r => setTimeout(r, 0));
 
   gridSimulation.onDebugLog = (msg) => window.rainydesk.log(msg);
+  if (perMonitorSurface) {
+    gridSimulation.onDropExit = (drops) => void window.rainydesk.handoffDrops(drops).catch(() => {});
+  }
   gridSimulation.setIntensity(config.intensity / 100);
   gridSimulation.setWind(config.wind);
 
@@ -2403,7 +2548,7 @@ async function initMatrixRenderer(gen) {
     matrixRenderer.updateWindowZones(zones);
     window.rainydesk.log(`[Matrix] Loaded ${zones.length} window zones (origin offset: ${originX}, ${originY})`);
   } else if (lastWindowData?.length) {
-    const zones = lastWindowData.map(w => ({
+    const zones = collisionZones(lastWindowData).map(w => ({
       left: w.x - originX,
       top: w.y - originY,
       right: w.x + w.width - originX,
@@ -2648,7 +2793,10 @@ async function init() {
 
   // PHASE 3: Get display info and calculate (includes phantom DPI detection)
   const dpiResult = await window.rainydesk.detectPhantomDPI();
-  virtualDesktop = dpiResult.virtualDesktop || await window.rainydesk.getVirtualDesktop();
+  const surfaceDesktop = await window.rainydesk.getSurfaceDesktop();
+  perMonitorSurface = surfaceDesktop !== null;
+  virtualDesktop = surfaceDesktop || dpiResult.virtualDesktop || await window.rainydesk.getVirtualDesktop();
+  if (perMonitorSurface) window.rainydesk.log(`[Init] Per-monitor overlay: ${virtualDesktop.width}x${virtualDesktop.height} at (${virtualDesktop.originX}, ${virtualDesktop.originY})`);
 
   // Default FPS limit to primary monitor's native refresh rate (overridden by autosave if present)
   const nativeHz = virtualDesktop?.monitors?.[virtualDesktop.primaryIndex]?.refreshRate || 60;
@@ -10,6 +10,8 @@
 import {
     CollisionCallback,
     CollisionEvent,
+    DropExitCallback,
+    HandoffDrop,
     SimulationConfig,
     WindowZone,
     DEFAULT_CONFIG,
@@ -58,6 +60,11 @@ export class GridSimulation {
     private floorMap: Int16Array | null = null;        // Per-column splash floor Y (work area bottom)
     private displayFloorMap: Int16Array | null = null; // Per-column puddle floor Y (display bottom)
 
+    // Umbrella cursor canopy (grid space, radius <= 0 = off)
+    private umbrellaX = 0;
+    private umbrellaY = 0;
+    private umbrellaRadius = 0;
+
     // Rain particles (Lagrangian layer)
     private dropsX: Float32Array;
     private dropsY: Float32Array;
@@ -116,6 +123,10 @@ export class GridSimulation {
     // Audio callback
     public onCollision: CollisionCallback | null = null;
 
+    // Drops leaving through the sides or bottom (per-monitor hand-off); flushed once per step
+    public onDropExit: DropExitCallback | null = null;
+    private exitedDrops: HandoffDrop[] = [];
+
     // Reusable event object (zero-GC pattern)
     private readonly collisionEvent: CollisionEvent = {
         velocity: 0,
@@ -306,6 +317,44 @@ export class GridSimulation {
 
         // Splashes every frame (visual-only)
         this.stepSplashes(dt);
+
+        if (this.onDropExit && this.exitedDrops.length > 0) {
+            const exited = this.exitedDrops;
+            this.exitedDrops = [];
+            this.onDropExit(exited);
+        }
+    }
+
+    /* Take over drops handed off by a neighbouring overlay (global coords, screen units) */
+    injectDrops(drops: HandoffDrop[]): void {
+        const scale = this.gridScale;
+        for (const drop of drops) {
+            if (this.dropCount >= this.config.maxDrops) return;
+            const i = this.dropCount++;
+            this.dropsX[i] = (drop.x - this.globalOffsetX) * scale;
+            this.dropsY[i] = (drop.y - this.globalOffsetY) * scale;
+            this.dropsPrevX[i] = this.dropsX[i]!;
+            this.dropsPrevY[i] = this.dropsY[i]!;
+            this.dropsVelX[i] = drop.velX * scale;
+            this.dropsVelY[i] = drop.velY * scale;
+            this.dropsRadius[i] = drop.radius * scale;
+            this.dropsOpacity[i] = 1.0;
+        }
+    }
+
+    /**
+     * Swap in a new static void mask and spawn map (rain zones changed) without a reinit.
+     * Takes effect on the next updateWindowZones. Returns false if the sizes don't match
+     * this grid; the caller should reinitialize instead.
+     */
+    setStaticMasks(voidMask: Uint8Array, spawnMap: Int16Array): boolean {
+        if (voidMask.length !== this.grid.length || spawnMap.length !== this.gridWidth) {
+            return false;
+        }
+        this.voidMask = voidMask;
+        this.spawnMap = spawnMap;
+        this.originalSpawnMap = new Int16Array(spawnMap);
+        return true;
     }
 
     /* Window zone update logic */
@@ -433,6 +482,62 @@ export class GridSimulation {
         }
     }
 
+    /* Place the umbrella canopy at a global screen point (pointer position, logical px). */
+    setUmbrella(x: number, y: number, radius: number): void {
+        this.umbrellaX = (x - this.globalOffsetX) * this.gridScale;
+        this.umbrellaY = (y - this.globalOffsetY) * this.gridScale;
+        this.umbrellaRadius = Math.max(0, radius * this.gridScale);
+    }
+
+    clearUmbrella(): void {
+        this.umbrellaRadius = 0;
+    }
+
+    /**
+     * Pointer interaction (interaction mode): flick drops and slosh puddles around a
+     * global screen point. Velocity is the pointer's, in logical px/s.
+     */
+    disturb(x: number, y: number, velX: number, velY: number, radius: number): void {
+        const cx = (x - this.globalOffsetX) * this.gridScale;
+        const cy = (y - this.globalOffsetY) * this.gridScale;
+        const r = Math.max(1, radius * this.gridScale);
+        const vx = velX * this.gridScale;
+        const vy = velY * this.gridScale;
+
+        // Drops: push along the pointer's motion, falling off toward the edge
+        for (let i = 0; i < this.dropCount; i++) {
+            const dx = this.dropsX[i]! - cx;
+            const dy = this.dropsY[i]! - cy;
+            const dist = Math.sqrt(dx * dx + dy * dy);
+            if (dist > r) continue;
+            const falloff = 1 - dist / r;
+            this.dropsVelX[i] = this.dropsVelX[i]! + vx * falloff;
+            this.dropsVelY[i] = this.dropsVelY[i]! + vy * falloff;
+        }
+
+        // Puddles: add energy + momentum, splash where the pointer hits hardest
+        const speed = Math.min(1, Math.sqrt(velX * velX + velY * velY) / 2000);
+        const push = Math.max(-1, Math.min(1, velX / 1000));
+        const x0 = Math.max(0, Math.floor(cx - r));
+        const x1 = Math.min(this.gridWidth - 1, Math.ceil(cx + r));
+        const y0 = Math.max(0, Math.floor(cy - r));
+        const y1 = Math.min(this.gridHeight - 1, Math.ceil(cy + r));
+        for (let gy = y0; gy <= y1; gy++) {
+            for (let gx = x0; gx <= x1; gx++) {
+                const index = gy * this.gridWidth + gx;
+                if (this.grid[index] !== CELL_WATER) continue;
+                const dist = Math.sqrt((gx - cx) * (gx - cx) + (gy - cy) * (gy - cy));
+                if (dist > r) continue;
+                const falloff = 1 - dist / r;
+                this.waterEnergy[index] = Math.min(1.0, this.waterEnergy[index]! + speed * falloff);
+                this.waterMomentumX[index] = Math.max(-1, Math.min(1, this.waterMomentumX[index]! + push * falloff));
+                if (falloff > 0.7 && speed > 0.2) {
+                    this.spawnPuddleSplash(gx, gy, speed * falloff);
+                }
+            }
+        }
+    }
+
     /* Clear all in-flight drops and splashes (used on fullscreen state change). */
     clearAllDrops(): void {
         this.dropCount = 0;
@@ -775,6 +880,7 @@ export class GridSimulation {
             } else {
                 // Normal mode: drops exit at the bottom
                 if (this.dropsY[i]! >= this.gridHeight) {
+                    this.reportExit(i);
                     this.despawnDrop(i);
                     i--;
                     continue;
@@ -787,12 +893,14 @@ export class GridSimulation {
             const velX = this.dropsVelX[i]!;
             if (dropX < 0 && velX < 0) {
                 // Off left side AND moving left — will never enter, despawn
+                this.reportExit(i);
                 this.despawnDrop(i);
                 i--;
                 continue;
             }
             if (dropX >= this.gridWidth && velX > 0) {
                 // Off right side AND moving right — will never enter, despawn
+                this.reportExit(i);
                 this.despawnDrop(i);
                 i--;
                 continue;
@@ -817,6 +925,18 @@ export class GridSimulation {
             const cellIndex = cellY * this.gridWidth + cellX;
             const cellValue = this.grid[cellIndex]!;
 
+            // Umbrella canopy: upper half-disc above the pointer, drops splash off its surface
+            if (this.umbrellaRadius > 0) {
+                const dx = this.dropsX[i]! - this.umbrellaX;
+                const dy = this.dropsY[i]! - this.umbrellaY;
+                if (dy <= 0 && dx * dx + dy * dy <= this.umbrellaRadius * this.umbrellaRadius) {
+                    this.spawnSplash(this.dropsX[i]!, this.dropsY[i]!, this.dropsVelX[i]!, this.dropsVelY[i]!);
+                    this.despawnDrop(i);
+                    i--;
+                    continue;
+                }
+            }
+
             // VOID cells: immediate despawn with splash (no air-transition check needed)
             // This handles rain falling off monitor edges into gaps
             if (cellValue === CELL_VOID) {
@@ -1776,6 +1896,18 @@ export class GridSimulation {
         }
     }
 
+    private reportExit(index: number): void {
+        if (!this.onDropExit) return;
+        const s = this.screenScale;
+        this.exitedDrops.push({
+            x: this.dropsX[index]! * s + this.globalOffsetX,
+            y: this.dropsY[index]! * s + this.globalOffsetY,
+            velX: this.dropsVelX[index]! * s,
+            velY: this.dropsVelY[index]! * s,
+            radius: this.dropsRadius[index]! * s,
+        });
+    }
+
     private despawnDrop(index: number): void {
         // Swap with last drop (O(1) removal)
         const last = this.dropCount - 1;
@@ -24,6 +24,21 @@ export interface CollisionEvent {
 /** Callback for collision events (wired to AudioSystem) */
 export type CollisionCallback = (event: CollisionEvent) => void;
 
+/** Airborne drop leaving the grid, for hand-off to a neighbouring per-monitor overlay */
+export interface HandoffDrop {
+    /** Position in global (virtual desktop) coordinates */
+    x: number;
+    y: number;
+    /** Velocity in screen pixels/sec */
+    velX: number;
+    velY: number;
+    /** Radius in screen pixels */
+    radius: number;
+}
+
+/** Callback for drops that left the grid this frame (batched per step) */
+export type DropExitCallback = (drops: HandoffDrop[]) => void;
+
 /** Display info from Tauri backend */
 export interface DisplayInfo {
     id: string;
@@ -3,8 +3,27 @@
 import { invoke } from '@tauri-apps/api/core';
 import { listen, emit } from '@tauri-apps/api/event';
 import { getVersion } from '@tauri-apps/api/app';
+import { getCurrentWebviewWindow } from '@tauri-apps/api/webviewWindow';
 import type {
   VirtualDesktop,
+  MonitorConfigChange,
+  MonitorState,
+  WindowPollStats,
+  TitlePrivacy,
+  WindowMode,
+  HandoffDrop,
+  WindowRecordingSummary,
+  WindowReplayStatus,
+  TargetWindowCandidate,
+  TargetWindowStatus,
+  CursorPosition,
+  InteractionStatus,
+  Obstacle,
+  ObstacleDraft,
+  RainZone,
+  RainZoneDraft,
+  StaticGeometry,
+  VoidMaskData,
   DisplayInfo,
   PhantomDPIResult,
   RendererStats,
@@ -23,10 +42,92 @@ window.rainydesk = {
   getDisplayInfo: () => invoke<DisplayInfo>('get_display_info'),
 
   // Get all displays for multi-monitor grid calculation
-  getAllDisplays: () => invoke<DisplayInfo>('get_all_displays'),
+  getAllDisplays: () => invoke<DisplayInfo[]>('get_all_displays'),
 
   // Get virtual desktop info (bounding box + monitor regions)
   getVirtualDesktop: () => invoke<VirtualDesktop>('get_virtual_desktop'),
+
+  // Per-monitor occupancy (fullscreen/maximized occupant, coverage, topmost window)
+  getMonitorStates: () => invoke<MonitorState[]>('get_monitor_states'),
+  onMonitorState: (callback) => {
+    void listen<MonitorState[]>('monitor-state', (event) => callback(event.payload));
+  },
+  getWindowPollStats: () => invoke<WindowPollStats>('get_window_poll_stats'),
+
+  // Window title privacy for window-data payloads and detector logs (persisted)
+  getTitlePrivacy: () => invoke<TitlePrivacy>('get_title_privacy'),
+  setTitlePrivacy: (mode) => invoke<void>('set_title_privacy', { mode }),
+
+  // Whether taskbars/docks/panels are collision surfaces (window-data `docks`; persisted)
+  getRainOnDocks: () => invoke<boolean>('get_rain_on_docks'),
+  setRainOnDocks: (enabled) => invoke<void>('set_rain_on_docks', { enabled }),
+
+  // Mega window vs one overlay/background per monitor (persisted); per-monitor overlays
+  // get their own monitor as the desktop and hand airborne drops to each other
+  getWindowMode: () => invoke<WindowMode>('get_window_mode'),
+  setWindowMode: (mode) => invoke<void>('set_window_mode', { mode }),
+  getSurfaceDesktop: () => invoke<VirtualDesktop | null>('get_surface_desktop'),
+  handoffDrops: (drops) => invoke<void>('handoff_drops', { drops }),
+  // Sent to one overlay (emit_to), so listen on this window rather than globally
+  onDropsHandoff: (callback) => {
+    void getCurrentWebviewWindow().listen<HandoffDrop[]>('drops-handoff', (event) => callback(event.payload));
+  },
+
+  // Target-window mode: rain only above one chosen window (persisted by process/class)
+  listTargetWindows: () => invoke<TargetWindowCandidate[]>('list_target_windows'),
+  getTargetWindow: () => invoke<TargetWindowStatus | null>('get_target_window'),
+  setTargetWindow: (id) => invoke<TargetWindowStatus>('set_target_window', { id }),
+  clearTargetWindow: () => invoke<void>('clear_target_window'),
+  onTargetWindow: (callback) => {
+    void listen<TargetWindowStatus>('target-window', (event) => callback(event.payload));
+  },
+
+  // Interaction mode: overlay accepts the mouse while the modifier is held or for a timed session
+  getInteractionStatus: () => invoke<InteractionStatus | null>('get_interaction_status'),
+  startInteraction: (seconds) => invoke<InteractionStatus>('start_interaction', { seconds }),
+  stopInteraction: () => invoke<void>('stop_interaction'),
+  setInteractionModifier: (modifier) => invoke<InteractionStatus>('set_interaction_modifier', { modifier }),
+  onInteractionMode: (callback) => {
+    void listen<InteractionStatus>('interaction-mode', (event) => callback(event.payload));
+  },
+
+  // Pointer position for the umbrella cursor (only streamed while system.umbrellaCursor is on)
+  onCursorPosition: (callback) => {
+    void listen<CursorPosition>('cursor-position', (event) => callback(event.payload));
+  },
+
+  // Custom static obstacles, stored per monitor layout in monitor-relative fractions
+  listObstacles: () => invoke<Obstacle[]>('list_obstacles'),
+  addObstacle: (obstacle) => invoke<Obstacle>('add_obstacle', { obstacle }),
+  updateObstacle: (id, obstacle) => invoke<Obstacle>('update_obstacle', { id, obstacle }),
+  deleteObstacle: (id) => invoke<void>('delete_obstacle', { id }),
+  // Rain-free / rain-only zones, stored per monitor in monitor-relative fractions
+  listRainZones: () => invoke<RainZone[]>('list_rain_zones'),
+  addRainZone: (zone) => invoke<RainZone>('add_rain_zone', { zone }),
+  updateRainZone: (id, zone) => invoke<RainZone>('update_rain_zone', { id, zone }),
+  deleteRainZone: (id) => invoke<void>('delete_rain_zone', { id }),
+  getStaticGeometry: () => invoke<StaticGeometry>('get_static_geometry'),
+
+  // Void mask (run-length, row-major, starting with void) + spawn rows at a grid scale
+  getVoidMask: (gridScale) => invoke<VoidMaskData>('get_void_mask', { gridScale }),
+  onStaticGeometry: (callback) => {
+    void listen<StaticGeometry>('static-geometry', (event) => callback(event.payload));
+  },
+
+  // Layout diagnostics PNG (monitors, void mask, detected + filtered windows)
+  saveDiagnosticsImage: () => invoke<string>('save_diagnostics_image'),
+  getDiagnosticsImage: () => invoke<ArrayBuffer>('get_diagnostics_image'),
+  // Window-detection recording (logs/recordings/*.jsonl) and replay in place of live detection
+  startWindowRecording: () => invoke<string>('start_window_recording'),
+  stopWindowRecording: () => invoke<WindowRecordingSummary | null>('stop_window_recording'),
+  startWindowReplay: (path) => invoke<WindowReplayStatus>('start_window_replay', { path }),
+  setWindowReplayPlaying: (playing) => invoke<WindowReplayStatus>('set_window_replay_playing', { playing }),
+  seekWindowReplay: (positionMs) => invoke<WindowReplayStatus>('seek_window_replay', { positionMs }),
+  stopWindowReplay: () => invoke<void>('stop_window_replay'),
+  getWindowReplayStatus: () => invoke<WindowReplayStatus | null>('get_window_replay_status'),
+  onWindowReplayState: (callback) => {
+    void listen<WindowReplayStatus | null>('window-replay-state', (event) => callback(event.payload));
+  },
   getSystemSpecs: () => invoke<{ cpuModel: string; gpuModel: string; gpuVramGb: number | null; totalRamGb: number }>('get_system_specs'),
 
   // Receive virtual desktop info from main process (event-based)
@@ -64,6 +165,23 @@ window.rainydesk = {
     return listen('window-data', (event) => callback(event.payload));
   },
 
+  // Window lifecycle deltas (Rust only emits these when something actually changes)
+  onWindowOpened: (callback) => {
+    return listen('window-opened', (event) => callback(event.payload));
+  },
+  onWindowClosed: (callback) => {
+    return listen('window-closed', (event) => callback(event.payload));
+  },
+  onWindowMoved: (callback) => {
+    return listen('window-moved', (event) => callback(event.payload));
+  },
+  onWindowResized: (callback) => {
+    return listen('window-resized', (event) => callback(event.payload));
+  },
+  onWindowStateChanged: (callback) => {
+    return listen('window-state-changed', (event) => callback(event.payload));
+  },
+
   getConfig: () => invoke<{ rainEnabled: boolean; intensity: number; volume: number; wind: number }>('get_config'),
 
   // Notify main process of current rainscape (for tray tooltip)
@@ -119,6 +237,9 @@ window.rainydesk = {
   setPanelDetached: (detached) => invoke('set_panel_detached', { detached }),
   snapPanelToTray: () => invoke('snap_panel_to_tray'),
   resizeRainscaper: (width, height) => invoke('resize_rainscaper', { width, height }),
+  // Panel UI scale, persisted in panel-config.json; setting it resizes the panel window
+  getUiScale: () => invoke<number | null>('get_ui_scale'),
+  setUiScale: (scale) => invoke<number>('set_ui_scale', { scale }),
 
   // App version (from tauri.conf.json)
   getVersion: () => getVersion(),
@@ -168,7 +289,7 @@ window.rainydesk = {
 
   // Monitor hot-swap detection (Rust → renderer)
   onMonitorConfigChanged: (callback) => {
-    void listen('monitor-config-changed', () => callback());
+    void listen<MonitorConfigChange>('monitor-config-changed', (event) => callback(event.payload));
   },
 
   // Detect phantom DPI scaling (Intel Iris iGPU + WebView2 bug)